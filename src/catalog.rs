@@ -0,0 +1,1504 @@
+//!
+//! Catalog of all functions the builders can emit.
+//!
+//! Holds name, arity, parameter and return types for every function.
+//! Parameter types are the OpenFormula types and pseudotypes (4, 4.11).
+//!
+
+use std::fmt::{Display, Formatter};
+use std::sync::OnceLock;
+use Category as C;
+use ValueType as T;
+
+/// Value types and pseudotypes used in function signatures.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ValueType {
+    /// Any value.
+    Any,
+    /// Number.
+    Number,
+    /// Number without fractional part.
+    Integer,
+    /// Complex number.
+    Complex,
+    /// Text.
+    Text,
+    /// Logical.
+    Logical,
+    /// Error.
+    Error,
+    /// Single reference.
+    Reference,
+    /// List of references.
+    ReferenceList,
+    /// Array or reference.
+    Array,
+    /// Single value, Number, Logical or Text.
+    Scalar,
+    /// Date as Number or Text.
+    DateParam,
+    /// Time as Number or Text.
+    TimeParam,
+    /// Number or Text.
+    TextOrNumber,
+    /// Search criterion for *IF functions.
+    Criterion,
+    /// Range with field names in the first row.
+    Database,
+    /// Field selector for a database.
+    Field,
+    /// Criteria range for a database.
+    Criteria,
+    /// Sequence of numbers.
+    NumberSequence,
+    /// List of number sequences.
+    NumberSequenceList,
+    /// Sequence of dates.
+    DateSequence,
+    /// Sequence of logical values.
+    LogicalSequence,
+    /// Sequence of complex numbers.
+    ComplexSequence,
+}
+
+/// Function category. Matches the module of the builder functions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Category {
+    /// Bit operations.
+    Bitop,
+    /// Complex numbers.
+    Complex,
+    /// Database functions.
+    Database,
+    /// Date and time.
+    DateTime,
+    /// Engineering, base conversion.
+    Engineering,
+    /// External access.
+    ExtAccess,
+    /// Financial functions.
+    Financial,
+    /// Information about cells and values.
+    Information,
+    /// Logical functions.
+    Logical,
+    /// Lookup and references.
+    Lookup,
+    /// Mathematical functions.
+    Math,
+    /// Matrix functions.
+    Matrix,
+    /// Rounding.
+    Rounding,
+    /// Statistics.
+    Statistic,
+    /// Text functions.
+    Text,
+}
+
+/// Namespace of the function name.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Namespace {
+    /// Functions defined by OpenFormula.
+    OpenFormula,
+    /// COM.MICROSOFT.*
+    Microsoft,
+    /// ORG.OPENOFFICE.*
+    OpenOffice,
+    /// ORG.LIBREOFFICE.*
+    LibreOffice,
+}
+
+impl Namespace {
+    /// Prefix for function names in this namespace.
+    pub fn prefix(&self) -> &'static str {
+        match self {
+            Namespace::OpenFormula => "",
+            Namespace::Microsoft => "COM.MICROSOFT.",
+            Namespace::OpenOffice => "ORG.OPENOFFICE.",
+            Namespace::LibreOffice => "ORG.LIBREOFFICE.",
+        }
+    }
+}
+
+/// Signature and properties of one function.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FunctionInfo {
+    /// Canonical name, including the namespace prefix.
+    pub name: &'static str,
+    /// Namespace.
+    pub namespace: Namespace,
+    /// Minimum number of arguments.
+    pub min_args: usize,
+    /// Maximum number of arguments. None if the function takes any number of arguments.
+    pub max_args: Option<usize>,
+    /// Parameter types. For variadic functions the last type repeats.
+    pub params: &'static [ValueType],
+    /// Return type.
+    pub returns: ValueType,
+    /// Category.
+    pub category: Category,
+    /// Result can change without any change of the arguments.
+    pub volatile: bool,
+}
+
+impl FunctionInfo {
+    /// Name without the namespace prefix.
+    pub fn local_name(&self) -> &'static str {
+        &self.name[self.namespace.prefix().len()..]
+    }
+
+    /// Type of the n-th parameter.
+    pub fn param_type(&self, idx: usize) -> Option<ValueType> {
+        if idx < self.params.len() {
+            Some(self.params[idx])
+        } else if self.max_args.is_none() {
+            self.params.last().copied()
+        } else {
+            None
+        }
+    }
+
+    /// Is this number of arguments valid.
+    pub fn accepts(&self, n: usize) -> bool {
+        n >= self.min_args && self.max_args.is_none_or(|max| n <= max)
+    }
+
+    const fn volatile(mut self) -> Self {
+        self.volatile = true;
+        self
+    }
+}
+
+impl Display for FunctionInfo {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}(", self.name)?;
+        for (i, p) in self.params.iter().enumerate() {
+            if i > 0 {
+                write!(f, ";")?;
+            }
+            if i >= self.min_args {
+                write!(f, "[{:?}]", p)?;
+            } else {
+                write!(f, "{:?}", p)?;
+            }
+        }
+        if self.max_args.is_none() {
+            write!(f, ";...")?;
+        }
+        write!(f, ") -> {:?}", self.returns)
+    }
+}
+
+/// All functions.
+pub fn functions() -> &'static [FunctionInfo] {
+    FUNCTIONS
+}
+
+/// Finds a function by its canonical name. Case insensitive.
+pub fn function_info(name: &str) -> Option<&'static FunctionInfo> {
+    // Index sorted by name, built on first use.
+    static SORTED: OnceLock<Vec<&'static FunctionInfo>> = OnceLock::new();
+    let sorted = SORTED.get_or_init(|| {
+        let mut v: Vec<_> = FUNCTIONS.iter().collect();
+        v.sort_by_key(|v| v.name);
+        v
+    });
+    sorted
+        .binary_search_by(|v| {
+            v.name
+                .bytes()
+                .cmp(name.bytes().map(|b| b.to_ascii_uppercase()))
+        })
+        .ok()
+        .map(|i| sorted[i])
+}
+
+// Marks a variadic function.
+const VAR: usize = usize::MAX;
+
+const fn starts_with(s: &str, prefix: &str) -> bool {
+    let s = s.as_bytes();
+    let prefix = prefix.as_bytes();
+    if s.len() < prefix.len() {
+        return false;
+    }
+    let mut i = 0;
+    while i < prefix.len() {
+        if s[i] != prefix[i] {
+            return false;
+        }
+        i += 1;
+    }
+    true
+}
+
+const fn f(
+    category: Category,
+    name: &'static str,
+    min_args: usize,
+    max_args: usize,
+    params: &'static [ValueType],
+    returns: ValueType,
+) -> FunctionInfo {
+    let namespace = if starts_with(name, "COM.MICROSOFT.") {
+        Namespace::Microsoft
+    } else if starts_with(name, "ORG.OPENOFFICE.") {
+        Namespace::OpenOffice
+    } else if starts_with(name, "ORG.LIBREOFFICE.") {
+        Namespace::LibreOffice
+    } else {
+        Namespace::OpenFormula
+    };
+    FunctionInfo {
+        name,
+        namespace,
+        min_args,
+        max_args: if max_args == VAR {
+            None
+        } else {
+            Some(max_args)
+        },
+        params,
+        returns,
+        category,
+        volatile: false,
+    }
+}
+
+const NUM: &[ValueType] = &[T::Number];
+const NUM2: &[ValueType] = &[T::Number, T::Number];
+const NUM3: &[ValueType] = &[T::Number, T::Number, T::Number];
+const NUM_SEQ: &[ValueType] = &[T::NumberSequenceList];
+const ANY: &[ValueType] = &[T::Any];
+const TEXT: &[ValueType] = &[T::Text];
+const COMPLEX: &[ValueType] = &[T::Complex];
+const ARRAY2: &[ValueType] = &[T::Array, T::Array];
+const DB: &[ValueType] = &[T::Database, T::Field, T::Criteria];
+const DATE: &[ValueType] = &[T::DateParam];
+const TIME: &[ValueType] = &[T::TimeParam];
+const COUP: &[ValueType] = &[T::DateParam, T::DateParam, T::Integer, T::Integer];
+const TBILL: &[ValueType] = &[T::DateParam, T::DateParam, T::Number];
+const SEC_BASIS: &[ValueType] = &[T::DateParam, T::DateParam, T::Number, T::Number, T::Integer];
+const SEC_ISSUE: &[ValueType] = &[
+    T::DateParam,
+    T::DateParam,
+    T::DateParam,
+    T::Number,
+    T::Number,
+    T::Integer,
+];
+const BOND: &[ValueType] = &[
+    T::DateParam,
+    T::DateParam,
+    T::Number,
+    T::Number,
+    T::Number,
+    T::Integer,
+    T::Integer,
+];
+const DURATION: &[ValueType] = &[
+    T::DateParam,
+    T::DateParam,
+    T::Number,
+    T::Number,
+    T::Integer,
+    T::Integer,
+];
+const ODDF: &[ValueType] = &[
+    T::DateParam,
+    T::DateParam,
+    T::DateParam,
+    T::DateParam,
+    T::Number,
+    T::Number,
+    T::Number,
+    T::Integer,
+    T::Integer,
+];
+const ODDL: &[ValueType] = &[
+    T::DateParam,
+    T::DateParam,
+    T::DateParam,
+    T::Number,
+    T::Number,
+    T::Number,
+    T::Integer,
+    T::Integer,
+];
+const AMOR: &[ValueType] = &[
+    T::Number,
+    T::DateParam,
+    T::DateParam,
+    T::Number,
+    T::Integer,
+    T::Number,
+    T::Integer,
+];
+const TVM: &[ValueType] = &[T::Number, T::Number, T::Number, T::Number, T::Number];
+const TVM_PER: &[ValueType] = &[
+    T::Number,
+    T::Number,
+    T::Number,
+    T::Number,
+    T::Number,
+    T::Number,
+];
+const CUM: &[ValueType] = &[
+    T::Number,
+    T::Integer,
+    T::Number,
+    T::Integer,
+    T::Integer,
+    T::Integer,
+];
+const BASE2: &[ValueType] = &[T::TextOrNumber, T::Integer];
+const DEC2: &[ValueType] = &[T::Number, T::Integer];
+const ROUND: &[ValueType] = &[T::Number, T::Integer];
+const FIND: &[ValueType] = &[T::Text, T::Text, T::Integer];
+const LEFT: &[ValueType] = &[T::Text, T::Integer];
+const MID: &[ValueType] = &[T::Text, T::Integer, T::Integer];
+const REPLACE: &[ValueType] = &[T::Text, T::Integer, T::Integer, T::Text];
+const IFS: &[ValueType] = &[T::Reference, T::Reference, T::Criterion];
+const IF: &[ValueType] = &[T::ReferenceList, T::Criterion, T::Reference];
+const LOOKUP: &[ValueType] = &[T::Any, T::Array, T::Integer, T::Logical];
+const REGRESSION: &[ValueType] = &[T::Array, T::Array, T::Logical, T::Logical];
+const GROWTH: &[ValueType] = &[T::Array, T::Array, T::Array, T::Logical];
+
+static FUNCTIONS: &[FunctionInfo] = &[
+    // bitop
+    f(
+        C::Bitop,
+        "BITAND",
+        2,
+        2,
+        &[T::Integer, T::Integer],
+        T::Number,
+    ),
+    f(
+        C::Bitop,
+        "BITLSHIFT",
+        2,
+        2,
+        &[T::Integer, T::Integer],
+        T::Number,
+    ),
+    f(
+        C::Bitop,
+        "BITOR",
+        2,
+        2,
+        &[T::Integer, T::Integer],
+        T::Number,
+    ),
+    f(
+        C::Bitop,
+        "BITRSHIFT",
+        2,
+        2,
+        &[T::Integer, T::Integer],
+        T::Number,
+    ),
+    f(
+        C::Bitop,
+        "BITXOR",
+        2,
+        2,
+        &[T::Integer, T::Integer],
+        T::Number,
+    ),
+    // complex
+    f(
+        C::Complex,
+        "COMPLEX",
+        2,
+        3,
+        &[T::Number, T::Number, T::Text],
+        T::Complex,
+    ),
+    f(C::Complex, "IMABS", 1, 1, COMPLEX, T::Number),
+    f(C::Complex, "IMAGINARY", 1, 1, COMPLEX, T::Number),
+    f(C::Complex, "IMARGUMENT", 1, 1, COMPLEX, T::Number),
+    f(C::Complex, "IMCONJUGATE", 1, 1, COMPLEX, T::Complex),
+    f(C::Complex, "IMCOS", 1, 1, COMPLEX, T::Complex),
+    f(C::Complex, "IMCOSH", 1, 1, COMPLEX, T::Complex),
+    f(C::Complex, "IMCOT", 1, 1, COMPLEX, T::Complex),
+    f(C::Complex, "IMCSC", 1, 1, COMPLEX, T::Complex),
+    f(C::Complex, "IMCSCH", 1, 1, COMPLEX, T::Complex),
+    f(
+        C::Complex,
+        "IMDIV",
+        2,
+        2,
+        &[T::Complex, T::Complex],
+        T::Complex,
+    ),
+    f(C::Complex, "IMEXP", 1, 1, COMPLEX, T::Complex),
+    f(C::Complex, "IMLN", 1, 1, COMPLEX, T::Complex),
+    f(C::Complex, "IMLOG10", 1, 1, COMPLEX, T::Complex),
+    f(C::Complex, "IMLOG2", 1, 1, COMPLEX, T::Complex),
+    f(
+        C::Complex,
+        "IMPOWER",
+        2,
+        2,
+        &[T::Complex, T::Number],
+        T::Complex,
+    ),
+    f(
+        C::Complex,
+        "IMPRODUCT",
+        1,
+        VAR,
+        &[T::ComplexSequence],
+        T::Complex,
+    ),
+    f(C::Complex, "IMREAL", 1, 1, COMPLEX, T::Number),
+    f(C::Complex, "IMSEC", 1, 1, COMPLEX, T::Complex),
+    f(C::Complex, "IMSECH", 1, 1, COMPLEX, T::Complex),
+    f(C::Complex, "IMSIN", 1, 1, COMPLEX, T::Complex),
+    f(C::Complex, "IMSINH", 1, 1, COMPLEX, T::Complex),
+    f(C::Complex, "IMSQRT", 1, 1, COMPLEX, T::Complex),
+    f(
+        C::Complex,
+        "IMSUB",
+        2,
+        2,
+        &[T::Complex, T::Complex],
+        T::Complex,
+    ),
+    f(
+        C::Complex,
+        "IMSUM",
+        1,
+        VAR,
+        &[T::ComplexSequence],
+        T::Complex,
+    ),
+    f(C::Complex, "IMTAN", 1, 1, COMPLEX, T::Complex),
+    // database
+    f(C::Database, "DAVERAGE", 3, 3, DB, T::Number),
+    f(C::Database, "DCOUNT", 2, 3, DB, T::Number),
+    f(C::Database, "DCOUNTA", 2, 3, DB, T::Number),
+    f(C::Database, "DGET", 3, 3, DB, T::Any),
+    f(C::Database, "DMAX", 3, 3, DB, T::Number),
+    f(C::Database, "DMIN", 3, 3, DB, T::Number),
+    f(C::Database, "DPRODUCT", 3, 3, DB, T::Number),
+    f(C::Database, "DSTDEV", 3, 3, DB, T::Number),
+    f(C::Database, "DSTDEVP", 3, 3, DB, T::Number),
+    f(C::Database, "DSUM", 3, 3, DB, T::Number),
+    f(C::Database, "DVAR", 3, 3, DB, T::Number),
+    f(C::Database, "DVARP", 3, 3, DB, T::Number),
+    // date
+    f(
+        C::DateTime,
+        "DATE",
+        3,
+        3,
+        &[T::Integer, T::Integer, T::Integer],
+        T::Number,
+    ),
+    f(
+        C::DateTime,
+        "DATEDIF",
+        3,
+        3,
+        &[T::DateParam, T::DateParam, T::Text],
+        T::Number,
+    ),
+    f(C::DateTime, "DATEVALUE", 1, 1, TEXT, T::Number),
+    f(C::DateTime, "DAY", 1, 1, DATE, T::Number),
+    f(
+        C::DateTime,
+        "DAYS",
+        2,
+        2,
+        &[T::DateParam, T::DateParam],
+        T::Number,
+    ),
+    f(
+        C::DateTime,
+        "DAYS360",
+        2,
+        3,
+        &[T::DateParam, T::DateParam, T::Logical],
+        T::Number,
+    ),
+    f(
+        C::DateTime,
+        "EDATE",
+        2,
+        2,
+        &[T::DateParam, T::Integer],
+        T::Number,
+    ),
+    f(
+        C::DateTime,
+        "EOMONTH",
+        2,
+        2,
+        &[T::DateParam, T::Integer],
+        T::Number,
+    ),
+    f(C::DateTime, "HOUR", 1, 1, TIME, T::Number),
+    f(C::DateTime, "ISOWEEKNUM", 1, 1, DATE, T::Number),
+    f(C::DateTime, "MINUTE", 1, 1, TIME, T::Number),
+    f(C::DateTime, "MONTH", 1, 1, DATE, T::Number),
+    f(
+        C::DateTime,
+        "NETWORKDAYS",
+        2,
+        4,
+        &[
+            T::DateParam,
+            T::DateParam,
+            T::DateSequence,
+            T::LogicalSequence,
+        ],
+        T::Number,
+    ),
+    f(C::DateTime, "NOW", 0, 0, &[], T::Number).volatile(),
+    f(C::DateTime, "SECOND", 1, 1, TIME, T::Number),
+    f(C::DateTime, "TIME", 3, 3, NUM3, T::Number),
+    f(C::DateTime, "TIMEVALUE", 1, 1, TEXT, T::Number),
+    f(C::DateTime, "TODAY", 0, 0, &[], T::Number).volatile(),
+    f(
+        C::DateTime,
+        "WEEKDAY",
+        1,
+        2,
+        &[T::DateParam, T::Integer],
+        T::Number,
+    ),
+    f(
+        C::DateTime,
+        "WEEKNUM",
+        1,
+        2,
+        &[T::DateParam, T::Integer],
+        T::Number,
+    ),
+    f(
+        C::DateTime,
+        "WORKDAY",
+        2,
+        4,
+        &[
+            T::DateParam,
+            T::Integer,
+            T::DateSequence,
+            T::LogicalSequence,
+        ],
+        T::Number,
+    ),
+    f(C::DateTime, "YEAR", 1, 1, DATE, T::Number),
+    f(
+        C::DateTime,
+        "YEARFRAC",
+        2,
+        3,
+        &[T::DateParam, T::DateParam, T::Integer],
+        T::Number,
+    ),
+    // engineering
+    f(C::Engineering, "ARABIC", 1, 1, TEXT, T::Number),
+    f(
+        C::Engineering,
+        "BASE",
+        2,
+        3,
+        &[T::Integer, T::Integer, T::Integer],
+        T::Text,
+    ),
+    f(
+        C::Engineering,
+        "BIN2DEC",
+        1,
+        1,
+        &[T::TextOrNumber],
+        T::Number,
+    ),
+    f(C::Engineering, "BIN2HEX", 1, 2, BASE2, T::Text),
+    f(C::Engineering, "BIN2OCT", 1, 2, BASE2, T::Text),
+    f(C::Engineering, "DEC2BIN", 1, 2, DEC2, T::Text),
+    f(C::Engineering, "DEC2HEX", 1, 2, DEC2, T::Text),
+    f(C::Engineering, "DEC2OCT", 1, 2, DEC2, T::Text),
+    f(
+        C::Engineering,
+        "DECIMAL",
+        2,
+        2,
+        &[T::Text, T::Integer],
+        T::Number,
+    ),
+    f(C::Engineering, "HEX2BIN", 1, 2, BASE2, T::Text),
+    f(
+        C::Engineering,
+        "HEX2DEC",
+        1,
+        1,
+        &[T::TextOrNumber],
+        T::Number,
+    ),
+    f(C::Engineering, "HEX2OCT", 1, 2, BASE2, T::Text),
+    f(C::Engineering, "OCT2BIN", 1, 2, BASE2, T::Text),
+    f(
+        C::Engineering,
+        "OCT2DEC",
+        1,
+        1,
+        &[T::TextOrNumber],
+        T::Number,
+    ),
+    f(C::Engineering, "OCT2HEX", 1, 2, BASE2, T::Text),
+    f(
+        C::Engineering,
+        "ROMAN",
+        1,
+        2,
+        &[T::Integer, T::Integer],
+        T::Text,
+    ),
+    // extaccess
+    f(
+        C::ExtAccess,
+        "DDE",
+        3,
+        4,
+        &[T::Text, T::Text, T::Text, T::Integer],
+        T::Any,
+    ),
+    f(
+        C::ExtAccess,
+        "HYPERLINK",
+        1,
+        2,
+        &[T::Text, T::Scalar],
+        T::Scalar,
+    ),
+    // financial
+    f(
+        C::Financial,
+        "ACCRINT",
+        6,
+        8,
+        &[
+            T::DateParam,
+            T::DateParam,
+            T::DateParam,
+            T::Number,
+            T::Number,
+            T::Integer,
+            T::Integer,
+            T::Logical,
+        ],
+        T::Number,
+    ),
+    f(
+        C::Financial,
+        "ACCRINTM",
+        3,
+        5,
+        &[T::DateParam, T::DateParam, T::Number, T::Number, T::Integer],
+        T::Number,
+    ),
+    f(C::Financial, "AMORDEGRC", 6, 7, AMOR, T::Number),
+    f(C::Financial, "AMORLINC", 6, 7, AMOR, T::Number),
+    f(C::Financial, "COUPDAYBS", 3, 4, COUP, T::Number),
+    f(C::Financial, "COUPDAYS", 3, 4, COUP, T::Number),
+    f(C::Financial, "COUPDAYSNC", 3, 4, COUP, T::Number),
+    f(C::Financial, "COUPNCD", 3, 4, COUP, T::Number),
+    f(C::Financial, "COUPNUM", 3, 4, COUP, T::Number),
+    f(C::Financial, "COUPPCD", 3, 4, COUP, T::Number),
+    f(C::Financial, "CUMIPMT", 6, 6, CUM, T::Number),
+    f(C::Financial, "CUMPRINC", 6, 6, CUM, T::Number),
+    f(C::Financial, "DB", 4, 5, TVM, T::Number),
+    f(C::Financial, "DDB", 4, 5, TVM, T::Number),
+    f(C::Financial, "DISC", 4, 5, SEC_BASIS, T::Number),
+    f(C::Financial, "DURATION", 5, 6, DURATION, T::Number),
+    f(
+        C::Financial,
+        "EFFECT",
+        2,
+        2,
+        &[T::Number, T::Integer],
+        T::Number,
+    ),
+    f(C::Financial, "FV", 3, 5, TVM, T::Number),
+    f(C::Financial, "INTRATE", 4, 5, SEC_BASIS, T::Number),
+    f(C::Financial, "IPMT", 4, 6, TVM_PER, T::Number),
+    f(
+        C::Financial,
+        "IRR",
+        1,
+        2,
+        &[T::NumberSequence, T::Number],
+        T::Number,
+    ),
+    f(C::Financial, "ISPMT", 4, 4, TVM, T::Number),
+    f(C::Financial, "MDURATION", 5, 6, DURATION, T::Number),
+    f(
+        C::Financial,
+        "MIRR",
+        3,
+        3,
+        &[T::NumberSequence, T::Number, T::Number],
+        T::Number,
+    ),
+    f(
+        C::Financial,
+        "NOMINAL",
+        2,
+        2,
+        &[T::Number, T::Integer],
+        T::Number,
+    ),
+    f(C::Financial, "NPER", 3, 5, TVM, T::Number),
+    f(
+        C::Financial,
+        "NPV",
+        2,
+        VAR,
+        &[T::Number, T::NumberSequenceList],
+        T::Number,
+    ),
+    f(C::Financial, "ODDFPRICE", 8, 9, ODDF, T::Number),
+    f(C::Financial, "ODDFYIELD", 8, 9, ODDF, T::Number),
+    f(C::Financial, "ODDLPRICE", 7, 8, ODDL, T::Number),
+    f(C::Financial, "ODDLYIELD", 7, 8, ODDL, T::Number),
+    f(C::Financial, "PDURATION", 3, 3, NUM3, T::Number),
+    f(C::Financial, "PMT", 3, 5, TVM, T::Number),
+    f(C::Financial, "PPMT", 4, 6, TVM_PER, T::Number),
+    f(C::Financial, "PRICE", 6, 7, BOND, T::Number),
+    f(C::Financial, "PRICEDISC", 4, 5, SEC_BASIS, T::Number),
+    f(C::Financial, "PRICEMAT", 5, 6, SEC_ISSUE, T::Number),
+    f(C::Financial, "PV", 3, 5, TVM, T::Number),
+    f(C::Financial, "RATE", 3, 6, TVM_PER, T::Number),
+    f(C::Financial, "RECEIVED", 4, 5, SEC_BASIS, T::Number),
+    f(C::Financial, "RRI", 3, 3, NUM3, T::Number),
+    f(C::Financial, "SLN", 3, 3, NUM3, T::Number),
+    f(C::Financial, "SYD", 4, 4, TVM, T::Number),
+    f(C::Financial, "TBILLEQ", 3, 3, TBILL, T::Number),
+    f(C::Financial, "TBILLPRICE", 3, 3, TBILL, T::Number),
+    f(C::Financial, "TBILLYIELD", 3, 3, TBILL, T::Number),
+    f(
+        C::Financial,
+        "VDB",
+        5,
+        7,
+        &[
+            T::Number,
+            T::Number,
+            T::Number,
+            T::Number,
+            T::Number,
+            T::Number,
+            T::Logical,
+        ],
+        T::Number,
+    ),
+    f(
+        C::Financial,
+        "XIRR",
+        2,
+        3,
+        &[T::NumberSequence, T::DateSequence, T::Number],
+        T::Number,
+    ),
+    f(
+        C::Financial,
+        "XNPV",
+        3,
+        3,
+        &[T::Number, T::NumberSequence, T::DateSequence],
+        T::Number,
+    ),
+    f(C::Financial, "YIELD", 6, 7, BOND, T::Number),
+    f(C::Financial, "YIELDDISC", 4, 5, SEC_BASIS, T::Number),
+    f(C::Financial, "YIELDMAT", 5, 6, SEC_ISSUE, T::Number),
+    // information
+    f(
+        C::Information,
+        "AREAS",
+        1,
+        1,
+        &[T::ReferenceList],
+        T::Number,
+    ),
+    f(
+        C::Information,
+        "CELL",
+        1,
+        2,
+        &[T::Text, T::Reference],
+        T::Any,
+    )
+    .volatile(),
+    f(C::Information, "COLUMN", 0, 1, &[T::Reference], T::Number),
+    f(C::Information, "COLUMNS", 1, 1, &[T::Array], T::Number),
+    f(C::Information, "COUNT", 1, VAR, ANY, T::Number),
+    f(C::Information, "COUNTA", 1, VAR, ANY, T::Number),
+    f(
+        C::Information,
+        "COUNTBLANK",
+        1,
+        1,
+        &[T::ReferenceList],
+        T::Number,
+    ),
+    f(
+        C::Information,
+        "COUNTIF",
+        2,
+        2,
+        &[T::ReferenceList, T::Criterion],
+        T::Number,
+    ),
+    f(
+        C::Information,
+        "COUNTIFS",
+        2,
+        VAR,
+        &[T::Reference, T::Criterion],
+        T::Number,
+    ),
+    f(C::Information, "ERROR.TYPE", 1, 1, &[T::Error], T::Number),
+    f(C::Information, "FORMULA", 1, 1, &[T::Reference], T::Text),
+    f(C::Information, "INFO", 1, 1, TEXT, T::Any).volatile(),
+    f(C::Information, "ISBLANK", 1, 1, ANY, T::Logical),
+    f(C::Information, "ISERR", 1, 1, ANY, T::Logical),
+    f(C::Information, "ISERROR", 1, 1, ANY, T::Logical),
+    f(C::Information, "ISEVEN", 1, 1, NUM, T::Logical),
+    f(
+        C::Information,
+        "ISFORMULA",
+        1,
+        1,
+        &[T::Reference],
+        T::Logical,
+    ),
+    f(C::Information, "ISLOGICAL", 1, 1, ANY, T::Logical),
+    f(C::Information, "ISNA", 1, 1, ANY, T::Logical),
+    f(C::Information, "ISNONTEXT", 1, 1, ANY, T::Logical),
+    f(C::Information, "ISNUMBER", 1, 1, ANY, T::Logical),
+    f(C::Information, "ISODD", 1, 1, NUM, T::Logical),
+    f(C::Information, "ISREF", 1, 1, ANY, T::Logical),
+    f(C::Information, "ISTEXT", 1, 1, ANY, T::Logical),
+    f(C::Information, "N", 1, 1, ANY, T::Number),
+    f(C::Information, "NA", 0, 0, &[], T::Error),
+    f(
+        C::Information,
+        "NUMBERVALUE",
+        1,
+        3,
+        &[T::Text, T::Text, T::Text],
+        T::Number,
+    ),
+    f(C::Information, "ROW", 0, 1, &[T::Reference], T::Number),
+    f(C::Information, "ROWS", 1, 1, &[T::Array], T::Number),
+    f(C::Information, "SHEET", 0, 1, &[T::Any], T::Number),
+    f(
+        C::Information,
+        "SHEETS",
+        0,
+        1,
+        &[T::ReferenceList],
+        T::Number,
+    ),
+    f(C::Information, "TYPE", 1, 1, ANY, T::Number),
+    f(C::Information, "VALUE", 1, 1, TEXT, T::Number),
+    // logical
+    f(C::Logical, "AND", 1, VAR, &[T::LogicalSequence], T::Logical),
+    f(C::Logical, "FALSE", 0, 0, &[], T::Logical),
+    f(
+        C::Logical,
+        "IF",
+        1,
+        3,
+        &[T::Logical, T::Any, T::Any],
+        T::Any,
+    ),
+    f(C::Logical, "IFERROR", 2, 2, &[T::Any, T::Any], T::Any),
+    f(C::Logical, "IFNA", 2, 2, &[T::Any, T::Any], T::Any),
+    f(C::Logical, "NOT", 1, 1, &[T::Logical], T::Logical),
+    f(C::Logical, "OR", 1, VAR, &[T::LogicalSequence], T::Logical),
+    f(C::Logical, "TRUE", 0, 0, &[], T::Logical),
+    f(C::Logical, "XOR", 1, VAR, &[T::LogicalSequence], T::Logical),
+    // lookup
+    f(
+        C::Lookup,
+        "ADDRESS",
+        2,
+        5,
+        &[T::Integer, T::Integer, T::Integer, T::Logical, T::Text],
+        T::Text,
+    ),
+    f(C::Lookup, "CHOOSE", 2, VAR, &[T::Integer, T::Any], T::Any),
+    f(
+        C::Lookup,
+        "GETPIVOTDATA",
+        2,
+        VAR,
+        &[T::Text, T::Reference, T::Text, T::Scalar],
+        T::Any,
+    ),
+    f(C::Lookup, "HLOOKUP", 3, 4, LOOKUP, T::Any),
+    f(
+        C::Lookup,
+        "INDEX",
+        1,
+        4,
+        &[T::Array, T::Integer, T::Integer, T::Integer],
+        T::Any,
+    ),
+    f(
+        C::Lookup,
+        "INDIRECT",
+        1,
+        2,
+        &[T::Text, T::Logical],
+        T::Reference,
+    )
+    .volatile(),
+    f(
+        C::Lookup,
+        "LOOKUP",
+        2,
+        3,
+        &[T::Any, T::Array, T::Array],
+        T::Any,
+    ),
+    f(
+        C::Lookup,
+        "MATCH",
+        2,
+        3,
+        &[T::Scalar, T::Array, T::Integer],
+        T::Number,
+    ),
+    f(
+        C::Lookup,
+        "MULTIPLE.OPERATIONS",
+        3,
+        5,
+        &[
+            T::Reference,
+            T::Reference,
+            T::Reference,
+            T::Reference,
+            T::Reference,
+        ],
+        T::Any,
+    ),
+    f(
+        C::Lookup,
+        "OFFSET",
+        3,
+        5,
+        &[T::Reference, T::Integer, T::Integer, T::Integer, T::Integer],
+        T::Reference,
+    )
+    .volatile(),
+    f(C::Lookup, "VLOOKUP", 3, 4, LOOKUP, T::Any),
+    // math
+    f(C::Math, "ABS", 1, 1, NUM, T::Number),
+    f(C::Math, "ACOS", 1, 1, NUM, T::Number),
+    f(C::Math, "ACOSH", 1, 1, NUM, T::Number),
+    f(C::Math, "ACOT", 1, 1, NUM, T::Number),
+    f(C::Math, "ACOTH", 1, 1, NUM, T::Number),
+    f(C::Math, "ASIN", 1, 1, NUM, T::Number),
+    f(C::Math, "ASINH", 1, 1, NUM, T::Number),
+    f(C::Math, "ATAN", 1, 1, NUM, T::Number),
+    f(C::Math, "ATAN2", 2, 2, NUM2, T::Number),
+    f(C::Math, "ATANH", 1, 1, NUM, T::Number),
+    f(
+        C::Math,
+        "BESSELI",
+        2,
+        2,
+        &[T::Number, T::Integer],
+        T::Number,
+    ),
+    f(
+        C::Math,
+        "BESSELJ",
+        2,
+        2,
+        &[T::Number, T::Integer],
+        T::Number,
+    ),
+    f(
+        C::Math,
+        "BESSELK",
+        2,
+        2,
+        &[T::Number, T::Integer],
+        T::Number,
+    ),
+    f(
+        C::Math,
+        "BESSELY",
+        2,
+        2,
+        &[T::Number, T::Integer],
+        T::Number,
+    ),
+    f(
+        C::Math,
+        "COMBIN",
+        2,
+        2,
+        &[T::Integer, T::Integer],
+        T::Number,
+    ),
+    f(
+        C::Math,
+        "COMBINA",
+        2,
+        2,
+        &[T::Integer, T::Integer],
+        T::Number,
+    ),
+    f(
+        C::Math,
+        "CONVERT",
+        3,
+        3,
+        &[T::Number, T::Text, T::Text],
+        T::Number,
+    ),
+    f(C::Math, "COS", 1, 1, NUM, T::Number),
+    f(C::Math, "COSH", 1, 1, NUM, T::Number),
+    f(C::Math, "COT", 1, 1, NUM, T::Number),
+    f(C::Math, "COTH", 1, 1, NUM, T::Number),
+    f(C::Math, "CSC", 1, 1, NUM, T::Number),
+    f(C::Math, "CSCH", 1, 1, NUM, T::Number),
+    f(C::Math, "DEGREES", 1, 1, NUM, T::Number),
+    f(C::Math, "DELTA", 1, 2, NUM2, T::Number),
+    f(C::Math, "ERF", 1, 2, NUM2, T::Number),
+    f(C::Math, "ERFC", 1, 1, NUM, T::Number),
+    f(C::Math, "EVEN", 1, 1, NUM, T::Number),
+    f(C::Math, "EXP", 1, 1, NUM, T::Number),
+    f(C::Math, "FACT", 1, 1, &[T::Integer], T::Number),
+    f(C::Math, "FACTDOUBLE", 1, 1, &[T::Integer], T::Number),
+    f(C::Math, "GAMMA", 1, 1, NUM, T::Number),
+    f(C::Math, "GAMMALN", 1, 1, NUM, T::Number),
+    f(C::Math, "GCD", 1, VAR, NUM_SEQ, T::Number),
+    f(C::Math, "GESTEP", 1, 2, NUM2, T::Number),
+    f(C::Math, "LCM", 1, VAR, NUM_SEQ, T::Number),
+    f(C::Math, "LN", 1, 1, NUM, T::Number),
+    f(C::Math, "LOG", 1, 2, NUM2, T::Number),
+    f(C::Math, "LOG10", 1, 1, NUM, T::Number),
+    f(C::Math, "MOD", 2, 2, NUM2, T::Number),
+    f(C::Math, "MULTINOMIAL", 1, VAR, NUM_SEQ, T::Number),
+    f(C::Math, "ODD", 1, 1, NUM, T::Number),
+    f(C::Math, "PI", 0, 0, &[], T::Number),
+    f(C::Math, "POWER", 2, 2, NUM2, T::Number),
+    f(C::Math, "PRODUCT", 1, VAR, NUM_SEQ, T::Number),
+    f(C::Math, "QUOTIENT", 2, 2, NUM2, T::Number),
+    f(C::Math, "RADIANS", 1, 1, NUM, T::Number),
+    f(C::Math, "RAND", 0, 0, &[], T::Number).volatile(),
+    f(
+        C::Math,
+        "RANDBETWEEN",
+        2,
+        2,
+        &[T::Integer, T::Integer],
+        T::Number,
+    )
+    .volatile(),
+    f(C::Math, "SEC", 1, 1, NUM, T::Number),
+    f(C::Math, "SECH", 1, 1, NUM, T::Number),
+    f(
+        C::Math,
+        "SERIESSUM",
+        4,
+        4,
+        &[T::Number, T::Number, T::Number, T::NumberSequence],
+        T::Number,
+    ),
+    f(C::Math, "SIGN", 1, 1, NUM, T::Number),
+    f(C::Math, "SIN", 1, 1, NUM, T::Number),
+    f(C::Math, "SINH", 1, 1, NUM, T::Number),
+    f(C::Math, "SQRT", 1, 1, NUM, T::Number),
+    f(C::Math, "SQRTPI", 1, 1, NUM, T::Number),
+    f(
+        C::Math,
+        "SUBTOTAL",
+        2,
+        VAR,
+        &[T::Integer, T::NumberSequenceList],
+        T::Number,
+    ),
+    f(C::Math, "SUM", 1, VAR, NUM_SEQ, T::Number),
+    f(C::Math, "SUMIF", 2, 3, IF, T::Number),
+    f(C::Math, "SUMIFS", 3, VAR, IFS, T::Number),
+    f(C::Math, "SUMPRODUCT", 1, VAR, &[T::Array], T::Number),
+    f(C::Math, "SUMX2MY2", 2, 2, ARRAY2, T::Number),
+    f(C::Math, "SUMX2PY2", 2, 2, ARRAY2, T::Number),
+    f(C::Math, "SUMXMY2", 2, 2, ARRAY2, T::Number),
+    f(C::Math, "TAN", 1, 1, NUM, T::Number),
+    f(C::Math, "TANH", 1, 1, NUM, T::Number),
+    // matrix
+    f(C::Matrix, "MDETERM", 1, 1, &[T::Array], T::Number),
+    f(C::Matrix, "MINVERSE", 1, 1, &[T::Array], T::Array),
+    f(C::Matrix, "MMULT", 2, 2, ARRAY2, T::Array),
+    f(C::Matrix, "MUNIT", 1, 1, &[T::Integer], T::Array),
+    f(C::Matrix, "TRANSPOSE", 1, 1, &[T::Array], T::Array),
+    // rounding
+    f(C::Rounding, "CEILING", 1, 3, NUM3, T::Number),
+    f(C::Rounding, "FLOOR", 1, 3, NUM3, T::Number),
+    f(C::Rounding, "INT", 1, 1, NUM, T::Number),
+    f(C::Rounding, "MROUND", 2, 2, NUM2, T::Number),
+    f(C::Rounding, "ROUND", 1, 2, ROUND, T::Number),
+    f(C::Rounding, "ROUNDDOWN", 1, 2, ROUND, T::Number),
+    f(C::Rounding, "ROUNDUP", 1, 2, ROUND, T::Number),
+    f(C::Rounding, "TRUNC", 1, 2, ROUND, T::Number),
+    // statistic
+    f(C::Statistic, "AVEDEV", 1, VAR, NUM_SEQ, T::Number),
+    f(C::Statistic, "AVERAGE", 1, VAR, NUM_SEQ, T::Number),
+    f(C::Statistic, "AVERAGEA", 1, VAR, ANY, T::Number),
+    f(C::Statistic, "AVERAGEIF", 2, 3, IF, T::Number),
+    f(C::Statistic, "AVERAGEIFS", 3, VAR, IFS, T::Number),
+    f(
+        C::Statistic,
+        "BETADIST",
+        3,
+        6,
+        &[
+            T::Number,
+            T::Number,
+            T::Number,
+            T::Number,
+            T::Number,
+            T::Logical,
+        ],
+        T::Number,
+    ),
+    f(C::Statistic, "BETAINV", 3, 5, TVM, T::Number),
+    f(
+        C::Statistic,
+        "BINOM.DIST.RANGE",
+        3,
+        4,
+        &[T::Integer, T::Number, T::Integer, T::Integer],
+        T::Number,
+    ),
+    f(
+        C::Statistic,
+        "BINOMDIST",
+        4,
+        4,
+        &[T::Integer, T::Integer, T::Number, T::Logical],
+        T::Number,
+    ),
+    f(
+        C::Statistic,
+        "CHISQDIST",
+        2,
+        3,
+        &[T::Number, T::Number, T::Logical],
+        T::Number,
+    ),
+    f(C::Statistic, "CHISQINV", 2, 2, NUM2, T::Number),
+    f(
+        C::Statistic,
+        "COM.MICROSOFT.CHISQ.TEST",
+        2,
+        2,
+        ARRAY2,
+        T::Number,
+    ),
+    f(
+        C::Statistic,
+        "COM.MICROSOFT.CONFIDENCE.NORM",
+        3,
+        3,
+        NUM3,
+        T::Number,
+    ),
+    f(
+        C::Statistic,
+        "COM.MICROSOFT.CONFIDENCE.T",
+        3,
+        3,
+        NUM3,
+        T::Number,
+    ),
+    f(C::Statistic, "CONFIDENCE", 3, 3, NUM3, T::Number),
+    f(C::Statistic, "CORREL", 2, 2, ARRAY2, T::Number),
+    f(C::Statistic, "COVAR", 2, 2, ARRAY2, T::Number),
+    f(C::Statistic, "CRITBINOM", 3, 3, NUM3, T::Number),
+    f(C::Statistic, "DEVSQ", 1, VAR, NUM_SEQ, T::Number),
+    f(
+        C::Statistic,
+        "EXPONDIST",
+        2,
+        3,
+        &[T::Number, T::Number, T::Logical],
+        T::Number,
+    ),
+    f(
+        C::Statistic,
+        "FDIST",
+        3,
+        4,
+        &[T::Number, T::Number, T::Number, T::Logical],
+        T::Number,
+    ),
+    f(C::Statistic, "FINV", 3, 3, NUM3, T::Number),
+    f(C::Statistic, "FISHER", 1, 1, NUM, T::Number),
+    f(C::Statistic, "FISHERINV", 1, 1, NUM, T::Number),
+    f(
+        C::Statistic,
+        "FORECAST",
+        3,
+        3,
+        &[T::Number, T::Array, T::Array],
+        T::Number,
+    ),
+    f(
+        C::Statistic,
+        "FREQUENCY",
+        2,
+        2,
+        &[T::NumberSequenceList, T::NumberSequenceList],
+        T::Array,
+    ),
+    f(C::Statistic, "FTEST", 2, 2, ARRAY2, T::Number),
+    f(
+        C::Statistic,
+        "GAMMADIST",
+        3,
+        4,
+        &[T::Number, T::Number, T::Number, T::Logical],
+        T::Number,
+    ),
+    f(C::Statistic, "GAMMAINV", 3, 3, NUM3, T::Number),
+    f(C::Statistic, "GAUSS", 1, 1, NUM, T::Number),
+    f(C::Statistic, "GEOMEAN", 1, VAR, NUM_SEQ, T::Number),
+    f(C::Statistic, "GROWTH", 1, 4, GROWTH, T::Array),
+    f(C::Statistic, "HARMEAN", 1, VAR, NUM_SEQ, T::Number),
+    f(
+        C::Statistic,
+        "HYPGEOMDIST",
+        4,
+        5,
+        &[T::Integer, T::Integer, T::Integer, T::Integer, T::Logical],
+        T::Number,
+    ),
+    f(C::Statistic, "INTERCEPT", 2, 2, ARRAY2, T::Number),
+    f(C::Statistic, "KURT", 1, VAR, NUM_SEQ, T::Number),
+    f(
+        C::Statistic,
+        "LARGE",
+        2,
+        2,
+        &[T::NumberSequenceList, T::Integer],
+        T::Number,
+    ),
+    f(C::Statistic, "LEGACY.CHIDIST", 2, 2, NUM2, T::Number),
+    f(C::Statistic, "LEGACY.CHIINV", 2, 2, NUM2, T::Number),
+    f(C::Statistic, "LEGACY.CHITEST", 2, 2, ARRAY2, T::Number),
+    f(C::Statistic, "LEGACY.FDIST", 3, 3, NUM3, T::Number),
+    f(C::Statistic, "LEGACY.FINV", 3, 3, NUM3, T::Number),
+    f(C::Statistic, "LEGACY.NORMSDIST", 1, 1, NUM, T::Number),
+    f(C::Statistic, "LEGACY.NORMSINV", 1, 1, NUM, T::Number),
+    f(
+        C::Statistic,
+        "LEGACY.TDIST",
+        3,
+        3,
+        &[T::Number, T::Number, T::Integer],
+        T::Number,
+    ),
+    f(C::Statistic, "LINEST", 1, 4, REGRESSION, T::Array),
+    f(C::Statistic, "LOGEST", 1, 4, REGRESSION, T::Array),
+    f(C::Statistic, "LOGINV", 1, 3, NUM3, T::Number),
+    f(
+        C::Statistic,
+        "LOGNORMDIST",
+        1,
+        4,
+        &[T::Number, T::Number, T::Number, T::Logical],
+        T::Number,
+    ),
+    f(C::Statistic, "MAX", 1, VAR, NUM_SEQ, T::Number),
+    f(C::Statistic, "MAXA", 1, VAR, ANY, T::Number),
+    f(C::Statistic, "MEDIAN", 1, VAR, NUM_SEQ, T::Number),
+    f(C::Statistic, "MIN", 1, VAR, NUM_SEQ, T::Number),
+    f(C::Statistic, "MINA", 1, VAR, ANY, T::Number),
+    f(C::Statistic, "MODE", 1, VAR, NUM_SEQ, T::Number),
+    f(
+        C::Statistic,
+        "NEGBINOMDIST",
+        3,
+        3,
+        &[T::Integer, T::Integer, T::Number],
+        T::Number,
+    ),
+    f(
+        C::Statistic,
+        "NORMDIST",
+        3,
+        4,
+        &[T::Number, T::Number, T::Number, T::Logical],
+        T::Number,
+    ),
+    f(C::Statistic, "NORMINV", 3, 3, NUM3, T::Number),
+    f(C::Statistic, "PEARSON", 2, 2, ARRAY2, T::Number),
+    f(
+        C::Statistic,
+        "PERCENTILE",
+        2,
+        2,
+        &[T::NumberSequenceList, T::Number],
+        T::Number,
+    ),
+    f(
+        C::Statistic,
+        "PERCENTRANK",
+        2,
+        3,
+        &[T::NumberSequenceList, T::Number, T::Integer],
+        T::Number,
+    ),
+    f(C::Statistic, "PHI", 1, 1, NUM, T::Number),
+    f(
+        C::Statistic,
+        "POISSON",
+        2,
+        3,
+        &[T::Integer, T::Number, T::Logical],
+        T::Number,
+    ),
+    f(
+        C::Statistic,
+        "QUARTILE",
+        2,
+        2,
+        &[T::NumberSequenceList, T::Integer],
+        T::Number,
+    ),
+    f(
+        C::Statistic,
+        "RANK",
+        2,
+        3,
+        &[T::Number, T::NumberSequenceList, T::Number],
+        T::Number,
+    ),
+    f(C::Statistic, "RSQ", 2, 2, ARRAY2, T::Number),
+    f(C::Statistic, "SKEW", 1, VAR, NUM_SEQ, T::Number),
+    f(C::Statistic, "SKEWP", 1, VAR, NUM_SEQ, T::Number),
+    f(C::Statistic, "SLOPE", 2, 2, ARRAY2, T::Number),
+    f(
+        C::Statistic,
+        "SMALL",
+        2,
+        2,
+        &[T::NumberSequenceList, T::Integer],
+        T::Number,
+    ),
+    f(C::Statistic, "STANDARDIZE", 3, 3, NUM3, T::Number),
+    f(C::Statistic, "STDEV", 1, VAR, NUM_SEQ, T::Number),
+    f(C::Statistic, "STDEVA", 1, VAR, ANY, T::Number),
+    f(C::Statistic, "STDEVP", 1, VAR, NUM_SEQ, T::Number),
+    f(C::Statistic, "STDEVPA", 1, VAR, ANY, T::Number),
+    f(C::Statistic, "STEYX", 2, 2, ARRAY2, T::Number),
+    f(C::Statistic, "TINV", 2, 2, NUM2, T::Number),
+    f(C::Statistic, "TREND", 1, 4, GROWTH, T::Array),
+    f(
+        C::Statistic,
+        "TRIMMEAN",
+        2,
+        2,
+        &[T::NumberSequenceList, T::Number],
+        T::Number,
+    ),
+    f(
+        C::Statistic,
+        "TTEST",
+        4,
+        4,
+        &[T::Array, T::Array, T::Integer, T::Integer],
+        T::Number,
+    ),
+    f(C::Statistic, "VAR", 1, VAR, NUM_SEQ, T::Number),
+    f(C::Statistic, "VARA", 1, VAR, ANY, T::Number),
+    f(C::Statistic, "VARP", 1, VAR, NUM_SEQ, T::Number),
+    f(C::Statistic, "VARPA", 1, VAR, ANY, T::Number),
+    f(
+        C::Statistic,
+        "WEIBULL",
+        4,
+        4,
+        &[T::Number, T::Number, T::Number, T::Logical],
+        T::Number,
+    ),
+    f(
+        C::Statistic,
+        "ZTEST",
+        2,
+        3,
+        &[T::NumberSequenceList, T::Number, T::Number],
+        T::Number,
+    ),
+    // text
+    f(C::Text, "ASC", 1, 1, TEXT, T::Text),
+    f(C::Text, "BAHTTEXT", 1, 1, NUM, T::Text),
+    f(C::Text, "CHAR", 1, 1, &[T::Integer], T::Text),
+    f(C::Text, "CLEAN", 1, 1, TEXT, T::Text),
+    f(C::Text, "CODE", 1, 1, TEXT, T::Number),
+    f(
+        C::Text,
+        "COM.MICROSOFT.TEXTJOIN",
+        3,
+        VAR,
+        &[T::Text, T::Logical, T::Any],
+        T::Text,
+    ),
+    f(C::Text, "CONCATENATE", 1, VAR, TEXT, T::Text),
+    f(C::Text, "DOLLAR", 1, 2, &[T::Number, T::Integer], T::Text),
+    f(C::Text, "EXACT", 2, 2, &[T::Text, T::Text], T::Logical),
+    f(C::Text, "FIND", 2, 3, FIND, T::Number),
+    f(C::Text, "FINDB", 2, 3, FIND, T::Number),
+    f(
+        C::Text,
+        "FIXED",
+        1,
+        3,
+        &[T::Number, T::Integer, T::Logical],
+        T::Text,
+    ),
+    f(C::Text, "JIS", 1, 1, TEXT, T::Text),
+    f(C::Text, "LEFT", 1, 2, LEFT, T::Text),
+    f(C::Text, "LEFTB", 1, 2, LEFT, T::Text),
+    f(C::Text, "LEN", 1, 1, TEXT, T::Number),
+    f(C::Text, "LENB", 1, 1, TEXT, T::Number),
+    f(C::Text, "LOWER", 1, 1, TEXT, T::Text),
+    f(C::Text, "MID", 3, 3, MID, T::Text),
+    f(C::Text, "MIDB", 3, 3, MID, T::Text),
+    f(C::Text, "PROPER", 1, 1, TEXT, T::Text),
+    f(C::Text, "REPLACE", 4, 4, REPLACE, T::Text),
+    f(C::Text, "REPLACEB", 4, 4, REPLACE, T::Text),
+    f(C::Text, "REPT", 2, 2, &[T::Text, T::Integer], T::Text),
+    f(C::Text, "RIGHT", 1, 2, LEFT, T::Text),
+    f(C::Text, "RIGHTB", 1, 2, LEFT, T::Text),
+    f(C::Text, "SEARCH", 2, 3, FIND, T::Number),
+    f(C::Text, "SEARCHB", 2, 3, FIND, T::Number),
+    f(
+        C::Text,
+        "SUBSTITUTE",
+        3,
+        4,
+        &[T::Text, T::Text, T::Text, T::Integer],
+        T::Text,
+    ),
+    f(C::Text, "T", 1, 1, ANY, T::Text),
+    f(C::Text, "TEXT", 2, 2, &[T::Scalar, T::Text], T::Text),
+    f(C::Text, "TRIM", 1, 1, TEXT, T::Text),
+    f(C::Text, "UNICHAR", 1, 1, &[T::Integer], T::Text),
+    f(C::Text, "UNICODE", 1, 1, TEXT, T::Number),
+    f(C::Text, "UPPER", 1, 1, TEXT, T::Text),
+];
@@ -1,157 +1,205 @@
-use crate::{func1, func2, FNumber, Number, Sequence};
+use crate::{func1, func2, Complex, FComplex, FNumber, Number, Sequence};
+use std::ops::{Add, Div, Mul, Sub};
 
 /// Creates a complex number from a given real coefficient and imaginary coefficient.
 #[inline]
-pub fn complex(real: impl Number, imag: impl Number) -> FNumber {
-    FNumber(func2("COMPLEX", &real, &imag))
+pub fn complex(real: impl Number, imag: impl Number) -> FComplex {
+    FComplex(func2("COMPLEX", &real, &imag))
 }
 
 /// Returns the absolute value of a complex number.
 #[inline]
-pub fn imabs(complex: impl Number) -> FNumber {
+pub fn imabs(complex: impl Complex) -> FNumber {
     FNumber(func1("IMABS", &complex))
 }
 
 ///Returns the imaginary coefficient of a complex number.
 #[inline]
-pub fn imaginary(complex: impl Number) -> FNumber {
+pub fn imaginary(complex: impl Complex) -> FNumber {
     FNumber(func1("IMAGINARY", &complex))
 }
 
 ///Returns the complex argument of a complex number
 #[inline]
-pub fn imargument(complex: impl Number) -> FNumber {
+pub fn imargument(complex: impl Complex) -> FNumber {
     FNumber(func1("IMARGUMENT", &complex))
 }
 
 /// Returns the complex conjugate of a complex number.
 #[inline]
-pub fn imconjugate(complex: impl Number) -> FNumber {
-    FNumber(func1("IMCONJUGATE", &complex))
+pub fn imconjugate(complex: impl Complex) -> FComplex {
+    FComplex(func1("IMCONJUGATE", &complex))
 }
 
 /// Returns the cosine of a complex number.
 #[inline]
-pub fn imcos(complex: impl Number) -> FNumber {
-    FNumber(func1("IMCOS", &complex))
+pub fn imcos(complex: impl Complex) -> FComplex {
+    FComplex(func1("IMCOS", &complex))
 }
 
 ///Returns the hyperbolic cosine of a complex number.
 #[inline]
-pub fn imcosh(complex: impl Number) -> FNumber {
-    FNumber(func1("IMCOSH", &complex))
+pub fn imcosh(complex: impl Complex) -> FComplex {
+    FComplex(func1("IMCOSH", &complex))
 }
 
 ///Returns the cotangent of a complex number.
 #[inline]
-pub fn imcot(complex: impl Number) -> FNumber {
-    FNumber(func1("IMCOT", &complex))
+pub fn imcot(complex: impl Complex) -> FComplex {
+    FComplex(func1("IMCOT", &complex))
 }
 
 ///Returns the cosecant of a complex number
 #[inline]
-pub fn imcsc(complex: impl Number) -> FNumber {
-    FNumber(func1("IMCSC", &complex))
+pub fn imcsc(complex: impl Complex) -> FComplex {
+    FComplex(func1("IMCSC", &complex))
 }
 
 ///Returns the hyperbolic cosecant of a complex number.
 #[inline]
-pub fn imcsch(complex: impl Number) -> FNumber {
-    FNumber(func1("IMCSCH", &complex))
+pub fn imcsch(complex: impl Complex) -> FComplex {
+    FComplex(func1("IMCSCH", &complex))
 }
 
 /// Divides the first number by the second.
 #[inline]
-pub fn imdiv(complex_x: impl Number, complex_y: impl Number) -> FNumber {
-    FNumber(func2("IMDIV", &complex_x, &complex_y))
+pub fn imdiv(complex_x: impl Complex, complex_y: impl Complex) -> FComplex {
+    FComplex(func2("IMDIV", &complex_x, &complex_y))
 }
 
 ///Returns the exponent of e and a complex number.
 #[inline]
-pub fn imexp(complex: impl Number) -> FNumber {
-    FNumber(func1("IMEXP", &complex))
+pub fn imexp(complex: impl Complex) -> FComplex {
+    FComplex(func1("IMEXP", &complex))
 }
 
 ///Returns the natural logarithm of a complex number
 #[inline]
-pub fn imln(complex: impl Number) -> FNumber {
-    FNumber(func1("IMLN", &complex))
+pub fn imln(complex: impl Complex) -> FComplex {
+    FComplex(func1("IMLN", &complex))
 }
 
 /// Returns the common logarithm of a comp
 #[inline]
-pub fn imlog10(complex: impl Number) -> FNumber {
-    FNumber(func1("IMLOG10", &complex))
+pub fn imlog10(complex: impl Complex) -> FComplex {
+    FComplex(func1("IMLOG10", &complex))
 }
 
 ///Returns the binary logarithm of a complex number.
 #[inline]
-pub fn imlog2(complex: impl Number) -> FNumber {
-    FNumber(func1("IMLOG2", &complex))
+pub fn imlog2(complex: impl Complex) -> FComplex {
+    FComplex(func1("IMLOG2", &complex))
 }
 
 ///Returns the complex number X raised to the Yth power.
 #[inline]
-pub fn impower(complex_x: impl Number, complex_y: impl Number) -> FNumber {
-    FNumber(func2("IMPOWER", &complex_x, &complex_y))
+pub fn impower(complex_x: impl Complex, power: impl Number) -> FComplex {
+    FComplex(func2("IMPOWER", &complex_x, &power))
 }
 
 ///Returns the product of complex numbers.
 #[inline]
-pub fn improduct(complex_x: impl Number, complex_y: impl Number) -> FNumber {
-    FNumber(func2("IMPRODUCT", &complex_x, &complex_y))
+pub fn improduct(complex_x: impl Complex, complex_y: impl Complex) -> FComplex {
+    FComplex(func2("IMPRODUCT", &complex_x, &complex_y))
 }
 
 /// Returns the real coefficient of a complex number.
 #[inline]
-pub fn imreal(complex: impl Number) -> FNumber {
+pub fn imreal(complex: impl Complex) -> FNumber {
     FNumber(func1("IMREAL", &complex))
 }
 
 /// Returns the sine of a complex number.
 #[inline]
-pub fn imsin(complex: impl Number) -> FNumber {
-    FNumber(func1("IMSIN", &complex))
+pub fn imsin(complex: impl Complex) -> FComplex {
+    FComplex(func1("IMSIN", &complex))
 }
 
 ///Returns the hyperbolic sine of a comp
 #[inline]
-pub fn imsinh(complex: impl Number) -> FNumber {
-    FNumber(func1("IMSINH", &complex))
+pub fn imsinh(complex: impl Complex) -> FComplex {
+    FComplex(func1("IMSINH", &complex))
 }
 
 ///Returns the secant of a complex number.
 #[inline]
-pub fn imsec(complex: impl Number) -> FNumber {
-    FNumber(func1("IMSEC", &complex))
+pub fn imsec(complex: impl Complex) -> FComplex {
+    FComplex(func1("IMSEC", &complex))
 }
 
 ///Returns the hyperbolic secant of a complex number.
 #[inline]
-pub fn imsech(complex: impl Number) -> FNumber {
-    FNumber(func1("IMSECH", &complex))
+pub fn imsech(complex: impl Complex) -> FComplex {
+    FComplex(func1("IMSECH", &complex))
 }
 
 /// Returns the square root of a complex number
 #[inline]
-pub fn imsqrt(complex: impl Number) -> FNumber {
-    FNumber(func1("IMSQRT", &complex))
+pub fn imsqrt(complex: impl Complex) -> FComplex {
+    FComplex(func1("IMSQRT", &complex))
 }
 
 /// Subtracts the second complex number from the first
 #[inline]
-pub fn imsub(complex_x: impl Number, complex_y: impl Number) -> FNumber {
-    FNumber(func2("IMSUB", &complex_x, &complex_y))
+pub fn imsub(complex_x: impl Complex, complex_y: impl Complex) -> FComplex {
+    FComplex(func2("IMSUB", &complex_x, &complex_y))
 }
 
 ///Sums (add) a set of complex numbers, including all numbers in ranges.
 #[inline]
-pub fn imsum(complex_sequence: impl Sequence) -> FNumber {
-    FNumber(func1("IMSUM", &complex_sequence))
+pub fn imsum(complex_sequence: impl Sequence) -> FComplex {
+    FComplex(func1("IMSUM", &complex_sequence))
 }
 
 ///Returns the tangent of a complex number
 #[inline]
-pub fn imtan(complex: impl Number) -> FNumber {
-    FNumber(func1("IMTAN", &complex))
+pub fn imtan(complex: impl Complex) -> FComplex {
+    FComplex(func1("IMTAN", &complex))
 }
+
+impl<A: Complex> Add<A> for FComplex {
+    type Output = FComplex;
+
+    fn add(self, rhs: A) -> Self::Output {
+        FComplex(func2("IMSUM", &self, &rhs))
+    }
+}
+
+impl<A: Complex> Sub<A> for FComplex {
+    type Output = FComplex;
+
+    fn sub(self, rhs: A) -> Self::Output {
+        FComplex(func2("IMSUB", &self, &rhs))
+    }
+}
+
+impl<A: Complex> Mul<A> for FComplex {
+    type Output = FComplex;
+
+    fn mul(self, rhs: A) -> Self::Output {
+        FComplex(func2("IMPRODUCT", &self, &rhs))
+    }
+}
+
+impl<A: Complex> Div<A> for FComplex {
+    type Output = FComplex;
+
+    fn div(self, rhs: A) -> Self::Output {
+        FComplex(func2("IMDIV", &self, &rhs))
+    }
+}
+
+#[cfg(feature = "num-complex")]
+impl crate::Any for num_complex::Complex64 {
+    fn formula(&self, buf: &mut String) {
+        let _ = self.expr().formula_into(buf);
+    }
+
+    fn expr(&self) -> crate::Expr {
+        func2("COMPLEX", &self.re, &self.im)
+    }
+}
+#[cfg(feature = "num-complex")]
+impl Complex for num_complex::Complex64 {}
+#[cfg(feature = "num-complex")]
+impl Sequence for num_complex::Complex64 {}
@@ -1,12 +1,12 @@
 use crate::{
-    func0, func1, func2, func3, func4, DateTimeParam, FLogical, FNumber, FnNumber0, FnNumber1,
-    Logical, Number, Param, Sequence, Text,
+    func0, func1, func2, func3, func4, DateTimeParam, FNumber, Logical, Number, Param, Sequence,
+    Text,
 };
 
 /// Constructs a date from year, month, and day of month.
 #[inline]
-pub fn date(day: impl Number, month: impl Number, year: impl Number) -> FNumber {
-    FNumber(func3("DATE", &day, &month, &year))
+pub fn date(year: impl Number, month: impl Number, day: impl Number) -> FNumber {
+    FNumber(func3("DATE", &year, &month, &day))
 }
 
 /// Method for DATEDIF()
@@ -75,12 +75,12 @@ pub enum Days360Method {
     Europe,
 }
 impl Param for Days360Method {
-    type ParamType<'a> = FLogical;
+    type ParamType<'a> = bool;
 
     fn as_param(&self) -> Self::ParamType<'_> {
         match self {
-            Days360Method::USNasd => FLogical("FALSE()".into()),
-            Days360Method::Europe => FLogical("TRUE()".into()),
+            Days360Method::USNasd => false,
+            Days360Method::Europe => true,
         }
     }
 }
@@ -127,8 +127,8 @@ pub fn minute(time: impl DateTimeParam) -> FNumber {
 
 /// Extracts the month from a date.
 #[inline]
-pub fn month(date: impl DateTimeParam) -> FnNumber1<'static, impl DateTimeParam> {
-    FnNumber1("MONTH", date)
+pub fn month(date: impl DateTimeParam) -> FNumber {
+    FNumber(func1("MONTH", &date))
 }
 
 /// Returns the whole number of work days between two dates.
@@ -175,8 +175,8 @@ pub fn timevalue(text: impl Text) -> FNumber {
 
 /// Returns the serial number of today.
 #[inline]
-pub fn today() -> FnNumber0<'static> {
-    FnNumber0("TODAY")
+pub fn today() -> FNumber {
+    FNumber(func0("TODAY"))
 }
 
 /// Method for WEEKDAY()
@@ -282,39 +282,42 @@ pub fn year(date: impl DateTimeParam) -> FNumber {
     FNumber(func1("YEAR", &date))
 }
 
-/// Method for YEARFRAC()
-pub enum YearFracMethod {
-    ///
+/// Day count basis for YEARFRAC() and the financial functions.
+pub enum DayCountBasis {
+    /// US (NASD) 30/360
     USNasd30_360,
-    ///
+    /// Actual/actual
     ActualActual,
-    ///
+    /// Actual/360
     Actual360,
-    ///
+    /// Actual/365
     Actual365,
-    ///
+    /// European 30/360
     European30_360,
 }
-impl Param for YearFracMethod {
-    type ParamType<'a> = &'a str;
+impl Param for DayCountBasis {
+    type ParamType<'a> = u32;
 
     fn as_param(&self) -> Self::ParamType<'_> {
         match self {
-            YearFracMethod::USNasd30_360 => "0",
-            YearFracMethod::ActualActual => "1",
-            YearFracMethod::Actual360 => "2",
-            YearFracMethod::Actual365 => "3",
-            YearFracMethod::European30_360 => "4",
+            DayCountBasis::USNasd30_360 => 0,
+            DayCountBasis::ActualActual => 1,
+            DayCountBasis::Actual360 => 2,
+            DayCountBasis::Actual365 => 3,
+            DayCountBasis::European30_360 => 4,
         }
     }
 }
 
+/// Alias for DayCountBasis.
+pub use DayCountBasis as YearFracMethod;
+
 /// Extracts the number of years (including fractional part) between two dates
 #[inline]
 pub fn yearfrac(
     start_date: impl DateTimeParam,
     end_date: impl DateTimeParam,
-    basis: YearFracMethod,
+    basis: DayCountBasis,
 ) -> FNumber {
     FNumber(func3("YEARFRAC", &start_date, &end_date, &basis.as_param()))
 }
@@ -0,0 +1,174 @@
+//!
+//! Date, time and duration values from chrono. Needs the feature "chrono".
+//!
+//! NaiveDate, NaiveTime and NaiveDateTime are written as DATE(y;m;d),
+//! TIME(h;m;s) or DATE()+TIME(). Duration is written as number of days.
+//!
+//! Inline arrays can only contain constants, use serial() there.
+//!
+
+use crate::{
+    func3, Any, DateTimeParam, Expr, Field, InfixOp, Logical, Number, Scalar, Sequence,
+    TextOrNumber,
+};
+use chrono::{Datelike, Duration, NaiveDate, NaiveDateTime, NaiveTime, Timelike};
+
+const SECONDS_PER_DAY: f64 = 86_400.0;
+
+/// Null date of the serial numbers used by LibreOffice, 1899-12-30.
+pub fn default_null_date() -> NaiveDate {
+    NaiveDate::from_ymd_opt(1899, 12, 30).expect("valid date")
+}
+
+/// Conversion to a serial number, the days since the null date.
+pub trait ToSerial {
+    /// Days since the null date. Times are fractions of a day.
+    fn to_serial(&self, null_date: NaiveDate) -> f64;
+}
+
+impl ToSerial for NaiveDate {
+    fn to_serial(&self, null_date: NaiveDate) -> f64 {
+        (*self - null_date).num_days() as f64
+    }
+}
+
+impl ToSerial for NaiveTime {
+    fn to_serial(&self, _null_date: NaiveDate) -> f64 {
+        seconds(self) / SECONDS_PER_DAY
+    }
+}
+
+impl ToSerial for NaiveDateTime {
+    fn to_serial(&self, null_date: NaiveDate) -> f64 {
+        self.date().to_serial(null_date) + self.time().to_serial(null_date)
+    }
+}
+
+impl ToSerial for Duration {
+    fn to_serial(&self, _null_date: NaiveDate) -> f64 {
+        days(self)
+    }
+}
+
+/// A chrono value written as serial number.
+#[derive(Debug, Clone, Copy)]
+pub struct Serial<T> {
+    value: T,
+    null_date: NaiveDate,
+}
+
+impl<T: ToSerial> Serial<T> {
+    /// Sets the null date. Defaults to 1899-12-30.
+    pub fn null_date(mut self, null_date: NaiveDate) -> Self {
+        self.null_date = null_date;
+        self
+    }
+}
+
+impl<T: ToSerial> Any for Serial<T> {
+    fn formula(&self, buf: &mut String) {
+        self.expr().formula(buf);
+    }
+
+    fn expr(&self) -> Expr {
+        self.value.to_serial(self.null_date).expr()
+    }
+}
+impl<T: ToSerial> Number for Serial<T> {}
+impl<T: ToSerial> Logical for Serial<T> {}
+impl<T: ToSerial> Sequence for Serial<T> {}
+impl<T: ToSerial> TextOrNumber for Serial<T> {}
+impl<T: ToSerial> Field for Serial<T> {}
+impl<T: ToSerial> Scalar for Serial<T> {}
+impl<T: ToSerial> DateTimeParam for Serial<T> {}
+
+/// Writes the value as serial number instead of DATE()/TIME().
+pub fn serial<T: ToSerial>(value: T) -> Serial<T> {
+    Serial {
+        value,
+        null_date: default_null_date(),
+    }
+}
+
+fn seconds(time: &NaiveTime) -> f64 {
+    time.num_seconds_from_midnight() as f64 + time.nanosecond() as f64 / 1e9
+}
+
+fn days(duration: &Duration) -> f64 {
+    match duration.num_nanoseconds() {
+        Some(v) => v as f64 / 1e9 / SECONDS_PER_DAY,
+        None => duration.num_milliseconds() as f64 / 1000.0 / SECONDS_PER_DAY,
+    }
+}
+
+fn date_expr(date: &NaiveDate) -> Expr {
+    func3("DATE", &date.year(), &date.month(), &date.day())
+}
+
+fn time_expr(time: &NaiveTime) -> Expr {
+    if time.nanosecond() == 0 {
+        func3("TIME", &time.hour(), &time.minute(), &time.second())
+    } else {
+        let seconds = time.second() as f64 + time.nanosecond() as f64 / 1e9;
+        func3("TIME", &time.hour(), &time.minute(), &seconds)
+    }
+}
+
+macro_rules! value_datetime {
+    ($t:ty) => {
+        impl Number for $t {}
+        impl Sequence for $t {}
+        impl TextOrNumber for $t {}
+        impl Field for $t {}
+        impl Scalar for $t {}
+        impl DateTimeParam for $t {}
+    };
+}
+
+impl Any for NaiveDate {
+    fn formula(&self, buf: &mut String) {
+        self.expr().formula(buf);
+    }
+
+    fn expr(&self) -> Expr {
+        date_expr(self)
+    }
+}
+value_datetime!(NaiveDate);
+
+impl Any for NaiveTime {
+    fn formula(&self, buf: &mut String) {
+        self.expr().formula(buf);
+    }
+
+    fn expr(&self) -> Expr {
+        time_expr(self)
+    }
+}
+value_datetime!(NaiveTime);
+
+impl Any for NaiveDateTime {
+    fn formula(&self, buf: &mut String) {
+        self.expr().formula(buf);
+    }
+
+    fn expr(&self) -> Expr {
+        Expr::infix(
+            date_expr(&self.date()),
+            InfixOp::Add,
+            time_expr(&self.time()),
+        )
+    }
+}
+value_datetime!(NaiveDateTime);
+
+impl Any for Duration {
+    fn formula(&self, buf: &mut String) {
+        self.expr().formula(buf);
+    }
+
+    fn expr(&self) -> Expr {
+        days(self).expr()
+    }
+}
+value_datetime!(Duration);
@@ -0,0 +1,191 @@
+use crate::{func1, func2, func3, FNumber, FText, Number, Param, Scalar, Text};
+
+/// Style for ROMAN().
+pub enum RomanForm {
+    /// Classic form.
+    Classic,
+    /// More concise.
+    Concise1,
+    /// More concise.
+    Concise2,
+    /// More concise.
+    Concise3,
+    /// Simplified form.
+    Simplified,
+}
+impl Param for RomanForm {
+    type ParamType<'a> = u32;
+
+    fn as_param(&self) -> Self::ParamType<'_> {
+        match self {
+            RomanForm::Classic => 0,
+            RomanForm::Concise1 => 1,
+            RomanForm::Concise2 => 2,
+            RomanForm::Concise3 => 3,
+            RomanForm::Simplified => 4,
+        }
+    }
+}
+
+/// Converts a roman numeral text to a number.
+#[inline]
+pub fn arabic(text: impl Text) -> FNumber {
+    FNumber(func1("ARABIC", &text))
+}
+
+/// Converts a positive integer to a text in the given radix.
+#[inline]
+pub fn base(n: impl Number, radix: impl Number) -> FText {
+    FText(func2("BASE", &n, &radix))
+}
+
+/// Converts a positive integer to a text in the given radix, padded with zeros to the
+/// minimum length.
+#[inline]
+pub fn base_len(n: impl Number, radix: impl Number, min_len: impl Number) -> FText {
+    FText(func3("BASE", &n, &radix, &min_len))
+}
+
+/// Converts a binary number to decimal.
+#[inline]
+pub fn bin2dec(x: impl Scalar) -> FNumber {
+    FNumber(func1("BIN2DEC", &x))
+}
+
+/// Converts a binary number to hexadecimal.
+#[inline]
+pub fn bin2hex(x: impl Scalar) -> FText {
+    FText(func1("BIN2HEX", &x))
+}
+
+/// Converts a binary number to hexadecimal, padded to the given number of places.
+#[inline]
+pub fn bin2hex_places(x: impl Scalar, places: impl Number) -> FText {
+    FText(func2("BIN2HEX", &x, &places))
+}
+
+/// Converts a binary number to octal.
+#[inline]
+pub fn bin2oct(x: impl Scalar) -> FText {
+    FText(func1("BIN2OCT", &x))
+}
+
+/// Converts a binary number to octal, padded to the given number of places.
+#[inline]
+pub fn bin2oct_places(x: impl Scalar, places: impl Number) -> FText {
+    FText(func2("BIN2OCT", &x, &places))
+}
+
+/// Converts a decimal number to binary.
+#[inline]
+pub fn dec2bin(n: impl Number) -> FText {
+    FText(func1("DEC2BIN", &n))
+}
+
+/// Converts a decimal number to binary, padded to the given number of places.
+#[inline]
+pub fn dec2bin_places(n: impl Number, places: impl Number) -> FText {
+    FText(func2("DEC2BIN", &n, &places))
+}
+
+/// Converts a decimal number to hexadecimal.
+#[inline]
+pub fn dec2hex(n: impl Number) -> FText {
+    FText(func1("DEC2HEX", &n))
+}
+
+/// Converts a decimal number to hexadecimal, padded to the given number of places.
+#[inline]
+pub fn dec2hex_places(n: impl Number, places: impl Number) -> FText {
+    FText(func2("DEC2HEX", &n, &places))
+}
+
+/// Converts a decimal number to octal.
+#[inline]
+pub fn dec2oct(n: impl Number) -> FText {
+    FText(func1("DEC2OCT", &n))
+}
+
+/// Converts a decimal number to octal, padded to the given number of places.
+#[inline]
+pub fn dec2oct_places(n: impl Number, places: impl Number) -> FText {
+    FText(func2("DEC2OCT", &n, &places))
+}
+
+/// Converts a text in the given radix to a number.
+#[inline]
+pub fn decimal(text: impl Text, radix: impl Number) -> FNumber {
+    FNumber(func2("DECIMAL", &text, &radix))
+}
+
+/// Converts a hexadecimal number to binary.
+#[inline]
+pub fn hex2bin(x: impl Scalar) -> FText {
+    FText(func1("HEX2BIN", &x))
+}
+
+/// Converts a hexadecimal number to binary, padded to the given number of places.
+#[inline]
+pub fn hex2bin_places(x: impl Scalar, places: impl Number) -> FText {
+    FText(func2("HEX2BIN", &x, &places))
+}
+
+/// Converts a hexadecimal number to decimal.
+#[inline]
+pub fn hex2dec(x: impl Scalar) -> FNumber {
+    FNumber(func1("HEX2DEC", &x))
+}
+
+/// Converts a hexadecimal number to octal.
+#[inline]
+pub fn hex2oct(x: impl Scalar) -> FText {
+    FText(func1("HEX2OCT", &x))
+}
+
+/// Converts a hexadecimal number to octal, padded to the given number of places.
+#[inline]
+pub fn hex2oct_places(x: impl Scalar, places: impl Number) -> FText {
+    FText(func2("HEX2OCT", &x, &places))
+}
+
+/// Converts an octal number to binary.
+#[inline]
+pub fn oct2bin(x: impl Scalar) -> FText {
+    FText(func1("OCT2BIN", &x))
+}
+
+/// Converts an octal number to binary, padded to the given number of places.
+#[inline]
+pub fn oct2bin_places(x: impl Scalar, places: impl Number) -> FText {
+    FText(func2("OCT2BIN", &x, &places))
+}
+
+/// Converts an octal number to decimal.
+#[inline]
+pub fn oct2dec(x: impl Scalar) -> FNumber {
+    FNumber(func1("OCT2DEC", &x))
+}
+
+/// Converts an octal number to hexadecimal.
+#[inline]
+pub fn oct2hex(x: impl Scalar) -> FText {
+    FText(func1("OCT2HEX", &x))
+}
+
+/// Converts an octal number to hexadecimal, padded to the given number of places.
+#[inline]
+pub fn oct2hex_places(x: impl Scalar, places: impl Number) -> FText {
+    FText(func2("OCT2HEX", &x, &places))
+}
+
+/// Converts a number to a roman numeral.
+#[inline]
+pub fn roman(n: impl Number) -> FText {
+    FText(func1("ROMAN", &n))
+}
+
+/// Converts a number to a roman numeral in the given style.
+#[inline]
+pub fn roman_form(n: impl Number, form: RomanForm) -> FText {
+    FText(func2("ROMAN", &n, &form.as_param()))
+}
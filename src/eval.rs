@@ -0,0 +1,1229 @@
+//!
+//! Evaluates formulas against a WorkBook.
+//!
+//! Supports the operators and the functions from the modules math, logical and
+//! information. Unknown functions evaluate to #NAME?.
+//!
+//! Cells that contain a formula are evaluated too, the cached value is not used.
+//! Each formula cell is evaluated once per Evaluator, create a new one after
+//! changing the WorkBook.
+//!
+
+use crate::{parse_expr, Any, ErrorValue, Expr, InfixOp, PostfixOp, PrefixOp};
+use spreadsheet_ods::{CellRange, CellRef, Value, WorkBook};
+use std::cell::RefCell;
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::f64::consts::PI;
+
+/// Result of an evaluation.
+#[derive(Debug, Clone, PartialEq)]
+pub enum EvalValue {
+    /// Empty cell or omitted parameter.
+    Empty,
+    /// Number.
+    Number(f64),
+    /// Text.
+    Text(String),
+    /// Logical.
+    Logical(bool),
+    /// Error value like #DIV/0!
+    Error(ErrorValue),
+    /// Array, a list of rows.
+    Array(Vec<Vec<EvalValue>>),
+    /// Reference to a cell range. The table is always set.
+    Reference(CellRange),
+}
+
+impl EvalValue {
+    fn number(v: f64) -> Self {
+        if v.is_finite() {
+            EvalValue::Number(v)
+        } else {
+            EvalValue::Error(ErrorValue::Num)
+        }
+    }
+}
+
+/// The sheet and cell that contains the evaluated formula.
+#[derive(Debug, Clone, Copy)]
+struct Ctx {
+    sheet: usize,
+    cell: Option<(u32, u32)>,
+}
+
+/// Evaluates formulas against a WorkBook.
+pub struct Evaluator<'a> {
+    book: &'a WorkBook,
+    sheet: usize,
+    // Formula cells currently evaluated, to detect circular references.
+    active: RefCell<Vec<(usize, u32, u32)>>,
+    // Values of the formula cells evaluated so far.
+    values: RefCell<HashMap<(usize, u32, u32), EvalValue>>,
+}
+
+impl<'a> Evaluator<'a> {
+    /// Evaluator for formulas in the given sheet. References without a table refer to
+    /// this sheet.
+    pub fn new(book: &'a WorkBook, sheet: usize) -> Self {
+        Self {
+            book,
+            sheet,
+            active: RefCell::new(Vec::new()),
+            values: RefCell::new(HashMap::new()),
+        }
+    }
+
+    /// Evaluates the formula. A resulting reference is returned as
+    /// EvalValue::Reference.
+    pub fn eval<T: Any>(&self, f: T) -> EvalValue {
+        let ctx = Ctx {
+            sheet: self.sheet,
+            cell: None,
+        };
+        self.expr(&f.expr(), ctx)
+    }
+
+    /// Evaluates the formula. A resulting reference is replaced with the cell value,
+    /// or an array of the cell values for a range. The array ends with the used
+    /// area of the sheet.
+    pub fn eval_value<T: Any>(&self, f: T) -> EvalValue {
+        match self.eval(f) {
+            EvalValue::Reference(r) => self.range_value(&r),
+            v => v,
+        }
+    }
+
+    /// Value of a cell. A cell with a formula is evaluated.
+    pub fn cell_value(&self, sheet: usize, row: u32, col: u32) -> EvalValue {
+        if sheet >= self.book.num_sheets() {
+            return EvalValue::Error(ErrorValue::Ref);
+        }
+        let sh = self.book.sheet(sheet);
+        if let Some(formula) = sh.formula(row, col) {
+            if let Some(v) = self.values.borrow().get(&(sheet, row, col)) {
+                return v.clone();
+            }
+            if self.active.borrow().contains(&(sheet, row, col)) {
+                return EvalValue::Error(ErrorValue::Value);
+            }
+            let expr = match parse_expr(formula) {
+                Ok(expr) => expr,
+                Err(_) => return EvalValue::Error(ErrorValue::Name),
+            };
+            self.active.borrow_mut().push((sheet, row, col));
+            let ctx = Ctx {
+                sheet,
+                cell: Some((row, col)),
+            };
+            let v = match self.expr(&expr, ctx) {
+                EvalValue::Reference(r) => self.range_value(&r),
+                v => v,
+            };
+            self.active.borrow_mut().pop();
+            let v = match v {
+                // An empty result is shown as 0.
+                EvalValue::Empty => EvalValue::Number(0.0),
+                v => v,
+            };
+            self.values
+                .borrow_mut()
+                .insert((sheet, row, col), v.clone());
+            v
+        } else {
+            match sh.value(row, col) {
+                Value::Empty => EvalValue::Empty,
+                Value::Boolean(v) => EvalValue::Logical(*v),
+                Value::Text(v) => EvalValue::Text(v.clone()),
+                v => {
+                    if let Some(n) = v.as_f64_opt() {
+                        EvalValue::Number(n)
+                    } else if let Some(s) = v.as_str_opt() {
+                        EvalValue::Text(s.to_string())
+                    } else {
+                        EvalValue::Error(ErrorValue::Value)
+                    }
+                }
+            }
+        }
+    }
+
+    fn expr(&self, expr: &Expr, ctx: Ctx) -> EvalValue {
+        match expr {
+            Expr::Number(v) => match v.parse::<f64>() {
+                Ok(v) => EvalValue::number(v),
+                Err(_) => EvalValue::Error(ErrorValue::Value),
+            },
+            Expr::Text(v) => EvalValue::Text(v.clone()),
+            Expr::Logical(v) => EvalValue::Logical(*v),
+            Expr::CellRef(r) => self.cellref(r, ctx),
+            Expr::CellRange(r) => self.cellrange(r, ctx),
+            Expr::Name(_) => EvalValue::Error(ErrorValue::Name),
+            Expr::Error(v) => EvalValue::Error(*v),
+            Expr::Missing => EvalValue::Empty,
+            Expr::Func { name, args } => self.func(name, args, ctx),
+            Expr::Infix { op, lhs, rhs } => self.infix(*op, lhs, rhs, ctx),
+            Expr::Prefix { op, expr } => {
+                let v = self.expr(expr, ctx);
+                match op {
+                    PrefixOp::Neg => self.map(v, &|v| {
+                        self.num(v).map_or_else(|e| e, |v| EvalValue::number(-v))
+                    }),
+                    PrefixOp::Plus => v,
+                }
+            }
+            Expr::Postfix { op, expr } => {
+                let v = self.expr(expr, ctx);
+                match op {
+                    PostfixOp::Percent => self.map(v, &|v| {
+                        self.num(v)
+                            .map_or_else(|e| e, |v| EvalValue::number(v / 100.0))
+                    }),
+                }
+            }
+            Expr::Parentheses(expr) => self.expr(expr, ctx),
+            Expr::Array(rows) => EvalValue::Array(
+                rows.iter()
+                    .map(|r| r.iter().map(|v| self.scalar(self.expr(v, ctx))).collect())
+                    .collect(),
+            ),
+            Expr::List(_) => EvalValue::Error(ErrorValue::Value),
+            Expr::Raw(v) => match parse_expr(v) {
+                Ok(expr) => self.expr(&expr, ctx),
+                Err(_) => EvalValue::Error(ErrorValue::Name),
+            },
+        }
+    }
+
+    // -----------------------------------------------------------------------
+    // references
+
+    fn sheet_idx(&self, table: Option<&String>, ctx: Ctx) -> Option<usize> {
+        match table {
+            None => Some(ctx.sheet),
+            Some(name) => self.book.sheet_idx(name),
+        }
+    }
+
+    fn reference(
+        &self,
+        sheet: usize,
+        from: (u32, u32),
+        to: (u32, u32),
+        abs: [bool; 4],
+    ) -> EvalValue {
+        let name = self.book.sheet(sheet).name().clone();
+        EvalValue::Reference(CellRange::new_all(
+            None,
+            Some(name),
+            abs[0],
+            from.0.min(to.0),
+            abs[1],
+            from.1.min(to.1),
+            None,
+            abs[2],
+            from.0.max(to.0),
+            abs[3],
+            from.1.max(to.1),
+        ))
+    }
+
+    fn cellref(&self, r: &CellRef, ctx: Ctx) -> EvalValue {
+        if r.iri().is_some() {
+            return EvalValue::Error(ErrorValue::Ref);
+        }
+        match self.sheet_idx(r.table(), ctx) {
+            Some(sheet) if sheet < self.book.num_sheets() => self.reference(
+                sheet,
+                (r.row(), r.col()),
+                (r.row(), r.col()),
+                [r.row_abs(), r.col_abs(), r.row_abs(), r.col_abs()],
+            ),
+            _ => EvalValue::Error(ErrorValue::Ref),
+        }
+    }
+
+    fn cellrange(&self, r: &CellRange, ctx: Ctx) -> EvalValue {
+        if r.iri().is_some() {
+            return EvalValue::Error(ErrorValue::Ref);
+        }
+        let sheet = self.sheet_idx(r.table(), ctx);
+        if r.to_table().is_some() && self.sheet_idx(r.to_table(), ctx) != sheet {
+            // 3D references are not supported.
+            return EvalValue::Error(ErrorValue::Ref);
+        }
+        match sheet {
+            Some(sheet) if sheet < self.book.num_sheets() => self.reference(
+                sheet,
+                (r.row(), r.col()),
+                (r.to_row(), r.to_col()),
+                [r.row_abs(), r.col_abs(), r.to_row_abs(), r.to_col_abs()],
+            ),
+            _ => EvalValue::Error(ErrorValue::Ref),
+        }
+    }
+
+    fn range_sheet(&self, r: &CellRange) -> usize {
+        self.book
+            .sheet_idx(r.table().map(|v| v.as_str()).unwrap_or_default())
+            .unwrap_or(self.sheet)
+    }
+
+    /// Cell values of the range as rows, limited to the used part of the sheet.
+    fn range_cells(&self, r: &CellRange) -> Vec<Vec<EvalValue>> {
+        let sheet = self.range_sheet(r);
+        let (used_rows, used_cols) = self.book.sheet(sheet).used_grid_size();
+        let to_row = r.to_row().min(used_rows.max(r.row() + 1) - 1);
+        let to_col = r.to_col().min(used_cols.max(r.col() + 1) - 1);
+        (r.row()..=to_row)
+            .map(|row| {
+                (r.col()..=to_col)
+                    .map(|col| self.cell_value(sheet, row, col))
+                    .collect()
+            })
+            .collect()
+    }
+
+    fn range_size(r: &CellRange) -> (u32, u32) {
+        (r.to_row() - r.row() + 1, r.to_col() - r.col() + 1)
+    }
+
+    /// Value of a single cell, or an array for a range. The array only contains
+    /// the cells in the used area, the cells after it are empty.
+    fn range_value(&self, r: &CellRange) -> EvalValue {
+        if Self::range_size(r) == (1, 1) {
+            self.cell_value(self.range_sheet(r), r.row(), r.col())
+        } else {
+            EvalValue::Array(self.range_cells(r))
+        }
+    }
+
+    // -----------------------------------------------------------------------
+    // conversions
+
+    /// Replaces a reference to a single cell with its value.
+    fn scalar(&self, v: EvalValue) -> EvalValue {
+        match v {
+            EvalValue::Reference(r) if Self::range_size(&r) == (1, 1) => self.range_value(&r),
+            EvalValue::Reference(_) => EvalValue::Error(ErrorValue::Value),
+            v => v,
+        }
+    }
+
+    fn num(&self, v: EvalValue) -> Result<f64, EvalValue> {
+        match self.scalar(v) {
+            EvalValue::Empty => Ok(0.0),
+            EvalValue::Number(v) => Ok(v),
+            EvalValue::Logical(v) => Ok(if v { 1.0 } else { 0.0 }),
+            EvalValue::Text(v) => parse_number(&v).ok_or(EvalValue::Error(ErrorValue::Value)),
+            EvalValue::Error(e) => Err(EvalValue::Error(e)),
+            EvalValue::Array(_) | EvalValue::Reference(_) => {
+                Err(EvalValue::Error(ErrorValue::Value))
+            }
+        }
+    }
+
+    fn logical(&self, v: EvalValue) -> Result<bool, EvalValue> {
+        match self.scalar(v) {
+            EvalValue::Text(v) if v.eq_ignore_ascii_case("TRUE") => Ok(true),
+            EvalValue::Text(v) if v.eq_ignore_ascii_case("FALSE") => Ok(false),
+            v => self.num(v).map(|v| v != 0.0),
+        }
+    }
+
+    fn text(&self, v: EvalValue) -> Result<String, EvalValue> {
+        match self.scalar(v) {
+            EvalValue::Empty => Ok(String::new()),
+            EvalValue::Number(v) => Ok(format_number(v)),
+            EvalValue::Logical(v) => Ok(if v { "TRUE" } else { "FALSE" }.to_string()),
+            EvalValue::Text(v) => Ok(v),
+            EvalValue::Error(e) => Err(EvalValue::Error(e)),
+            EvalValue::Array(_) | EvalValue::Reference(_) => {
+                Err(EvalValue::Error(ErrorValue::Value))
+            }
+        }
+    }
+
+    /// Applies the function to a scalar or to every element of an array.
+    fn map(&self, v: EvalValue, f: &dyn Fn(EvalValue) -> EvalValue) -> EvalValue {
+        match v {
+            EvalValue::Reference(r) if Self::range_size(&r) != (1, 1) => {
+                self.map(self.range_value(&r), f)
+            }
+            EvalValue::Array(rows) => EvalValue::Array(
+                rows.into_iter()
+                    .map(|r| r.into_iter().map(f).collect())
+                    .collect(),
+            ),
+            v => f(v),
+        }
+    }
+
+    /// Applies the function to scalars or element-wise to arrays.
+    fn zip(
+        &self,
+        a: EvalValue,
+        b: EvalValue,
+        f: &dyn Fn(EvalValue, EvalValue) -> EvalValue,
+    ) -> EvalValue {
+        // Cells of a range after the used area are empty, outside the range
+        // they are #N/A.
+        let size = |v: &EvalValue| match v {
+            EvalValue::Reference(r) => Self::range_size(r),
+            _ => (0, 0),
+        };
+        let (a_size, b_size) = (size(&a), size(&b));
+        let a = match a {
+            EvalValue::Reference(r) if Self::range_size(&r) != (1, 1) => self.range_value(&r),
+            a => a,
+        };
+        let b = match b {
+            EvalValue::Reference(r) if Self::range_size(&r) != (1, 1) => self.range_value(&r),
+            b => b,
+        };
+        match (a, b) {
+            (EvalValue::Array(a), EvalValue::Array(b)) => {
+                let rows = a.len().max(b.len());
+                let cols = a.iter().chain(b.iter()).map(|r| r.len()).max().unwrap_or(0);
+                let get = |m: &Vec<Vec<EvalValue>>, size: (u32, u32), r: usize, c: usize| {
+                    if m.len() == 1 && m[0].len() == 1 && size == (0, 0) {
+                        m[0][0].clone()
+                    } else if let Some(v) = m.get(r).and_then(|v| v.get(c)) {
+                        v.clone()
+                    } else if r < size.0 as usize && c < size.1 as usize {
+                        EvalValue::Empty
+                    } else {
+                        EvalValue::Error(ErrorValue::NA)
+                    }
+                };
+                EvalValue::Array(
+                    (0..rows)
+                        .map(|r| {
+                            (0..cols)
+                                .map(|c| f(get(&a, a_size, r, c), get(&b, b_size, r, c)))
+                                .collect()
+                        })
+                        .collect(),
+                )
+            }
+            (EvalValue::Array(a), b) => self.map(EvalValue::Array(a), &|a| f(a, b.clone())),
+            (a, EvalValue::Array(b)) => self.map(EvalValue::Array(b), &|b| f(a.clone(), b)),
+            (a, b) => f(a, b),
+        }
+    }
+
+    /// All values of the parameter. References and arrays are expanded, other
+    /// values are returned as is.
+    fn values(&self, v: EvalValue) -> (Vec<EvalValue>, bool) {
+        match v {
+            EvalValue::Reference(r) => (self.range_cells(&r).into_iter().flatten().collect(), true),
+            EvalValue::Array(rows) => (rows.into_iter().flatten().collect(), true),
+            v => (vec![v], false),
+        }
+    }
+
+    /// Numbers of a sequence parameter. Inside references and arrays only numbers
+    /// count, direct parameters are converted.
+    fn numbers(&self, args: &[Expr], ctx: Ctx) -> Result<Vec<f64>, EvalValue> {
+        let mut nums = Vec::new();
+        for arg in args {
+            let (values, expanded) = self.values(self.expr(arg, ctx));
+            for v in values {
+                match v {
+                    EvalValue::Number(n) => nums.push(n),
+                    EvalValue::Error(e) => return Err(EvalValue::Error(e)),
+                    EvalValue::Empty => {}
+                    v if !expanded => nums.push(self.num(v)?),
+                    _ => {}
+                }
+            }
+        }
+        Ok(nums)
+    }
+
+    // -----------------------------------------------------------------------
+    // operators
+
+    fn infix(&self, op: InfixOp, lhs: &Expr, rhs: &Expr, ctx: Ctx) -> EvalValue {
+        let a = self.expr(lhs, ctx);
+        let b = self.expr(rhs, ctx);
+        match op {
+            InfixOp::Add => self.arith(a, b, |a, b| Ok(a + b)),
+            InfixOp::Sub => self.arith(a, b, |a, b| Ok(a - b)),
+            InfixOp::Mul => self.arith(a, b, |a, b| Ok(a * b)),
+            InfixOp::Div => self.arith(a, b, |a, b| {
+                if b == 0.0 {
+                    Err(ErrorValue::Div0)
+                } else {
+                    Ok(a / b)
+                }
+            }),
+            InfixOp::Pow => self.arith(a, b, |a, b| {
+                if a == 0.0 && b == 0.0 {
+                    Err(ErrorValue::Num)
+                } else {
+                    Ok(a.powf(b))
+                }
+            }),
+            InfixOp::Concat => self.zip(a, b, &|a, b| match (self.text(a), self.text(b)) {
+                (Ok(a), Ok(b)) => EvalValue::Text(a + &b),
+                (Err(e), _) | (_, Err(e)) => e,
+            }),
+            InfixOp::Eq => self.compare(a, b, |o| o == Ordering::Equal),
+            InfixOp::Ne => self.compare(a, b, |o| o != Ordering::Equal),
+            InfixOp::Lt => self.compare(a, b, |o| o == Ordering::Less),
+            InfixOp::Le => self.compare(a, b, |o| o != Ordering::Greater),
+            InfixOp::Gt => self.compare(a, b, |o| o == Ordering::Greater),
+            InfixOp::Ge => self.compare(a, b, |o| o != Ordering::Less),
+            InfixOp::Range | InfixOp::Intersect => self.refop(op, a, b),
+            InfixOp::RefConcat => EvalValue::Error(ErrorValue::Value),
+        }
+    }
+
+    fn arith(
+        &self,
+        a: EvalValue,
+        b: EvalValue,
+        f: fn(f64, f64) -> Result<f64, ErrorValue>,
+    ) -> EvalValue {
+        self.zip(a, b, &|a, b| match (self.num(a), self.num(b)) {
+            (Ok(a), Ok(b)) => match f(a, b) {
+                Ok(v) => EvalValue::number(v),
+                Err(e) => EvalValue::Error(e),
+            },
+            (Err(e), _) | (_, Err(e)) => e,
+        })
+    }
+
+    fn compare(&self, a: EvalValue, b: EvalValue, f: fn(Ordering) -> bool) -> EvalValue {
+        self.zip(a, b, &|a, b| {
+            let a = self.scalar(a);
+            let b = self.scalar(b);
+            match (a, b) {
+                (EvalValue::Error(e), _) | (_, EvalValue::Error(e)) => EvalValue::Error(e),
+                (a, b) => EvalValue::Logical(f(compare_values(&a, &b))),
+            }
+        })
+    }
+
+    fn refop(&self, op: InfixOp, a: EvalValue, b: EvalValue) -> EvalValue {
+        let (a, b) = match (a, b) {
+            (EvalValue::Reference(a), EvalValue::Reference(b)) => (a, b),
+            (EvalValue::Error(e), _) | (_, EvalValue::Error(e)) => return EvalValue::Error(e),
+            _ => return EvalValue::Error(ErrorValue::Value),
+        };
+        let sheet = self.range_sheet(&a);
+        if sheet != self.range_sheet(&b) {
+            return EvalValue::Error(ErrorValue::Ref);
+        }
+        let abs = [false; 4];
+        if op == InfixOp::Range {
+            self.reference(
+                sheet,
+                (a.row().min(b.row()), a.col().min(b.col())),
+                (a.to_row().max(b.to_row()), a.to_col().max(b.to_col())),
+                abs,
+            )
+        } else {
+            let from = (a.row().max(b.row()), a.col().max(b.col()));
+            let to = (a.to_row().min(b.to_row()), a.to_col().min(b.to_col()));
+            if from.0 > to.0 || from.1 > to.1 {
+                EvalValue::Error(ErrorValue::Null)
+            } else {
+                self.reference(sheet, from, to, abs)
+            }
+        }
+    }
+
+    // -----------------------------------------------------------------------
+    // functions
+
+    fn arg(&self, args: &[Expr], n: usize, ctx: Ctx) -> EvalValue {
+        match args.get(n) {
+            Some(arg) => self.expr(arg, ctx),
+            None => EvalValue::Empty,
+        }
+    }
+
+    fn num_arg(&self, args: &[Expr], n: usize, ctx: Ctx) -> Result<f64, EvalValue> {
+        self.num(self.arg(args, n, ctx))
+    }
+
+    /// Optional number parameter.
+    fn num_arg_or(
+        &self,
+        args: &[Expr],
+        n: usize,
+        default: f64,
+        ctx: Ctx,
+    ) -> Result<f64, EvalValue> {
+        match args.get(n) {
+            None | Some(Expr::Missing) => Ok(default),
+            Some(_) => self.num_arg(args, n, ctx),
+        }
+    }
+
+    fn math1(&self, args: &[Expr], ctx: Ctx, f: fn(f64) -> Result<f64, ErrorValue>) -> EvalValue {
+        if args.len() != 1 {
+            return EvalValue::Error(ErrorValue::Value);
+        }
+        let v = self.arg(args, 0, ctx);
+        self.map(v, &|v| match self.num(v) {
+            Ok(v) => match f(v) {
+                Ok(v) => EvalValue::number(v),
+                Err(e) => EvalValue::Error(e),
+            },
+            Err(e) => e,
+        })
+    }
+
+    fn math2(
+        &self,
+        args: &[Expr],
+        ctx: Ctx,
+        f: fn(f64, f64) -> Result<f64, ErrorValue>,
+    ) -> EvalValue {
+        if args.len() != 2 {
+            return EvalValue::Error(ErrorValue::Value);
+        }
+        let a = self.arg(args, 0, ctx);
+        let b = self.arg(args, 1, ctx);
+        self.arith(a, b, f)
+    }
+
+    fn func(&self, name: &str, args: &[Expr], ctx: Ctx) -> EvalValue {
+        let r =
+            match name {
+                // math
+                "ABS" => return self.math1(args, ctx, |v| Ok(v.abs())),
+                "ACOS" => return self.math1(args, ctx, |v| domain(v.acos())),
+                "ACOSH" => return self.math1(args, ctx, |v| domain(v.acosh())),
+                "ACOT" => return self.math1(args, ctx, |v| Ok(PI / 2.0 - v.atan())),
+                "ACOTH" => {
+                    return self.math1(args, ctx, |v| domain(0.5 * ((v + 1.0) / (v - 1.0)).ln()))
+                }
+                "ASIN" => return self.math1(args, ctx, |v| domain(v.asin())),
+                "ASINH" => return self.math1(args, ctx, |v| Ok(v.asinh())),
+                "ATAN" => return self.math1(args, ctx, |v| Ok(v.atan())),
+                "ATAN2" => {
+                    return self.math2(args, ctx, |x, y| {
+                        if x == 0.0 && y == 0.0 {
+                            Err(ErrorValue::Div0)
+                        } else {
+                            Ok(y.atan2(x))
+                        }
+                    })
+                }
+                "ATANH" => return self.math1(args, ctx, |v| domain(v.atanh())),
+                "COMBIN" => return self.math2(args, ctx, |n, k| combin(n.trunc(), k.trunc())),
+                "COMBINA" => {
+                    return self.math2(args, ctx, |n, k| {
+                        combin(n.trunc() + k.trunc() - 1.0, k.trunc())
+                    })
+                }
+                "COS" => return self.math1(args, ctx, |v| Ok(v.cos())),
+                "COSH" => return self.math1(args, ctx, |v| Ok(v.cosh())),
+                "COT" => return self.math1(args, ctx, |v| div(1.0, v.tan())),
+                "COTH" => return self.math1(args, ctx, |v| div(1.0, v.tanh())),
+                "CSC" => return self.math1(args, ctx, |v| div(1.0, v.sin())),
+                "CSCH" => return self.math1(args, ctx, |v| div(1.0, v.sinh())),
+                "DEGREES" => return self.math1(args, ctx, |v| Ok(v.to_degrees())),
+                "DELTA" if args.len() == 1 => self
+                    .num_arg(args, 0, ctx)
+                    .map(|x| EvalValue::Number(if x == 0.0 { 1.0 } else { 0.0 })),
+                "DELTA" => return self.math2(args, ctx, |x, y| Ok(if x == y { 1.0 } else { 0.0 })),
+                "EVEN" => return self.math1(args, ctx, |v| Ok(round_away(v, 2.0))),
+                "EXP" => return self.math1(args, ctx, |v| Ok(v.exp())),
+                "FACT" => return self.math1(args, ctx, |v| fact(v.trunc(), 1.0)),
+                "FACTDOUBLE" => return self.math1(args, ctx, |v| fact(v.trunc(), 2.0)),
+                "GCD" => self.numbers(args, ctx).and_then(|v| gcd_lcm(&v, true)),
+                "GESTEP" => {
+                    let x = self.num_arg(args, 0, ctx);
+                    let step = self.num_arg_or(args, 1, 0.0, ctx);
+                    x.and_then(|x| step.map(|s| EvalValue::Number(if x >= s { 1.0 } else { 0.0 })))
+                }
+                "LCM" => self.numbers(args, ctx).and_then(|v| gcd_lcm(&v, false)),
+                "LN" => return self.math1(args, ctx, |v| positive(v).map(f64::ln)),
+                "LOG" => {
+                    let n = self.num_arg(args, 0, ctx);
+                    let base = self.num_arg_or(args, 1, 10.0, ctx);
+                    n.and_then(|n| base.map(|b| (n, b))).map(|(n, b)| {
+                        if n <= 0.0 || b <= 0.0 || b == 1.0 {
+                            EvalValue::Error(ErrorValue::Num)
+                        } else {
+                            EvalValue::number(n.log(b))
+                        }
+                    })
+                }
+                "LOG10" => return self.math1(args, ctx, |v| positive(v).map(f64::log10)),
+                "MOD" => {
+                    return self.math2(args, ctx, |a, b| {
+                        if b == 0.0 {
+                            Err(ErrorValue::Div0)
+                        } else {
+                            Ok(a - b * (a / b).floor())
+                        }
+                    })
+                }
+                "MULTINOMIAL" => self.numbers(args, ctx).and_then(|v| {
+                    let mut sum = 0.0;
+                    let mut res = 1.0;
+                    for n in v.iter().map(|v| v.trunc()) {
+                        if n < 0.0 {
+                            return Err(EvalValue::Error(ErrorValue::Num));
+                        }
+                        sum += n;
+                        res *= combin(sum, n).map_err(EvalValue::Error)?;
+                    }
+                    Ok(EvalValue::number(res))
+                }),
+                "ODD" => {
+                    return self.math1(args, ctx, |v| {
+                        let r = round_away(v + v.signum(), 2.0) - v.signum();
+                        Ok(if v == 0.0 { 1.0 } else { r })
+                    })
+                }
+                "PI" => Ok(EvalValue::Number(PI)),
+                "POWER" => {
+                    return self.math2(args, ctx, |a, b| {
+                        if a == 0.0 && b == 0.0 {
+                            Err(ErrorValue::Num)
+                        } else {
+                            Ok(a.powf(b))
+                        }
+                    })
+                }
+                "PRODUCT" => self
+                    .numbers(args, ctx)
+                    .map(|v| EvalValue::number(v.iter().product())),
+                "QUOTIENT" => return self.math2(args, ctx, |a, b| div(a, b).map(f64::trunc)),
+                "RADIANS" => return self.math1(args, ctx, |v| Ok(v.to_radians())),
+                "SEC" => return self.math1(args, ctx, |v| div(1.0, v.cos())),
+                "SECH" => return self.math1(args, ctx, |v| Ok(1.0 / v.cosh())),
+                "SERIESSUM" => {
+                    let x = self.num_arg(args, 0, ctx);
+                    let n = self.num_arg(args, 1, ctx);
+                    let m = self.num_arg(args, 2, ctx);
+                    let coeff = self.numbers(args.get(3..).unwrap_or_default(), ctx);
+                    x.and_then(|x| {
+                        let (n, m, coeff) = (n?, m?, coeff?);
+                        Ok(EvalValue::number(
+                            coeff
+                                .iter()
+                                .enumerate()
+                                .map(|(i, a)| a * x.powf(n + i as f64 * m))
+                                .sum(),
+                        ))
+                    })
+                }
+                "SIGN" => {
+                    return self.math1(args, ctx, |v| Ok(if v == 0.0 { 0.0 } else { v.signum() }))
+                }
+                "SIN" => return self.math1(args, ctx, |v| Ok(v.sin())),
+                "SINH" => return self.math1(args, ctx, |v| Ok(v.sinh())),
+                "SQRT" => {
+                    return self.math1(args, ctx, |v| {
+                        if v < 0.0 {
+                            Err(ErrorValue::Num)
+                        } else {
+                            Ok(v.sqrt())
+                        }
+                    })
+                }
+                "SQRTPI" => {
+                    return self.math1(args, ctx, |v| {
+                        if v < 0.0 {
+                            Err(ErrorValue::Num)
+                        } else {
+                            Ok((v * PI).sqrt())
+                        }
+                    })
+                }
+                "SUM" => self
+                    .numbers(args, ctx)
+                    .map(|v| EvalValue::number(v.iter().sum())),
+                "SUMPRODUCT" => self.sumproduct(args, ctx),
+                "SUMX2MY2" => self.sum_pairs(args, ctx, |x, y| x * x - y * y),
+                "SUMX2PY2" => self.sum_pairs(args, ctx, |x, y| x * x + y * y),
+                "SUMXMY2" => self.sum_pairs(args, ctx, |x, y| (x - y) * (x - y)),
+                "TAN" => return self.math1(args, ctx, |v| Ok(v.tan())),
+                "TANH" => return self.math1(args, ctx, |v| Ok(v.tanh())),
+
+                // logical
+                "AND" => self.logicals(args, ctx).map(|v| {
+                    v.map_or(EvalValue::Error(ErrorValue::Value), |v| {
+                        EvalValue::Logical(v.iter().all(|v| *v))
+                    })
+                }),
+                "FALSE" => Ok(EvalValue::Logical(false)),
+                "IF" => {
+                    if args.is_empty() || args.len() > 3 {
+                        return EvalValue::Error(ErrorValue::Value);
+                    }
+                    let cond = self.logical(self.arg(args, 0, ctx));
+                    cond.map(|c| match (c, args.get(1), args.get(2)) {
+                        (true, None, _) => EvalValue::Logical(true),
+                        (false, _, None) => EvalValue::Logical(false),
+                        (true, Some(v), _) | (false, _, Some(v)) => match v {
+                            Expr::Missing => EvalValue::Number(0.0),
+                            v => self.expr(v, ctx),
+                        },
+                    })
+                }
+                "IFERROR" | "IFNA" => {
+                    if args.len() != 2 {
+                        return EvalValue::Error(ErrorValue::Value);
+                    }
+                    let v = self.scalar(self.arg(args, 0, ctx));
+                    match &v {
+                        EvalValue::Error(e) if name == "IFERROR" || *e == ErrorValue::NA => {
+                            Ok(self.arg(args, 1, ctx))
+                        }
+                        _ => Ok(v),
+                    }
+                }
+                "NOT" => {
+                    if args.len() != 1 {
+                        return EvalValue::Error(ErrorValue::Value);
+                    }
+                    let v = self.arg(args, 0, ctx);
+                    return self.map(v, &|v| {
+                        self.logical(v)
+                            .map_or_else(|e| e, |v| EvalValue::Logical(!v))
+                    });
+                }
+                "OR" => self.logicals(args, ctx).map(|v| {
+                    v.map_or(EvalValue::Error(ErrorValue::Value), |v| {
+                        EvalValue::Logical(v.iter().any(|v| *v))
+                    })
+                }),
+                "TRUE" => Ok(EvalValue::Logical(true)),
+                "XOR" => self.logicals(args, ctx).map(|v| {
+                    v.map_or(EvalValue::Error(ErrorValue::Value), |v| {
+                        EvalValue::Logical(v.iter().filter(|v| **v).count() % 2 == 1)
+                    })
+                }),
+
+                // information
+                "AREAS" => match self.arg(args, 0, ctx) {
+                    EvalValue::Reference(_) => Ok(EvalValue::Number(1.0)),
+                    _ => Ok(EvalValue::Error(ErrorValue::Value)),
+                },
+                "COLUMN" => self.position(args, ctx, |r| r.col()),
+                "COLUMNS" => self.size(args, ctx, |(_, cols)| cols),
+                "COUNT" => {
+                    let mut count = 0;
+                    for arg in args {
+                        let (values, _) = self.values(self.expr(arg, ctx));
+                        count += values
+                            .iter()
+                            .filter(|v| matches!(v, EvalValue::Number(_)))
+                            .count();
+                    }
+                    Ok(EvalValue::Number(count as f64))
+                }
+                "COUNTA" => {
+                    let mut count = 0;
+                    for arg in args {
+                        if let Expr::Missing = arg {
+                            continue;
+                        }
+                        let (values, _) = self.values(self.expr(arg, ctx));
+                        count += values
+                            .iter()
+                            .filter(|v| !matches!(v, EvalValue::Empty))
+                            .count();
+                    }
+                    Ok(EvalValue::Number(count as f64))
+                }
+                "COUNTBLANK" => match self.arg(args, 0, ctx) {
+                    EvalValue::Reference(r) => {
+                        let (rows, cols) = Self::range_size(&r);
+                        let filled = self
+                            .range_cells(&r)
+                            .iter()
+                            .flatten()
+                            .filter(|v| match v {
+                                EvalValue::Empty => false,
+                                EvalValue::Text(v) => !v.is_empty(),
+                                _ => true,
+                            })
+                            .count();
+                        Ok(EvalValue::Number((rows * cols) as f64 - filled as f64))
+                    }
+                    EvalValue::Error(e) => Ok(EvalValue::Error(e)),
+                    _ => Ok(EvalValue::Error(ErrorValue::Value)),
+                },
+                "ERROR.TYPE" => match self.scalar(self.arg(args, 0, ctx)) {
+                    EvalValue::Error(e) => Ok(EvalValue::Number(e.code() as f64)),
+                    _ => Ok(EvalValue::Error(ErrorValue::NA)),
+                },
+                "ISBLANK" => self.is(args, ctx, |v| matches!(v, EvalValue::Empty)),
+                "ISERR" => self.is(
+                    args,
+                    ctx,
+                    |v| matches!(v, EvalValue::Error(e) if *e != ErrorValue::NA),
+                ),
+                "ISERROR" => self.is(args, ctx, |v| matches!(v, EvalValue::Error(_))),
+                "ISEVEN" | "ISODD" => self.num_arg(args, 0, ctx).map(|v| {
+                    let even = v.trunc() % 2.0 == 0.0;
+                    EvalValue::Logical(even == (name == "ISEVEN"))
+                }),
+                "ISFORMULA" => match self.arg(args, 0, ctx) {
+                    EvalValue::Reference(r) => {
+                        let sheet = self.book.sheet(self.range_sheet(&r));
+                        Ok(EvalValue::Logical(
+                            sheet.formula(r.row(), r.col()).is_some(),
+                        ))
+                    }
+                    _ => Ok(EvalValue::Error(ErrorValue::Value)),
+                },
+                "ISLOGICAL" => self.is(args, ctx, |v| matches!(v, EvalValue::Logical(_))),
+                "ISNA" => self.is(args, ctx, |v| matches!(v, EvalValue::Error(ErrorValue::NA))),
+                "ISNONTEXT" => self.is(args, ctx, |v| !matches!(v, EvalValue::Text(_))),
+                "ISNUMBER" => self.is(args, ctx, |v| matches!(v, EvalValue::Number(_))),
+                "ISREF" => Ok(EvalValue::Logical(matches!(
+                    self.arg(args, 0, ctx),
+                    EvalValue::Reference(_)
+                ))),
+                "ISTEXT" => self.is(args, ctx, |v| matches!(v, EvalValue::Text(_))),
+                "N" => match self.scalar(self.arg(args, 0, ctx)) {
+                    EvalValue::Number(v) => Ok(EvalValue::Number(v)),
+                    EvalValue::Logical(v) => Ok(EvalValue::Number(if v { 1.0 } else { 0.0 })),
+                    EvalValue::Error(e) => Ok(EvalValue::Error(e)),
+                    _ => Ok(EvalValue::Number(0.0)),
+                },
+                "NA" => Ok(EvalValue::Error(ErrorValue::NA)),
+                "ROW" => self.position(args, ctx, |r| r.row()),
+                "ROWS" => self.size(args, ctx, |(rows, _)| rows),
+                "SHEET" => match args.first() {
+                    None => Ok(EvalValue::Number(ctx.sheet as f64 + 1.0)),
+                    Some(_) => match self.arg(args, 0, ctx) {
+                        EvalValue::Reference(r) => {
+                            Ok(EvalValue::Number(self.range_sheet(&r) as f64 + 1.0))
+                        }
+                        EvalValue::Text(v) => Ok(self
+                            .book
+                            .sheet_idx(v)
+                            .map_or(EvalValue::Error(ErrorValue::NA), |v| {
+                                EvalValue::Number(v as f64 + 1.0)
+                            })),
+                        _ => Ok(EvalValue::Error(ErrorValue::Value)),
+                    },
+                },
+                "SHEETS" => match args.first() {
+                    None => Ok(EvalValue::Number(self.book.num_sheets() as f64)),
+                    Some(_) => match self.arg(args, 0, ctx) {
+                        EvalValue::Reference(_) => Ok(EvalValue::Number(1.0)),
+                        _ => Ok(EvalValue::Error(ErrorValue::Value)),
+                    },
+                },
+                "TYPE" => Ok(match self.scalar(self.arg(args, 0, ctx)) {
+                    EvalValue::Empty | EvalValue::Number(_) => EvalValue::Number(1.0),
+                    EvalValue::Text(_) => EvalValue::Number(2.0),
+                    EvalValue::Logical(_) => EvalValue::Number(4.0),
+                    EvalValue::Error(_) => EvalValue::Number(16.0),
+                    EvalValue::Array(_) | EvalValue::Reference(_) => EvalValue::Number(64.0),
+                }),
+                "VALUE" => match self.scalar(self.arg(args, 0, ctx)) {
+                    EvalValue::Text(v) => Ok(parse_number(&v)
+                        .map_or(EvalValue::Error(ErrorValue::Value), EvalValue::Number)),
+                    v => self.num(v).map(EvalValue::Number),
+                },
+
+                _ => Ok(EvalValue::Error(ErrorValue::Name)),
+            };
+        r.unwrap_or_else(|e| e)
+    }
+
+    /// Logical values of all parameters. None if there are no values.
+    fn logicals(&self, args: &[Expr], ctx: Ctx) -> Result<Option<Vec<bool>>, EvalValue> {
+        let mut res = Vec::new();
+        for arg in args {
+            let (values, expanded) = self.values(self.expr(arg, ctx));
+            for v in values {
+                match v {
+                    EvalValue::Empty => {}
+                    EvalValue::Text(_) if expanded => {}
+                    v => res.push(self.logical(v)?),
+                }
+            }
+        }
+        Ok(if res.is_empty() { None } else { Some(res) })
+    }
+
+    fn is(
+        &self,
+        args: &[Expr],
+        ctx: Ctx,
+        f: fn(&EvalValue) -> bool,
+    ) -> Result<EvalValue, EvalValue> {
+        if args.len() != 1 {
+            return Err(EvalValue::Error(ErrorValue::Value));
+        }
+        let v = self.scalar(self.arg(args, 0, ctx));
+        Ok(EvalValue::Logical(f(&v)))
+    }
+
+    fn position(
+        &self,
+        args: &[Expr],
+        ctx: Ctx,
+        f: fn(&CellRange) -> u32,
+    ) -> Result<EvalValue, EvalValue> {
+        match args.first() {
+            None | Some(Expr::Missing) => match ctx.cell {
+                Some((row, col)) => {
+                    let r = CellRange::local(row, col, row, col);
+                    Ok(EvalValue::Number(f(&r) as f64 + 1.0))
+                }
+                None => Err(EvalValue::Error(ErrorValue::Value)),
+            },
+            Some(_) => match self.arg(args, 0, ctx) {
+                EvalValue::Reference(r) => Ok(EvalValue::Number(f(&r) as f64 + 1.0)),
+                EvalValue::Error(e) => Err(EvalValue::Error(e)),
+                _ => Err(EvalValue::Error(ErrorValue::Value)),
+            },
+        }
+    }
+
+    fn size(
+        &self,
+        args: &[Expr],
+        ctx: Ctx,
+        f: fn((u32, u32)) -> u32,
+    ) -> Result<EvalValue, EvalValue> {
+        match self.arg(args, 0, ctx) {
+            EvalValue::Reference(r) => Ok(EvalValue::Number(f(Self::range_size(&r)) as f64)),
+            EvalValue::Array(rows) => {
+                let size = (
+                    rows.len() as u32,
+                    rows.first().map_or(0, |r| r.len()) as u32,
+                );
+                Ok(EvalValue::Number(f(size) as f64))
+            }
+            EvalValue::Error(e) => Err(EvalValue::Error(e)),
+            _ => Ok(EvalValue::Number(1.0)),
+        }
+    }
+
+    /// Parameter as array, with the number of rows and columns. For a range only
+    /// the used area is returned, the missing cells are empty.
+    fn array(&self, v: EvalValue) -> (Vec<Vec<EvalValue>>, (usize, usize)) {
+        match v {
+            EvalValue::Reference(r) => {
+                let (rows, cols) = Self::range_size(&r);
+                let cells = match self.range_value(&r) {
+                    EvalValue::Array(cells) => cells,
+                    v => vec![vec![v]],
+                };
+                (cells, (rows as usize, cols as usize))
+            }
+            EvalValue::Array(rows) => {
+                let size = (rows.len(), rows.first().map_or(0, |r| r.len()));
+                (rows, size)
+            }
+            v => (vec![vec![v]], (1, 1)),
+        }
+    }
+
+    fn sumproduct(&self, args: &[Expr], ctx: Ctx) -> Result<EvalValue, EvalValue> {
+        let arrays: Vec<_> = args.iter().map(|v| self.array(self.expr(v, ctx))).collect();
+        let Some((_, shape)) = arrays.first() else {
+            return Err(EvalValue::Error(ErrorValue::Value));
+        };
+        if arrays.iter().any(|(_, v)| v != shape) {
+            return Err(EvalValue::Error(ErrorValue::Value));
+        }
+        // Only the stored cells can be non-empty.
+        let rows = arrays.iter().map(|(a, _)| a.len()).max().unwrap_or(0);
+        let cols = arrays
+            .iter()
+            .flat_map(|(a, _)| a.iter().map(|r| r.len()))
+            .max()
+            .unwrap_or(0);
+        let mut sum = 0.0;
+        for r in 0..rows {
+            for c in 0..cols {
+                let mut prod = 1.0;
+                for (a, _) in arrays.iter() {
+                    prod *= match a.get(r).and_then(|v| v.get(c)) {
+                        Some(EvalValue::Number(v)) => *v,
+                        Some(EvalValue::Error(e)) => return Err(EvalValue::Error(*e)),
+                        _ => 0.0,
+                    };
+                }
+                sum += prod;
+            }
+        }
+        Ok(EvalValue::number(sum))
+    }
+
+    fn sum_pairs(
+        &self,
+        args: &[Expr],
+        ctx: Ctx,
+        f: fn(f64, f64) -> f64,
+    ) -> Result<EvalValue, EvalValue> {
+        if args.len() != 2 {
+            return Err(EvalValue::Error(ErrorValue::Value));
+        }
+        let (x, x_size) = self.array(self.arg(args, 0, ctx));
+        let (y, y_size) = self.array(self.arg(args, 1, ctx));
+        if x_size.0 * x_size.1 != y_size.0 * y_size.1 {
+            return Err(EvalValue::Error(ErrorValue::NA));
+        }
+        // Pairs by position in row order. Missing cells are empty and don't count.
+        if let Some(e) = y
+            .iter()
+            .flatten()
+            .find(|v| matches!(v, EvalValue::Error(_)))
+        {
+            return Err(e.clone());
+        }
+        let mut sum = 0.0;
+        for (r, row) in x.iter().enumerate() {
+            for (c, x) in row.iter().enumerate() {
+                let i = r * x_size.1 + c;
+                let y = y.get(i / y_size.1).and_then(|v| v.get(i % y_size.1));
+                match (x, y) {
+                    (EvalValue::Error(e), _) => return Err(EvalValue::Error(*e)),
+                    (EvalValue::Number(x), Some(EvalValue::Number(y))) => sum += f(*x, *y),
+                    _ => {}
+                }
+            }
+        }
+        Ok(EvalValue::number(sum))
+    }
+}
+
+/// Parses a number in a text. Only plain decimal notation is accepted.
+fn parse_number(v: &str) -> Option<f64> {
+    let v = v.trim();
+    if v.is_empty()
+        || !v
+            .chars()
+            .all(|c| c.is_ascii_digit() || matches!(c, '.' | 'e' | 'E' | '+' | '-'))
+    {
+        return None;
+    }
+    v.parse().ok()
+}
+
+/// Formats a number for text conversion.
+fn format_number(v: f64) -> String {
+    if v == v.trunc() && v.abs() < 1e15 {
+        format!("{}", v as i64)
+    } else {
+        format!("{}", v)
+    }
+}
+
+/// Sort order for comparisons: numbers < text < logical, text is compared without case.
+fn compare_values(a: &EvalValue, b: &EvalValue) -> Ordering {
+    fn rank(v: &EvalValue) -> u8 {
+        match v {
+            EvalValue::Number(_) | EvalValue::Empty => 0,
+            EvalValue::Text(_) => 1,
+            EvalValue::Logical(_) => 2,
+            _ => 3,
+        }
+    }
+    match (a, b) {
+        (EvalValue::Empty, EvalValue::Text(b)) => "".cmp(&b.to_lowercase()),
+        (EvalValue::Text(a), EvalValue::Empty) => a.to_lowercase().as_str().cmp(""),
+        (EvalValue::Empty, EvalValue::Logical(b)) => false.cmp(b),
+        (EvalValue::Logical(a), EvalValue::Empty) => a.cmp(&false),
+        (EvalValue::Empty, EvalValue::Empty) => Ordering::Equal,
+        (EvalValue::Empty, EvalValue::Number(b)) => 0.0.partial_cmp(b).unwrap_or(Ordering::Equal),
+        (EvalValue::Number(a), EvalValue::Empty) => a.partial_cmp(&0.0).unwrap_or(Ordering::Equal),
+        (EvalValue::Number(a), EvalValue::Number(b)) => a.partial_cmp(b).unwrap_or(Ordering::Equal),
+        (EvalValue::Text(a), EvalValue::Text(b)) => a.to_lowercase().cmp(&b.to_lowercase()),
+        (EvalValue::Logical(a), EvalValue::Logical(b)) => a.cmp(b),
+        (a, b) => rank(a).cmp(&rank(b)),
+    }
+}
+
+fn domain(v: f64) -> Result<f64, ErrorValue> {
+    if v.is_nan() {
+        Err(ErrorValue::Num)
+    } else {
+        Ok(v)
+    }
+}
+
+fn positive(v: f64) -> Result<f64, ErrorValue> {
+    if v <= 0.0 {
+        Err(ErrorValue::Num)
+    } else {
+        Ok(v)
+    }
+}
+
+fn div(a: f64, b: f64) -> Result<f64, ErrorValue> {
+    if b == 0.0 {
+        Err(ErrorValue::Div0)
+    } else {
+        Ok(a / b)
+    }
+}
+
+/// Rounds away from zero to a multiple of m.
+fn round_away(v: f64, m: f64) -> f64 {
+    (v.abs() / m).ceil() * m * v.signum()
+}
+
+fn fact(n: f64, step: f64) -> Result<f64, ErrorValue> {
+    if n < 0.0 {
+        return Err(ErrorValue::Num);
+    }
+    let mut res = 1.0;
+    let mut i = n;
+    while i > 1.0 {
+        res *= i;
+        // Overflows after a few hundred steps, stop before looping over a huge n.
+        if res.is_infinite() {
+            return Err(ErrorValue::Num);
+        }
+        i -= step;
+    }
+    Ok(res)
+}
+
+fn combin(n: f64, k: f64) -> Result<f64, ErrorValue> {
+    if n < 0.0 || k < 0.0 || k > n {
+        return Err(ErrorValue::Num);
+    }
+    let k = k.min(n - k);
+    let mut res = 1.0;
+    for i in 0..k as u64 {
+        res = res * (n - i as f64) / (i as f64 + 1.0);
+        if res.is_infinite() {
+            return Err(ErrorValue::Num);
+        }
+    }
+    Ok(res.round())
+}
+
+fn gcd_lcm(v: &[f64], gcd: bool) -> Result<EvalValue, EvalValue> {
+    fn g(a: f64, b: f64) -> f64 {
+        if b == 0.0 {
+            a
+        } else {
+            g(b, a % b)
+        }
+    }
+    let mut res: Option<f64> = None;
+    for n in v.iter().map(|v| v.trunc()) {
+        if n < 0.0 {
+            return Err(EvalValue::Error(ErrorValue::Num));
+        }
+        res = Some(match res {
+            None => n,
+            Some(r) if gcd => g(r, n),
+            Some(r) if r == 0.0 || n == 0.0 => 0.0,
+            Some(r) => r / g(r, n) * n,
+        });
+    }
+    Ok(EvalValue::number(res.unwrap_or(0.0)))
+}
@@ -0,0 +1,583 @@
+//!
+//! Renders expressions as Excel A1 formulas, for writing xlsx files.
+//!
+//! Uses ',' as separator, Sheet1!A1 references, TRUE/FALSE and the Excel
+//! function names. Functions that are newer than Excel 2007 get the _xlfn.
+//! prefix as required in the file format.
+//! Optional arguments that Excel requires are filled with the OpenFormula
+//! defaults.
+//!
+//! parse_excel() goes the other way and translates Excel formulas to
+//! OpenFormula, using the same name table.
+//!
+
+use crate::parse::{parse_with, EXCEL};
+use crate::{
+    function_info, functions, parse_expr, r1c1, Any, Expr, InfixOp, Namespace, ParseError, RefStyle,
+};
+use spreadsheet_ods::{CellRange, CellRef};
+use std::error::Error;
+use std::fmt;
+use std::fmt::{Display, Formatter, Write};
+
+/// OpenFormula function names that are different in Excel.
+pub(crate) const EXCEL_NAMES: &[(&str, &str)] = &[
+    ("ACOT", "_xlfn.ACOT"),
+    ("ACOTH", "_xlfn.ACOTH"),
+    ("ARABIC", "_xlfn.ARABIC"),
+    ("BASE", "_xlfn.BASE"),
+    ("BINOM.DIST.RANGE", "_xlfn.BINOM.DIST.RANGE"),
+    ("BITAND", "_xlfn.BITAND"),
+    ("BITLSHIFT", "_xlfn.BITLSHIFT"),
+    ("BITOR", "_xlfn.BITOR"),
+    ("BITRSHIFT", "_xlfn.BITRSHIFT"),
+    ("BITXOR", "_xlfn.BITXOR"),
+    ("CHISQDIST", "_xlfn.CHISQ.DIST"),
+    ("CHISQINV", "_xlfn.CHISQ.INV"),
+    ("COMBINA", "_xlfn.COMBINA"),
+    ("COM.MICROSOFT.CHISQ.TEST", "_xlfn.CHISQ.TEST"),
+    ("COM.MICROSOFT.CONFIDENCE.NORM", "_xlfn.CONFIDENCE.NORM"),
+    ("COM.MICROSOFT.CONFIDENCE.T", "_xlfn.CONFIDENCE.T"),
+    ("COM.MICROSOFT.TEXTJOIN", "_xlfn.TEXTJOIN"),
+    ("COT", "_xlfn.COT"),
+    ("COTH", "_xlfn.COTH"),
+    ("CSC", "_xlfn.CSC"),
+    ("CSCH", "_xlfn.CSCH"),
+    ("DAYS", "_xlfn.DAYS"),
+    ("DECIMAL", "_xlfn.DECIMAL"),
+    ("FDIST", "_xlfn.F.DIST"),
+    ("FINV", "_xlfn.F.INV"),
+    ("FORMULA", "_xlfn.FORMULATEXT"),
+    ("GAMMA", "_xlfn.GAMMA"),
+    ("GAUSS", "_xlfn.GAUSS"),
+    ("IFNA", "_xlfn.IFNA"),
+    ("IMCOSH", "_xlfn.IMCOSH"),
+    ("IMCOT", "_xlfn.IMCOT"),
+    ("IMCSC", "_xlfn.IMCSC"),
+    ("IMCSCH", "_xlfn.IMCSCH"),
+    ("IMSEC", "_xlfn.IMSEC"),
+    ("IMSECH", "_xlfn.IMSECH"),
+    ("IMSINH", "_xlfn.IMSINH"),
+    ("IMTAN", "_xlfn.IMTAN"),
+    ("ISFORMULA", "_xlfn.ISFORMULA"),
+    ("ISOWEEKNUM", "_xlfn.ISOWEEKNUM"),
+    ("JIS", "DBCS"),
+    ("LEGACY.CHIDIST", "CHIDIST"),
+    ("LEGACY.CHIINV", "CHIINV"),
+    ("LEGACY.CHITEST", "CHITEST"),
+    ("LEGACY.FDIST", "FDIST"),
+    ("LEGACY.FINV", "FINV"),
+    ("LEGACY.NORMSDIST", "NORMSDIST"),
+    ("LEGACY.NORMSINV", "NORMSINV"),
+    ("LEGACY.TDIST", "TDIST"),
+    ("LOGNORMDIST", "_xlfn.LOGNORM.DIST"),
+    ("MUNIT", "_xlfn.MUNIT"),
+    ("NUMBERVALUE", "_xlfn.NUMBERVALUE"),
+    ("PDURATION", "_xlfn.PDURATION"),
+    ("PHI", "_xlfn.PHI"),
+    ("RRI", "_xlfn.RRI"),
+    ("SEC", "_xlfn.SEC"),
+    ("SECH", "_xlfn.SECH"),
+    ("SHEET", "_xlfn.SHEET"),
+    ("SHEETS", "_xlfn.SHEETS"),
+    ("SKEWP", "_xlfn.SKEW.P"),
+    ("UNICHAR", "_xlfn.UNICHAR"),
+    ("UNICODE", "_xlfn.UNICODE"),
+    ("XOR", "_xlfn.XOR"),
+];
+
+/// Excel 2010 functions that are read as an older OpenFormula function with the
+/// same arguments. Only used for import, the export writes the older name.
+const EXCEL_ALIASES: &[(&str, &str)] = &[
+    ("_xlfn.BINOM.DIST", "BINOMDIST"),
+    ("_xlfn.CHISQ.DIST.RT", "LEGACY.CHIDIST"),
+    ("_xlfn.EXPON.DIST", "EXPONDIST"),
+    ("_xlfn.F.DIST.RT", "LEGACY.FDIST"),
+    ("_xlfn.GAMMA.DIST", "GAMMADIST"),
+    ("_xlfn.MODE.SNGL", "MODE"),
+    ("_xlfn.NORM.DIST", "NORMDIST"),
+    ("_xlfn.NORM.INV", "NORMINV"),
+    ("_xlfn.PERCENTILE.INC", "PERCENTILE"),
+    ("_xlfn.POISSON.DIST", "POISSON"),
+    ("_xlfn.QUARTILE.INC", "QUARTILE"),
+    ("_xlfn.RANK.EQ", "RANK"),
+    ("_xlfn.STDEV.P", "STDEVP"),
+    ("_xlfn.STDEV.S", "STDEV"),
+    ("_xlfn.T.INV.2T", "TINV"),
+    ("_xlfn.VAR.S", "VAR"),
+];
+
+/// OpenFormula functions without an Excel equivalent.
+const NO_EXCEL: &[&str] = &["DDE", "MULTIPLE.OPERATIONS"];
+
+/// Functions with optional arguments in OpenFormula that are required in Excel.
+/// Holds the number of required arguments and the defaults of the optional ones.
+const DEFAULTS: &[(&str, usize, &[&str])] = &[
+    ("CEILING", 1, &["1"]),
+    ("CHISQDIST", 2, &["TRUE"]),
+    ("EXPONDIST", 2, &["TRUE"]),
+    ("FDIST", 3, &["TRUE"]),
+    ("FLOOR", 1, &["1"]),
+    ("GAMMADIST", 3, &["TRUE"]),
+    ("LOGINV", 1, &["0", "1"]),
+    ("LOGNORMDIST", 1, &["0", "1", "TRUE"]),
+    ("NORMDIST", 3, &["TRUE"]),
+    ("POISSON", 2, &["TRUE"]),
+    ("ROUND", 1, &["0"]),
+    ("ROUNDDOWN", 1, &["0"]),
+    ("ROUNDUP", 1, &["0"]),
+];
+
+/// Constructs that have no Excel equivalent.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ExcelError {
+    /// Function that doesn't exist in Excel.
+    UnsupportedFunction(String),
+    /// Reference to another document.
+    ExternalReference(String),
+    /// Formula text that could not be parsed.
+    Parse(ParseError),
+    /// The buffer returned an error.
+    Write,
+}
+
+impl Display for ExcelError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            ExcelError::UnsupportedFunction(name) => {
+                write!(f, "function {} has no Excel equivalent", name)
+            }
+            ExcelError::ExternalReference(iri) => {
+                write!(f, "external reference to {} is not supported", iri)
+            }
+            ExcelError::Parse(err) => write!(f, "{}", err),
+            ExcelError::Write => write!(f, "write error"),
+        }
+    }
+}
+
+impl Error for ExcelError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ExcelError::Parse(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<ParseError> for ExcelError {
+    fn from(err: ParseError) -> Self {
+        ExcelError::Parse(err)
+    }
+}
+
+impl From<fmt::Error> for ExcelError {
+    fn from(_: fmt::Error) -> Self {
+        ExcelError::Write
+    }
+}
+
+/// Excel name for an OpenFormula function.
+pub fn excel_name(name: &str) -> Result<&str, ExcelError> {
+    if let Some((_, excel)) = EXCEL_NAMES.iter().find(|(of, _)| *of == name) {
+        return Ok(excel);
+    }
+    let supported = match function_info(name) {
+        Some(info) => {
+            !NO_EXCEL.contains(&info.name)
+                && matches!(
+                    info.namespace,
+                    Namespace::OpenFormula | Namespace::Microsoft
+                )
+        }
+        None => false,
+    };
+    if supported {
+        Ok(name)
+    } else {
+        Err(ExcelError::UnsupportedFunction(name.to_string()))
+    }
+}
+
+/// OpenFormula name for an Excel function. The _xlfn. prefix is optional.
+pub fn excel_import_name(name: &str) -> Result<&'static str, ExcelError> {
+    let upper = name.to_ascii_uppercase();
+    let local = unprefixed(&upper);
+    if let Some((of, _)) = EXCEL_NAMES
+        .iter()
+        .find(|(_, excel)| unprefixed(excel) == local)
+    {
+        return Ok(of);
+    }
+    if let Some((_, of)) = EXCEL_ALIASES
+        .iter()
+        .find(|(excel, _)| unprefixed(excel) == local)
+    {
+        return Ok(of);
+    }
+    let found = match function_info(local) {
+        Some(info) if info.namespace == Namespace::OpenFormula => Some(info),
+        _ => functions()
+            .iter()
+            .find(|v| v.namespace == Namespace::Microsoft && v.local_name() == local),
+    };
+    match found {
+        Some(info) if !NO_EXCEL.contains(&info.name) => Ok(info.name),
+        _ => Err(ExcelError::UnsupportedFunction(name.to_string())),
+    }
+}
+
+/// Converts the OpenFormula workdays array, Sunday to Saturday with non-zero for
+/// days off, to the Excel weekend mask "0000011" that starts with Monday.
+/// None if the array is not constant.
+fn weekend_mask(workdays: &Expr) -> Option<Expr> {
+    let Expr::Array(rows) = workdays else {
+        return None;
+    };
+    let [days] = rows.as_ref() else {
+        return None;
+    };
+    if days.len() != 7 {
+        return None;
+    }
+    let mut mask = String::with_capacity(7);
+    for day in days[1..].iter().chain(&days[..1]) {
+        let off = match day {
+            Expr::Logical(v) => *v,
+            Expr::Number(v) => v.parse::<f64>().ok()? != 0.0,
+            _ => return None,
+        };
+        mask.push(if off { '1' } else { '0' });
+    }
+    Some(Expr::Text(mask))
+}
+
+fn unprefixed(name: &str) -> &str {
+    name.strip_prefix("_XLFN.")
+        .or_else(|| name.strip_prefix("_xlfn."))
+        .or_else(|| name.strip_prefix("_XLWS."))
+        .unwrap_or(name)
+}
+
+/// Translates an Excel formula to OpenFormula. The leading '=' is optional.
+pub fn parse_excel(formula: &str) -> Result<Expr, ExcelError> {
+    let start = if formula.starts_with('=') { 1 } else { 0 };
+    let expr = parse_with(&formula[start..], &EXCEL).map_err(|mut e| {
+        e.span = e.span.start + start..e.span.end + start;
+        e
+    })?;
+    import(expr)
+}
+
+// Maps function names and the TRUE/FALSE constants.
+fn import(expr: Expr) -> Result<Expr, ExcelError> {
+    Ok(match expr {
+        Expr::Name(v) if v.eq_ignore_ascii_case("TRUE") => Expr::Logical(true),
+        Expr::Name(v) if v.eq_ignore_ascii_case("FALSE") => Expr::Logical(false),
+        Expr::Func { name, args } if unprefixed(&name.to_ascii_uppercase()) == "BETA.DIST" => {
+            // BETADIST has the cumulative flag after the optional bounds.
+            let mut args = args
+                .iter()
+                .cloned()
+                .map(import)
+                .collect::<Result<Vec<_>, _>>()?;
+            if args.len() >= 4 {
+                let cumulative = args.remove(3);
+                if args.len() == 3 {
+                    args.push(0.expr());
+                }
+                if args.len() == 4 {
+                    args.push(1.expr());
+                }
+                args.push(cumulative);
+            }
+            Expr::func("BETADIST", args)
+        }
+        Expr::Func { name, args } => Expr::func(
+            excel_import_name(&name)?,
+            args.iter()
+                .cloned()
+                .map(import)
+                .collect::<Result<Vec<_>, _>>()?,
+        ),
+        Expr::Infix { op, lhs, rhs } => {
+            Expr::infix(import((*lhs).clone())?, op, import((*rhs).clone())?)
+        }
+        Expr::Prefix { op, expr } => Expr::prefix(op, import((*expr).clone())?),
+        Expr::Postfix { op, expr } => Expr::postfix(import((*expr).clone())?, op),
+        Expr::Parentheses(v) => Expr::parentheses(import((*v).clone())?),
+        v => v,
+    })
+}
+
+/// Creates an Excel formula, starting with '='.
+pub fn excel_formula<T: Any>(f: T) -> Result<String, ExcelError> {
+    let mut buf = String::new();
+    excel_formula_into(&mut buf, f)?;
+    Ok(buf)
+}
+
+/// Creates an Excel formula with R1C1 references relative to the anchor cell.
+pub fn excel_formula_r1c1<T: Any>(f: T, anchor: &CellRef) -> Result<String, ExcelError> {
+    let mut buf = String::new();
+    excel_formula_styled_into(
+        &mut buf,
+        f,
+        RefStyle::R1C1 {
+            row: anchor.row(),
+            col: anchor.col(),
+        },
+    )?;
+    Ok(buf)
+}
+
+/// Writes an Excel formula, starting with '=', into the buffer.
+/// On error the buffer may contain part of the formula.
+pub fn excel_formula_into<W: Write + ?Sized, T: Any>(buf: &mut W, f: T) -> Result<(), ExcelError> {
+    excel_formula_styled_into(buf, f, RefStyle::A1)
+}
+
+/// Writes an Excel formula with the given reference style into the buffer.
+pub fn excel_formula_styled_into<W: Write + ?Sized, T: Any>(
+    buf: &mut W,
+    f: T,
+    style: RefStyle,
+) -> Result<(), ExcelError> {
+    buf.write_char('=')?;
+    ExcelWriter { buf, style }.expr(&f.expr())
+}
+
+struct ExcelWriter<'a, W: Write + ?Sized> {
+    buf: &'a mut W,
+    style: RefStyle,
+}
+
+impl<'a, W: Write + ?Sized> ExcelWriter<'a, W> {
+    fn expr(&mut self, expr: &Expr) -> Result<(), ExcelError> {
+        match expr {
+            Expr::Number(v) => self.buf.write_str(v)?,
+            Expr::Text(v) => crate::expr::text_literal(self.buf, v)?,
+            Expr::Logical(v) => self.buf.write_str(if *v { "TRUE" } else { "FALSE" })?,
+            Expr::CellRef(v) => self.cellref(v)?,
+            Expr::CellRange(v) => self.cellrange(v)?,
+            Expr::Name(v) => self.buf.write_str(v)?,
+            Expr::Error(v) => self.buf.write_str(v.as_str())?,
+            Expr::Missing => {}
+            Expr::Func { name, args } if name == "BETADIST" && args.len() == 6 => {
+                // BETADIST in Excel is always cumulative. BETA.DIST has the flag
+                // before the bounds.
+                let args = [0, 1, 2, 5, 3, 4].map(|i| args[i].clone());
+                self.call("_xlfn.BETA.DIST", &args)?;
+            }
+            Expr::Func { name, args }
+                if (name == "CEILING" || name == "FLOOR") && args.len() == 3 =>
+            {
+                // Excel takes the mode only in CEILING.MATH and FLOOR.MATH.
+                let significance = match &args[1] {
+                    Expr::Missing => 1.expr(),
+                    v => v.clone(),
+                };
+                let args = [args[0].clone(), significance, args[2].clone()];
+                if name == "CEILING" {
+                    self.call("_xlfn.CEILING.MATH", &args)?;
+                } else {
+                    self.call("_xlfn.FLOOR.MATH", &args)?;
+                }
+            }
+            Expr::Func { name, args } if name == "HYPGEOMDIST" && args.len() == 5 => {
+                self.call("_xlfn.HYPGEOM.DIST", args)?;
+            }
+            Expr::Func { name, args }
+                if (name == "NETWORKDAYS" || name == "WORKDAY") && args.len() == 4 =>
+            {
+                // The INTL variants take a weekend mask before the holidays.
+                let Some(mask) = weekend_mask(&args[3]) else {
+                    return Err(ExcelError::UnsupportedFunction(name.to_string()));
+                };
+                let args = [args[0].clone(), args[1].clone(), mask, args[2].clone()];
+                if name == "NETWORKDAYS" {
+                    self.call("_xlfn.NETWORKDAYS.INTL", &args)?;
+                } else {
+                    self.call("_xlfn.WORKDAY.INTL", &args)?;
+                }
+            }
+            Expr::Func { name, args } => {
+                self.buf.write_str(excel_name(name)?)?;
+                self.buf.write_char('(')?;
+                self.separated(args, ',')?;
+                if let Some((_, required, defaults)) = DEFAULTS.iter().find(|(v, _, _)| v == name) {
+                    if args.len() >= *required {
+                        for v in defaults.iter().skip(args.len() - required) {
+                            self.buf.write_char(',')?;
+                            self.buf.write_str(v)?;
+                        }
+                    }
+                }
+                self.buf.write_char(')')?;
+            }
+            Expr::Infix {
+                op: InfixOp::RefConcat,
+                lhs,
+                rhs,
+            } => {
+                // The union needs parentheses to be distinct from the
+                // argument separator.
+                self.buf.write_char('(')?;
+                self.operand(lhs, InfixOp::RefConcat.precedence())?;
+                self.buf.write_char(',')?;
+                self.operand(rhs, InfixOp::RefConcat.precedence() + 1)?;
+                self.buf.write_char(')')?;
+            }
+            Expr::Infix { op, lhs, rhs } => {
+                self.operand(lhs, op.precedence())?;
+                match op {
+                    InfixOp::Intersect => self.buf.write_char(' ')?,
+                    op => write!(self.buf, "{}", op)?,
+                }
+                self.operand(rhs, op.precedence() + 1)?;
+            }
+            Expr::Prefix { op, expr: v } => {
+                write!(self.buf, "{}", op)?;
+                self.operand(v, expr.precedence())?;
+            }
+            Expr::Postfix { op, expr: v } => {
+                self.operand(v, expr.precedence())?;
+                write!(self.buf, "{}", op)?;
+            }
+            Expr::Parentheses(v) => {
+                self.buf.write_char('(')?;
+                self.expr(v)?;
+                self.buf.write_char(')')?;
+            }
+            Expr::Array(rows) => {
+                self.buf.write_char('{')?;
+                for (i, r) in rows.iter().enumerate() {
+                    if i > 0 {
+                        self.buf.write_char(';')?;
+                    }
+                    self.separated(r, ',')?;
+                }
+                self.buf.write_char('}')?;
+            }
+            Expr::List(list) => self.separated(list, ',')?,
+            Expr::Raw(v) => self.expr(&parse_expr(v)?)?,
+        }
+        Ok(())
+    }
+
+    fn operand(&mut self, expr: &Expr, min_precedence: u8) -> Result<(), ExcelError> {
+        if expr.precedence() < min_precedence {
+            self.buf.write_char('(')?;
+            self.expr(expr)?;
+            self.buf.write_char(')')?;
+            Ok(())
+        } else {
+            self.expr(expr)
+        }
+    }
+
+    fn call(&mut self, name: &str, args: &[Expr]) -> Result<(), ExcelError> {
+        self.buf.write_str(name)?;
+        self.buf.write_char('(')?;
+        self.separated(args, ',')?;
+        self.buf.write_char(')')?;
+        Ok(())
+    }
+
+    fn separated(&mut self, list: &[Expr], sep: char) -> Result<(), ExcelError> {
+        for (i, v) in list.iter().enumerate() {
+            if i > 0 {
+                self.buf.write_char(sep)?;
+            }
+            self.expr(v)?;
+        }
+        Ok(())
+    }
+
+    fn cellref(&mut self, r: &CellRef) -> Result<(), ExcelError> {
+        if let Some(iri) = r.iri() {
+            return Err(ExcelError::ExternalReference(iri.clone()));
+        }
+        if let Some(table) = r.table() {
+            sheet_name(self.buf, table, None)?;
+        }
+        self.cell(r.col_abs(), r.col(), r.row_abs(), r.row())?;
+        Ok(())
+    }
+
+    fn cellrange(&mut self, r: &CellRange) -> Result<(), ExcelError> {
+        if let Some(iri) = r.iri() {
+            return Err(ExcelError::ExternalReference(iri.clone()));
+        }
+        if let Some(table) = r.table() {
+            let to_table = r.to_table().filter(|v| *v != table);
+            sheet_name(self.buf, table, to_table.map(|v| v.as_str()))?;
+        }
+        self.cell(r.col_abs(), r.col(), r.row_abs(), r.row())?;
+        self.buf.write_char(':')?;
+        self.cell(r.to_col_abs(), r.to_col(), r.to_row_abs(), r.to_row())?;
+        Ok(())
+    }
+
+    fn cell(&mut self, col_abs: bool, col: u32, row_abs: bool, row: u32) -> fmt::Result {
+        match self.style {
+            RefStyle::A1 => {
+                if col_abs {
+                    self.buf.write_char('$')?;
+                }
+                col_name(self.buf, col)?;
+                if row_abs {
+                    self.buf.write_char('$')?;
+                }
+                write!(self.buf, "{}", row + 1)
+            }
+            RefStyle::R1C1 {
+                row: anchor_row,
+                col: anchor_col,
+            } => r1c1(self.buf, row_abs, row, col_abs, col, anchor_row, anchor_col),
+        }
+    }
+}
+
+/// Writes Sheet1! or Sheet1:Sheet3! with quotes where necessary.
+fn sheet_name<W: Write + ?Sized>(buf: &mut W, table: &str, to_table: Option<&str>) -> fmt::Result {
+    let plain = |s: &str| {
+        !s.is_empty()
+            && !s.starts_with(|c: char| c.is_ascii_digit())
+            && s.chars().all(|c| c.is_alphanumeric() || c == '_')
+    };
+    let quote = !plain(table) || to_table.is_some_and(|v| !plain(v));
+    if quote {
+        buf.write_char('\'')?;
+    }
+    for (i, t) in std::iter::once(table).chain(to_table).enumerate() {
+        if i > 0 {
+            buf.write_char(':')?;
+        }
+        if quote {
+            buf.write_str(&t.replace('\'', "''"))?;
+        } else {
+            buf.write_str(t)?;
+        }
+    }
+    if quote {
+        buf.write_char('\'')?;
+    }
+    buf.write_char('!')
+}
+
+/// Writes the column letters for a 0-based column index.
+pub(crate) fn col_name<W: Write + ?Sized>(buf: &mut W, col: u32) -> fmt::Result {
+    let mut letters = [0u8; 8];
+    let mut n = col + 1;
+    let mut i = letters.len();
+    while n > 0 {
+        i -= 1;
+        letters[i] = b'A' + ((n - 1) % 26) as u8;
+        n = (n - 1) / 26;
+    }
+    for c in &letters[i..] {
+        buf.write_char(*c as char)?;
+    }
+    Ok(())
+}
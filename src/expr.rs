@@ -0,0 +1,462 @@
+//!
+//! Expression tree behind the formula value types.
+//!
+//! Every FAny, FNumber, FText, ... holds an Expr. It can be retrieved with
+//! Any::expr() and walked or rewritten, and it's rendered via Any::formula().
+//!
+
+use crate::{cellrange_r1c1, cellref_r1c1, Any};
+use spreadsheet_ods::{CellRange, CellRef};
+use std::borrow::Cow;
+use std::fmt::{self, Display, Formatter, Write};
+use std::sync::Arc;
+
+/// Binary operators.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InfixOp {
+    /// +
+    Add,
+    /// -
+    Sub,
+    /// *
+    Mul,
+    /// /
+    Div,
+    /// ^
+    Pow,
+    /// &
+    Concat,
+    /// =
+    Eq,
+    /// <>
+    Ne,
+    /// <
+    Lt,
+    /// <=
+    Le,
+    /// >
+    Gt,
+    /// >=
+    Ge,
+    /// Reference intersection !
+    Intersect,
+    /// Reference concatenation ~
+    RefConcat,
+    /// Reference range :
+    Range,
+}
+
+// Operator precedence, higher binds tighter.
+const PREC_PREFIX: u8 = 7;
+const PREC_POSTFIX: u8 = 6;
+const PREC_ATOM: u8 = 11;
+
+impl InfixOp {
+    /// Precedence according to the OpenFormula operator table. Higher binds tighter.
+    pub fn precedence(&self) -> u8 {
+        match self {
+            InfixOp::Eq | InfixOp::Ne | InfixOp::Lt | InfixOp::Le | InfixOp::Gt | InfixOp::Ge => 1,
+            InfixOp::Concat => 2,
+            InfixOp::Add | InfixOp::Sub => 3,
+            InfixOp::Mul | InfixOp::Div => 4,
+            InfixOp::Pow => 5,
+            InfixOp::RefConcat => 8,
+            InfixOp::Intersect => 9,
+            InfixOp::Range => 10,
+        }
+    }
+}
+
+impl Display for InfixOp {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let op = match self {
+            InfixOp::Add => "+",
+            InfixOp::Sub => "-",
+            InfixOp::Mul => "*",
+            InfixOp::Div => "/",
+            InfixOp::Pow => "^",
+            InfixOp::Concat => "&",
+            InfixOp::Eq => "=",
+            InfixOp::Ne => "<>",
+            InfixOp::Lt => "<",
+            InfixOp::Le => "<=",
+            InfixOp::Gt => ">",
+            InfixOp::Ge => ">=",
+            InfixOp::Intersect => "!",
+            InfixOp::RefConcat => "~",
+            InfixOp::Range => ":",
+        };
+        f.write_str(op)
+    }
+}
+
+/// Prefix operators.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PrefixOp {
+    /// -
+    Neg,
+    /// +
+    Plus,
+}
+
+impl Display for PrefixOp {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PrefixOp::Neg => f.write_str("-"),
+            PrefixOp::Plus => f.write_str("+"),
+        }
+    }
+}
+
+/// Postfix operators.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PostfixOp {
+    /// %
+    Percent,
+}
+
+impl Display for PostfixOp {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PostfixOp::Percent => f.write_str("%"),
+        }
+    }
+}
+
+/// Error values. The discriminant is the code returned by ERROR.TYPE.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ErrorValue {
+    /// #NULL! Intersection of references is empty.
+    Null = 1,
+    /// #DIV/0! Division by zero.
+    Div0 = 2,
+    /// #VALUE! Wrong type of value.
+    Value = 3,
+    /// #REF! Invalid reference.
+    Ref = 4,
+    /// #NAME? Unknown name.
+    Name = 5,
+    /// #NUM! Invalid number or result.
+    Num = 6,
+    /// #N/A Value not available.
+    NA = 7,
+    /// #GETTING_DATA Value is still being computed.
+    GettingData = 8,
+}
+
+impl ErrorValue {
+    /// All error values in order of their code.
+    pub const ALL: [ErrorValue; 8] = [
+        ErrorValue::Null,
+        ErrorValue::Div0,
+        ErrorValue::Value,
+        ErrorValue::Ref,
+        ErrorValue::Name,
+        ErrorValue::Num,
+        ErrorValue::NA,
+        ErrorValue::GettingData,
+    ];
+
+    /// Code as returned by ERROR.TYPE.
+    pub fn code(&self) -> u32 {
+        *self as u32
+    }
+
+    /// Error value for an ERROR.TYPE code.
+    pub fn from_code(code: u32) -> Option<ErrorValue> {
+        Self::ALL.iter().copied().find(|v| v.code() == code)
+    }
+
+    /// The literal as written in a formula.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ErrorValue::Null => "#NULL!",
+            ErrorValue::Div0 => "#DIV/0!",
+            ErrorValue::Value => "#VALUE!",
+            ErrorValue::Ref => "#REF!",
+            ErrorValue::Name => "#NAME?",
+            ErrorValue::Num => "#NUM!",
+            ErrorValue::NA => "#N/A",
+            ErrorValue::GettingData => "#GETTING_DATA",
+        }
+    }
+
+    /// Error value for a literal like #DIV/0!
+    pub fn from_literal(literal: &str) -> Option<ErrorValue> {
+        Self::ALL.iter().copied().find(|v| v.as_str() == literal)
+    }
+}
+
+impl Display for ErrorValue {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Node of the expression tree. Subtrees are shared, so cloning is cheap.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Expr {
+    /// Number literal as written in the formula.
+    Number(String),
+    /// Text literal, not escaped.
+    Text(String),
+    /// Logical literal, written as TRUE() or FALSE().
+    Logical(bool),
+    /// Cell reference.
+    CellRef(CellRef),
+    /// Cell range.
+    CellRange(CellRange),
+    /// Named expression.
+    Name(String),
+    /// Error literal like #DIV/0!
+    Error(ErrorValue),
+    /// An omitted value. Used for skipped function parameters.
+    Missing,
+    /// Function call.
+    Func {
+        name: Cow<'static, str>,
+        args: Arc<[Expr]>,
+    },
+    /// Binary operator.
+    Infix {
+        op: InfixOp,
+        lhs: Arc<Expr>,
+        rhs: Arc<Expr>,
+    },
+    /// Prefix operator.
+    Prefix { op: PrefixOp, expr: Arc<Expr> },
+    /// Postfix operator.
+    Postfix { op: PostfixOp, expr: Arc<Expr> },
+    /// Expression in parentheses.
+    Parentheses(Arc<Expr>),
+    /// Inline array, a list of rows.
+    Array(Arc<[Vec<Expr>]>),
+    /// List of values separated by ';'. Is flattened when used as function parameter.
+    List(Arc<[Expr]>),
+    /// Formula text that is not available as expression tree.
+    /// Used by the default implementation of Any::expr().
+    Raw(String),
+}
+
+impl Expr {
+    /// Function call. List parameters are flattened into the parameter list.
+    pub fn func<S: Into<Cow<'static, str>>>(name: S, args: Vec<Expr>) -> Self {
+        let mut flat = Vec::with_capacity(args.len());
+        flatten(&mut flat, args);
+        Expr::Func {
+            name: name.into(),
+            args: flat.into(),
+        }
+    }
+
+    /// Binary operation.
+    pub fn infix(lhs: Expr, op: InfixOp, rhs: Expr) -> Self {
+        Expr::Infix {
+            op,
+            lhs: Arc::new(lhs),
+            rhs: Arc::new(rhs),
+        }
+    }
+
+    /// Prefix operation.
+    pub fn prefix(op: PrefixOp, expr: Expr) -> Self {
+        Expr::Prefix {
+            op,
+            expr: Arc::new(expr),
+        }
+    }
+
+    /// Postfix operation.
+    pub fn postfix(expr: Expr, op: PostfixOp) -> Self {
+        Expr::Postfix {
+            op,
+            expr: Arc::new(expr),
+        }
+    }
+
+    /// Expression in parentheses.
+    pub fn parentheses(expr: Expr) -> Self {
+        Expr::Parentheses(Arc::new(expr))
+    }
+
+    /// Precedence of the expression when used as an operand.
+    pub fn precedence(&self) -> u8 {
+        match self {
+            Expr::Infix { op, .. } => op.precedence(),
+            Expr::Prefix { .. } => PREC_PREFIX,
+            Expr::Postfix { .. } => PREC_POSTFIX,
+            Expr::Number(v) if v.starts_with('-') => PREC_PREFIX,
+            // Unknown content, needs parentheses.
+            Expr::Raw(_) | Expr::List(_) => 0,
+            _ => PREC_ATOM,
+        }
+    }
+
+    /// Direct subexpressions, left to right.
+    pub fn children(&self) -> Vec<&Expr> {
+        match self {
+            Expr::Number(_)
+            | Expr::Text(_)
+            | Expr::Logical(_)
+            | Expr::CellRef(_)
+            | Expr::CellRange(_)
+            | Expr::Name(_)
+            | Expr::Error(_)
+            | Expr::Missing
+            | Expr::Raw(_) => Vec::new(),
+            Expr::Func { args, .. } => args.iter().collect(),
+            Expr::Infix { lhs, rhs, .. } => vec![lhs.as_ref(), rhs.as_ref()],
+            Expr::Prefix { expr, .. } => vec![expr.as_ref()],
+            Expr::Postfix { expr, .. } => vec![expr.as_ref()],
+            Expr::Parentheses(expr) => vec![expr.as_ref()],
+            Expr::Array(rows) => rows.iter().flatten().collect(),
+            Expr::List(list) => list.iter().collect(),
+        }
+    }
+}
+
+fn flatten(flat: &mut Vec<Expr>, args: impl IntoIterator<Item = Expr>) {
+    for arg in args {
+        match arg {
+            Expr::List(list) => flatten(flat, list.iter().cloned()),
+            arg => flat.push(arg),
+        }
+    }
+}
+
+/// Writes a quoted text literal.
+pub(crate) fn text_literal<W: Write + ?Sized>(buf: &mut W, text: &str) -> fmt::Result {
+    buf.write_char('"')?;
+    for (i, s) in text.split('"').enumerate() {
+        if i > 0 {
+            buf.write_str("\"\"")?;
+        }
+        buf.write_str(s)?;
+    }
+    buf.write_char('"')
+}
+
+/// How cell references are written.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RefStyle {
+    /// Column letters and row numbers, A1.
+    #[default]
+    A1,
+    /// R1C1 notation. Relative parts are written as offsets to this anchor cell.
+    /// In OpenFormula this is only a format for comparing formulas, see
+    /// formula_r1c1().
+    R1C1 { row: u32, col: u32 },
+}
+
+/// Writes an operand, in parentheses if it binds weaker than required.
+fn operand<W: Write + ?Sized>(
+    buf: &mut W,
+    expr: &Expr,
+    min_precedence: u8,
+    style: RefStyle,
+) -> fmt::Result {
+    if expr.precedence() < min_precedence {
+        buf.write_char('(')?;
+        expr.formula_styled(buf, style)?;
+        buf.write_char(')')
+    } else {
+        expr.formula_styled(buf, style)
+    }
+}
+
+fn separated<W: Write + ?Sized>(
+    buf: &mut W,
+    list: &[Expr],
+    sep: char,
+    style: RefStyle,
+) -> fmt::Result {
+    for (i, v) in list.iter().enumerate() {
+        if i > 0 {
+            buf.write_char(sep)?;
+        }
+        v.formula_styled(buf, style)?;
+    }
+    Ok(())
+}
+
+impl Expr {
+    /// Writes the formula text into the buffer, without the "of=" prefix.
+    /// The whole tree is written in one pass without intermediate strings.
+    pub fn formula_into<W: Write + ?Sized>(&self, buf: &mut W) -> fmt::Result {
+        self.formula_styled(buf, RefStyle::A1)
+    }
+
+    /// Writes the formula text with the given reference style.
+    pub fn formula_styled<W: Write + ?Sized>(&self, buf: &mut W, style: RefStyle) -> fmt::Result {
+        match self {
+            Expr::Number(v) => buf.write_str(v),
+            Expr::Text(v) => text_literal(buf, v),
+            Expr::Logical(v) => buf.write_str(if *v { "TRUE()" } else { "FALSE()" }),
+            Expr::CellRef(v) => match style {
+                RefStyle::A1 => buf.write_str(&v.to_formula()),
+                RefStyle::R1C1 { row, col } => cellref_r1c1(buf, v, row, col),
+            },
+            Expr::CellRange(v) => match style {
+                RefStyle::A1 => buf.write_str(&v.to_formula()),
+                RefStyle::R1C1 { row, col } => cellrange_r1c1(buf, v, row, col),
+            },
+            Expr::Name(v) => buf.write_str(v),
+            Expr::Error(v) => buf.write_str(v.as_str()),
+            Expr::Missing => Ok(()),
+            Expr::Func { name, args } => {
+                buf.write_str(name)?;
+                buf.write_char('(')?;
+                separated(buf, args, ';', style)?;
+                buf.write_char(')')
+            }
+            Expr::Infix { op, lhs, rhs } => {
+                // All binary operators are left associative.
+                operand(buf, lhs, op.precedence(), style)?;
+                write!(buf, "{}", op)?;
+                operand(buf, rhs, op.precedence() + 1, style)
+            }
+            Expr::Prefix { op, expr } => {
+                write!(buf, "{}", op)?;
+                operand(buf, expr, PREC_PREFIX, style)
+            }
+            Expr::Postfix { op, expr } => {
+                operand(buf, expr, PREC_POSTFIX, style)?;
+                write!(buf, "{}", op)
+            }
+            Expr::Parentheses(expr) => {
+                buf.write_char('(')?;
+                expr.formula_styled(buf, style)?;
+                buf.write_char(')')
+            }
+            Expr::Array(rows) => {
+                buf.write_char('{')?;
+                for (i, r) in rows.iter().enumerate() {
+                    if i > 0 {
+                        buf.write_char('|')?;
+                    }
+                    separated(buf, r, ';', style)?;
+                }
+                buf.write_char('}')
+            }
+            Expr::List(list) => separated(buf, list, ';', style),
+            Expr::Raw(v) => buf.write_str(v),
+        }
+    }
+}
+
+impl Any for Expr {
+    fn formula(&self, buf: &mut String) {
+        // Writing to a String can't fail.
+        let _ = self.formula_into(buf);
+    }
+
+    fn expr(&self) -> Expr {
+        self.clone()
+    }
+}
+
+impl Display for Expr {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        self.formula_into(f)
+    }
+}
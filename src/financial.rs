@@ -0,0 +1,1719 @@
+use crate::{
+    func, func1, func2, func3, func4, func5, Array, DateTimeParam, DayCountBasis, FNumber, Number,
+    Param, Sequence,
+};
+
+/// Payment type for the time-value-of-money functions.
+pub enum PaymentType {
+    /// Payments are due at the end of each period.
+    End,
+    /// Payments are due at the beginning of each period.
+    Begin,
+}
+impl Param for PaymentType {
+    type ParamType<'a> = u32;
+
+    fn as_param(&self) -> Self::ParamType<'_> {
+        match self {
+            PaymentType::End => 0,
+            PaymentType::Begin => 1,
+        }
+    }
+}
+
+/// Coupon payment frequency for the fixed-income functions.
+pub enum Frequency {
+    /// One coupon payment per year.
+    Annual,
+    /// Two coupon payments per year.
+    Semiannual,
+    /// Four coupon payments per year.
+    Quarterly,
+}
+impl Param for Frequency {
+    type ParamType<'a> = u32;
+
+    fn as_param(&self) -> Self::ParamType<'_> {
+        match self {
+            Frequency::Annual => 1,
+            Frequency::Semiannual => 2,
+            Frequency::Quarterly => 4,
+        }
+    }
+}
+
+/// Calculation method for ACCRINT()
+pub enum AccrintMethod {
+    /// Accrued interest from the issue date.
+    FromIssue,
+    /// Accrued interest from the first interest date.
+    FromFirstInterest,
+}
+impl Param for AccrintMethod {
+    type ParamType<'a> = bool;
+
+    fn as_param(&self) -> Self::ParamType<'_> {
+        match self {
+            AccrintMethod::FromIssue => true,
+            AccrintMethod::FromFirstInterest => false,
+        }
+    }
+}
+
+/// Calculates the accrued interest for a security that pays periodic interest.
+#[inline]
+pub fn accrint(
+    issue: impl DateTimeParam,
+    first_interest: impl DateTimeParam,
+    settlement: impl DateTimeParam,
+    rate: impl Number,
+    par: impl Number,
+    frequency: Frequency,
+) -> FNumber {
+    FNumber(func(
+        "ACCRINT",
+        &[
+            &issue,
+            &first_interest,
+            &settlement,
+            &rate,
+            &par,
+            &frequency.as_param(),
+        ],
+    ))
+}
+
+/// Calculates the accrued interest for a security that pays periodic interest.
+#[inline]
+pub fn accrint_basis(
+    issue: impl DateTimeParam,
+    first_interest: impl DateTimeParam,
+    settlement: impl DateTimeParam,
+    rate: impl Number,
+    par: impl Number,
+    frequency: Frequency,
+    basis: DayCountBasis,
+) -> FNumber {
+    FNumber(func(
+        "ACCRINT",
+        &[
+            &issue,
+            &first_interest,
+            &settlement,
+            &rate,
+            &par,
+            &frequency.as_param(),
+            &basis.as_param(),
+        ],
+    ))
+}
+
+/// Calculates the accrued interest for a security that pays periodic interest.
+#[inline]
+#[allow(clippy::too_many_arguments)]
+pub fn accrint_basis_method(
+    issue: impl DateTimeParam,
+    first_interest: impl DateTimeParam,
+    settlement: impl DateTimeParam,
+    rate: impl Number,
+    par: impl Number,
+    frequency: Frequency,
+    basis: DayCountBasis,
+    method: AccrintMethod,
+) -> FNumber {
+    FNumber(func(
+        "ACCRINT",
+        &[
+            &issue,
+            &first_interest,
+            &settlement,
+            &rate,
+            &par,
+            &frequency.as_param(),
+            &basis.as_param(),
+            &method.as_param(),
+        ],
+    ))
+}
+
+/// Calculates the accrued interest for a security that pays interest at maturity.
+#[inline]
+pub fn accrintm(
+    issue: impl DateTimeParam,
+    settlement: impl DateTimeParam,
+    rate: impl Number,
+    par: impl Number,
+) -> FNumber {
+    FNumber(func4("ACCRINTM", &issue, &settlement, &rate, &par))
+}
+
+/// Calculates the accrued interest for a security that pays interest at maturity.
+#[inline]
+pub fn accrintm_basis(
+    issue: impl DateTimeParam,
+    settlement: impl DateTimeParam,
+    rate: impl Number,
+    par: impl Number,
+    basis: DayCountBasis,
+) -> FNumber {
+    FNumber(func5(
+        "ACCRINTM",
+        &issue,
+        &settlement,
+        &rate,
+        &par,
+        &basis.as_param(),
+    ))
+}
+
+/// Calculates the depreciation for each accounting period, French accounting system.
+#[inline]
+pub fn amordegrc(
+    cost: impl Number,
+    purchase_date: impl DateTimeParam,
+    first_period_end: impl DateTimeParam,
+    salvage: impl Number,
+    period: impl Number,
+    rate: impl Number,
+) -> FNumber {
+    FNumber(func(
+        "AMORDEGRC",
+        &[
+            &cost,
+            &purchase_date,
+            &first_period_end,
+            &salvage,
+            &period,
+            &rate,
+        ],
+    ))
+}
+
+/// Calculates the depreciation for each accounting period, French accounting system.
+#[inline]
+#[allow(clippy::too_many_arguments)]
+pub fn amordegrc_basis(
+    cost: impl Number,
+    purchase_date: impl DateTimeParam,
+    first_period_end: impl DateTimeParam,
+    salvage: impl Number,
+    period: impl Number,
+    rate: impl Number,
+    basis: DayCountBasis,
+) -> FNumber {
+    FNumber(func(
+        "AMORDEGRC",
+        &[
+            &cost,
+            &purchase_date,
+            &first_period_end,
+            &salvage,
+            &period,
+            &rate,
+            &basis.as_param(),
+        ],
+    ))
+}
+
+/// Calculates the depreciation for each accounting period, French accounting system,
+/// linear depreciation.
+#[inline]
+pub fn amorlinc(
+    cost: impl Number,
+    purchase_date: impl DateTimeParam,
+    first_period_end: impl DateTimeParam,
+    salvage: impl Number,
+    period: impl Number,
+    rate: impl Number,
+) -> FNumber {
+    FNumber(func(
+        "AMORLINC",
+        &[
+            &cost,
+            &purchase_date,
+            &first_period_end,
+            &salvage,
+            &period,
+            &rate,
+        ],
+    ))
+}
+
+/// Calculates the depreciation for each accounting period, French accounting system,
+/// linear depreciation.
+#[inline]
+#[allow(clippy::too_many_arguments)]
+pub fn amorlinc_basis(
+    cost: impl Number,
+    purchase_date: impl DateTimeParam,
+    first_period_end: impl DateTimeParam,
+    salvage: impl Number,
+    period: impl Number,
+    rate: impl Number,
+    basis: DayCountBasis,
+) -> FNumber {
+    FNumber(func(
+        "AMORLINC",
+        &[
+            &cost,
+            &purchase_date,
+            &first_period_end,
+            &salvage,
+            &period,
+            &rate,
+            &basis.as_param(),
+        ],
+    ))
+}
+
+/// Returns the number of days from the beginning of the coupon period to the settlement
+/// date.
+#[inline]
+pub fn coupdaybs(
+    settlement: impl DateTimeParam,
+    maturity: impl DateTimeParam,
+    frequency: Frequency,
+) -> FNumber {
+    FNumber(func3(
+        "COUPDAYBS",
+        &settlement,
+        &maturity,
+        &frequency.as_param(),
+    ))
+}
+
+/// Returns the number of days from the beginning of the coupon period to the settlement
+/// date.
+#[inline]
+pub fn coupdaybs_basis(
+    settlement: impl DateTimeParam,
+    maturity: impl DateTimeParam,
+    frequency: Frequency,
+    basis: DayCountBasis,
+) -> FNumber {
+    FNumber(func4(
+        "COUPDAYBS",
+        &settlement,
+        &maturity,
+        &frequency.as_param(),
+        &basis.as_param(),
+    ))
+}
+
+/// Returns the number of days in the coupon period that contains the settlement date.
+#[inline]
+pub fn coupdays(
+    settlement: impl DateTimeParam,
+    maturity: impl DateTimeParam,
+    frequency: Frequency,
+) -> FNumber {
+    FNumber(func3(
+        "COUPDAYS",
+        &settlement,
+        &maturity,
+        &frequency.as_param(),
+    ))
+}
+
+/// Returns the number of days in the coupon period that contains the settlement date.
+#[inline]
+pub fn coupdays_basis(
+    settlement: impl DateTimeParam,
+    maturity: impl DateTimeParam,
+    frequency: Frequency,
+    basis: DayCountBasis,
+) -> FNumber {
+    FNumber(func4(
+        "COUPDAYS",
+        &settlement,
+        &maturity,
+        &frequency.as_param(),
+        &basis.as_param(),
+    ))
+}
+
+/// Returns the number of days from the settlement date to the next coupon date.
+#[inline]
+pub fn coupdaysnc(
+    settlement: impl DateTimeParam,
+    maturity: impl DateTimeParam,
+    frequency: Frequency,
+) -> FNumber {
+    FNumber(func3(
+        "COUPDAYSNC",
+        &settlement,
+        &maturity,
+        &frequency.as_param(),
+    ))
+}
+
+/// Returns the number of days from the settlement date to the next coupon date.
+#[inline]
+pub fn coupdaysnc_basis(
+    settlement: impl DateTimeParam,
+    maturity: impl DateTimeParam,
+    frequency: Frequency,
+    basis: DayCountBasis,
+) -> FNumber {
+    FNumber(func4(
+        "COUPDAYSNC",
+        &settlement,
+        &maturity,
+        &frequency.as_param(),
+        &basis.as_param(),
+    ))
+}
+
+/// Returns the next coupon date after the settlement date.
+#[inline]
+pub fn coupncd(
+    settlement: impl DateTimeParam,
+    maturity: impl DateTimeParam,
+    frequency: Frequency,
+) -> FNumber {
+    FNumber(func3(
+        "COUPNCD",
+        &settlement,
+        &maturity,
+        &frequency.as_param(),
+    ))
+}
+
+/// Returns the next coupon date after the settlement date.
+#[inline]
+pub fn coupncd_basis(
+    settlement: impl DateTimeParam,
+    maturity: impl DateTimeParam,
+    frequency: Frequency,
+    basis: DayCountBasis,
+) -> FNumber {
+    FNumber(func4(
+        "COUPNCD",
+        &settlement,
+        &maturity,
+        &frequency.as_param(),
+        &basis.as_param(),
+    ))
+}
+
+/// Returns the number of coupons to be paid between the settlement and maturity dates.
+#[inline]
+pub fn coupnum(
+    settlement: impl DateTimeParam,
+    maturity: impl DateTimeParam,
+    frequency: Frequency,
+) -> FNumber {
+    FNumber(func3(
+        "COUPNUM",
+        &settlement,
+        &maturity,
+        &frequency.as_param(),
+    ))
+}
+
+/// Returns the number of coupons to be paid between the settlement and maturity dates.
+#[inline]
+pub fn coupnum_basis(
+    settlement: impl DateTimeParam,
+    maturity: impl DateTimeParam,
+    frequency: Frequency,
+    basis: DayCountBasis,
+) -> FNumber {
+    FNumber(func4(
+        "COUPNUM",
+        &settlement,
+        &maturity,
+        &frequency.as_param(),
+        &basis.as_param(),
+    ))
+}
+
+/// Returns the previous coupon date before the settlement date.
+#[inline]
+pub fn couppcd(
+    settlement: impl DateTimeParam,
+    maturity: impl DateTimeParam,
+    frequency: Frequency,
+) -> FNumber {
+    FNumber(func3(
+        "COUPPCD",
+        &settlement,
+        &maturity,
+        &frequency.as_param(),
+    ))
+}
+
+/// Returns the previous coupon date before the settlement date.
+#[inline]
+pub fn couppcd_basis(
+    settlement: impl DateTimeParam,
+    maturity: impl DateTimeParam,
+    frequency: Frequency,
+    basis: DayCountBasis,
+) -> FNumber {
+    FNumber(func4(
+        "COUPPCD",
+        &settlement,
+        &maturity,
+        &frequency.as_param(),
+        &basis.as_param(),
+    ))
+}
+
+/// Calculates the cumulative interest paid between two periods.
+#[inline]
+pub fn cumipmt(
+    rate: impl Number,
+    nper: impl Number,
+    pv: impl Number,
+    start_period: impl Number,
+    end_period: impl Number,
+    pay_type: PaymentType,
+) -> FNumber {
+    FNumber(func(
+        "CUMIPMT",
+        &[
+            &rate,
+            &nper,
+            &pv,
+            &start_period,
+            &end_period,
+            &pay_type.as_param(),
+        ],
+    ))
+}
+
+/// Calculates the cumulative principal paid between two periods.
+#[inline]
+pub fn cumprinc(
+    rate: impl Number,
+    nper: impl Number,
+    pv: impl Number,
+    start_period: impl Number,
+    end_period: impl Number,
+    pay_type: PaymentType,
+) -> FNumber {
+    FNumber(func(
+        "CUMPRINC",
+        &[
+            &rate,
+            &nper,
+            &pv,
+            &start_period,
+            &end_period,
+            &pay_type.as_param(),
+        ],
+    ))
+}
+
+/// Returns the depreciation of an asset for a specified period using the fixed-declining
+/// balance method.
+#[inline]
+pub fn db(
+    cost: impl Number,
+    salvage: impl Number,
+    life_time: impl Number,
+    period: impl Number,
+) -> FNumber {
+    FNumber(func4("DB", &cost, &salvage, &life_time, &period))
+}
+
+/// Returns the depreciation of an asset for a specified period using the fixed-declining
+/// balance method.
+#[inline]
+pub fn db_month(
+    cost: impl Number,
+    salvage: impl Number,
+    life_time: impl Number,
+    period: impl Number,
+    month: impl Number,
+) -> FNumber {
+    FNumber(func5("DB", &cost, &salvage, &life_time, &period, &month))
+}
+
+/// Returns the depreciation of an asset for a specified period using the double-declining
+/// balance method.
+#[inline]
+pub fn ddb(
+    cost: impl Number,
+    salvage: impl Number,
+    life_time: impl Number,
+    period: impl Number,
+) -> FNumber {
+    FNumber(func4("DDB", &cost, &salvage, &life_time, &period))
+}
+
+/// Returns the depreciation of an asset for a specified period using the declining
+/// balance method with the given factor.
+#[inline]
+pub fn ddb_factor(
+    cost: impl Number,
+    salvage: impl Number,
+    life_time: impl Number,
+    period: impl Number,
+    factor: impl Number,
+) -> FNumber {
+    FNumber(func5("DDB", &cost, &salvage, &life_time, &period, &factor))
+}
+
+/// Returns the discount rate of a security.
+#[inline]
+pub fn disc(
+    settlement: impl DateTimeParam,
+    maturity: impl DateTimeParam,
+    price: impl Number,
+    redemption: impl Number,
+) -> FNumber {
+    FNumber(func4("DISC", &settlement, &maturity, &price, &redemption))
+}
+
+/// Returns the discount rate of a security.
+#[inline]
+pub fn disc_basis(
+    settlement: impl DateTimeParam,
+    maturity: impl DateTimeParam,
+    price: impl Number,
+    redemption: impl Number,
+    basis: DayCountBasis,
+) -> FNumber {
+    FNumber(func5(
+        "DISC",
+        &settlement,
+        &maturity,
+        &price,
+        &redemption,
+        &basis.as_param(),
+    ))
+}
+
+/// Returns the Macaulay duration of a security with periodic interest payments.
+#[inline]
+pub fn duration(
+    settlement: impl DateTimeParam,
+    maturity: impl DateTimeParam,
+    coupon: impl Number,
+    yield_: impl Number,
+    frequency: Frequency,
+) -> FNumber {
+    FNumber(func5(
+        "DURATION",
+        &settlement,
+        &maturity,
+        &coupon,
+        &yield_,
+        &frequency.as_param(),
+    ))
+}
+
+/// Returns the Macaulay duration of a security with periodic interest payments.
+#[inline]
+pub fn duration_basis(
+    settlement: impl DateTimeParam,
+    maturity: impl DateTimeParam,
+    coupon: impl Number,
+    yield_: impl Number,
+    frequency: Frequency,
+    basis: DayCountBasis,
+) -> FNumber {
+    FNumber(func(
+        "DURATION",
+        &[
+            &settlement,
+            &maturity,
+            &coupon,
+            &yield_,
+            &frequency.as_param(),
+            &basis.as_param(),
+        ],
+    ))
+}
+
+/// Returns the net annual interest rate for a nominal interest rate.
+#[inline]
+pub fn effect(nominal: impl Number, npery: impl Number) -> FNumber {
+    FNumber(func2("EFFECT", &nominal, &npery))
+}
+
+/// Returns the future value of an investment.
+#[inline]
+pub fn fv(rate: impl Number, nper: impl Number, pmt: impl Number) -> FNumber {
+    FNumber(func3("FV", &rate, &nper, &pmt))
+}
+
+/// Returns the future value of an investment.
+#[inline]
+pub fn fv_pv(rate: impl Number, nper: impl Number, pmt: impl Number, pv: impl Number) -> FNumber {
+    FNumber(func4("FV", &rate, &nper, &pmt, &pv))
+}
+
+/// Returns the future value of an investment.
+#[inline]
+pub fn fv_type(
+    rate: impl Number,
+    nper: impl Number,
+    pmt: impl Number,
+    pay_type: PaymentType,
+) -> FNumber {
+    FNumber(func5("FV", &rate, &nper, &pmt, &(), &pay_type.as_param()))
+}
+
+/// Returns the future value of an investment.
+#[inline]
+pub fn fv_pv_type(
+    rate: impl Number,
+    nper: impl Number,
+    pmt: impl Number,
+    pv: impl Number,
+    pay_type: PaymentType,
+) -> FNumber {
+    FNumber(func5("FV", &rate, &nper, &pmt, &pv, &pay_type.as_param()))
+}
+
+/// Calculates the interest rate for a fully invested security.
+#[inline]
+pub fn intrate(
+    settlement: impl DateTimeParam,
+    maturity: impl DateTimeParam,
+    investment: impl Number,
+    redemption: impl Number,
+) -> FNumber {
+    FNumber(func4(
+        "INTRATE",
+        &settlement,
+        &maturity,
+        &investment,
+        &redemption,
+    ))
+}
+
+/// Calculates the interest rate for a fully invested security.
+#[inline]
+pub fn intrate_basis(
+    settlement: impl DateTimeParam,
+    maturity: impl DateTimeParam,
+    investment: impl Number,
+    redemption: impl Number,
+    basis: DayCountBasis,
+) -> FNumber {
+    FNumber(func5(
+        "INTRATE",
+        &settlement,
+        &maturity,
+        &investment,
+        &redemption,
+        &basis.as_param(),
+    ))
+}
+
+/// Returns the amount of interest for a given period.
+#[inline]
+pub fn ipmt(rate: impl Number, per: impl Number, nper: impl Number, pv: impl Number) -> FNumber {
+    FNumber(func4("IPMT", &rate, &per, &nper, &pv))
+}
+
+/// Returns the amount of interest for a given period.
+#[inline]
+pub fn ipmt_fv(
+    rate: impl Number,
+    per: impl Number,
+    nper: impl Number,
+    pv: impl Number,
+    fv: impl Number,
+) -> FNumber {
+    FNumber(func5("IPMT", &rate, &per, &nper, &pv, &fv))
+}
+
+/// Returns the amount of interest for a given period.
+#[inline]
+pub fn ipmt_type(
+    rate: impl Number,
+    per: impl Number,
+    nper: impl Number,
+    pv: impl Number,
+    pay_type: PaymentType,
+) -> FNumber {
+    FNumber(func(
+        "IPMT",
+        &[&rate, &per, &nper, &pv, &(), &pay_type.as_param()],
+    ))
+}
+
+/// Returns the amount of interest for a given period.
+#[inline]
+pub fn ipmt_fv_type(
+    rate: impl Number,
+    per: impl Number,
+    nper: impl Number,
+    pv: impl Number,
+    fv: impl Number,
+    pay_type: PaymentType,
+) -> FNumber {
+    FNumber(func(
+        "IPMT",
+        &[&rate, &per, &nper, &pv, &fv, &pay_type.as_param()],
+    ))
+}
+
+/// Calculates the internal rate of return for a series of cash flows.
+#[inline]
+pub fn irr(values: impl Sequence) -> FNumber {
+    FNumber(func1("IRR", &values))
+}
+
+/// Calculates the internal rate of return for a series of cash flows.
+#[inline]
+pub fn irr_guess(values: impl Sequence, guess: impl Number) -> FNumber {
+    FNumber(func2("IRR", &values, &guess))
+}
+
+/// Returns the interest paid for a given period with even principal payments.
+#[inline]
+pub fn ispmt(rate: impl Number, per: impl Number, nper: impl Number, pv: impl Number) -> FNumber {
+    FNumber(func4("ISPMT", &rate, &per, &nper, &pv))
+}
+
+/// Returns the modified Macaulay duration of a security with periodic interest payments.
+#[inline]
+pub fn mduration(
+    settlement: impl DateTimeParam,
+    maturity: impl DateTimeParam,
+    coupon: impl Number,
+    yield_: impl Number,
+    frequency: Frequency,
+) -> FNumber {
+    FNumber(func5(
+        "MDURATION",
+        &settlement,
+        &maturity,
+        &coupon,
+        &yield_,
+        &frequency.as_param(),
+    ))
+}
+
+/// Returns the modified Macaulay duration of a security with periodic interest payments.
+#[inline]
+pub fn mduration_basis(
+    settlement: impl DateTimeParam,
+    maturity: impl DateTimeParam,
+    coupon: impl Number,
+    yield_: impl Number,
+    frequency: Frequency,
+    basis: DayCountBasis,
+) -> FNumber {
+    FNumber(func(
+        "MDURATION",
+        &[
+            &settlement,
+            &maturity,
+            &coupon,
+            &yield_,
+            &frequency.as_param(),
+            &basis.as_param(),
+        ],
+    ))
+}
+
+/// Returns the modified internal rate of return for a series of investments.
+#[inline]
+pub fn mirr(
+    values: impl Sequence,
+    finance_rate: impl Number,
+    reinvest_rate: impl Number,
+) -> FNumber {
+    FNumber(func3("MIRR", &values, &finance_rate, &reinvest_rate))
+}
+
+/// Returns the nominal annual interest rate for an effective interest rate.
+#[inline]
+pub fn nominal(effective_rate: impl Number, npery: impl Number) -> FNumber {
+    FNumber(func2("NOMINAL", &effective_rate, &npery))
+}
+
+/// Returns the number of periods for an investment.
+#[inline]
+pub fn nper(rate: impl Number, pmt: impl Number, pv: impl Number) -> FNumber {
+    FNumber(func3("NPER", &rate, &pmt, &pv))
+}
+
+/// Returns the number of periods for an investment.
+#[inline]
+pub fn nper_fv(rate: impl Number, pmt: impl Number, pv: impl Number, fv: impl Number) -> FNumber {
+    FNumber(func4("NPER", &rate, &pmt, &pv, &fv))
+}
+
+/// Returns the number of periods for an investment.
+#[inline]
+pub fn nper_type(
+    rate: impl Number,
+    pmt: impl Number,
+    pv: impl Number,
+    pay_type: PaymentType,
+) -> FNumber {
+    FNumber(func5("NPER", &rate, &pmt, &pv, &(), &pay_type.as_param()))
+}
+
+/// Returns the number of periods for an investment.
+#[inline]
+pub fn nper_fv_type(
+    rate: impl Number,
+    pmt: impl Number,
+    pv: impl Number,
+    fv: impl Number,
+    pay_type: PaymentType,
+) -> FNumber {
+    FNumber(func5("NPER", &rate, &pmt, &pv, &fv, &pay_type.as_param()))
+}
+
+/// Returns the net present value for a series of periodic cash flows.
+#[inline]
+pub fn npv(rate: impl Number, values: impl Sequence) -> FNumber {
+    FNumber(func2("NPV", &rate, &values))
+}
+
+/// Returns the price per 100 currency units face value of a security with an odd first
+/// period.
+#[inline]
+#[allow(clippy::too_many_arguments)]
+pub fn oddfprice(
+    settlement: impl DateTimeParam,
+    maturity: impl DateTimeParam,
+    issue: impl DateTimeParam,
+    first_coupon: impl DateTimeParam,
+    rate: impl Number,
+    annual_yield: impl Number,
+    redemption: impl Number,
+    frequency: Frequency,
+) -> FNumber {
+    FNumber(func(
+        "ODDFPRICE",
+        &[
+            &settlement,
+            &maturity,
+            &issue,
+            &first_coupon,
+            &rate,
+            &annual_yield,
+            &redemption,
+            &frequency.as_param(),
+        ],
+    ))
+}
+
+/// Returns the price per 100 currency units face value of a security with an odd first
+/// period.
+#[inline]
+#[allow(clippy::too_many_arguments)]
+pub fn oddfprice_basis(
+    settlement: impl DateTimeParam,
+    maturity: impl DateTimeParam,
+    issue: impl DateTimeParam,
+    first_coupon: impl DateTimeParam,
+    rate: impl Number,
+    annual_yield: impl Number,
+    redemption: impl Number,
+    frequency: Frequency,
+    basis: DayCountBasis,
+) -> FNumber {
+    FNumber(func(
+        "ODDFPRICE",
+        &[
+            &settlement,
+            &maturity,
+            &issue,
+            &first_coupon,
+            &rate,
+            &annual_yield,
+            &redemption,
+            &frequency.as_param(),
+            &basis.as_param(),
+        ],
+    ))
+}
+
+/// Returns the yield of a security with an odd first period.
+#[inline]
+#[allow(clippy::too_many_arguments)]
+pub fn oddfyield(
+    settlement: impl DateTimeParam,
+    maturity: impl DateTimeParam,
+    issue: impl DateTimeParam,
+    first_coupon: impl DateTimeParam,
+    rate: impl Number,
+    price: impl Number,
+    redemption: impl Number,
+    frequency: Frequency,
+) -> FNumber {
+    FNumber(func(
+        "ODDFYIELD",
+        &[
+            &settlement,
+            &maturity,
+            &issue,
+            &first_coupon,
+            &rate,
+            &price,
+            &redemption,
+            &frequency.as_param(),
+        ],
+    ))
+}
+
+/// Returns the yield of a security with an odd first period.
+#[inline]
+#[allow(clippy::too_many_arguments)]
+pub fn oddfyield_basis(
+    settlement: impl DateTimeParam,
+    maturity: impl DateTimeParam,
+    issue: impl DateTimeParam,
+    first_coupon: impl DateTimeParam,
+    rate: impl Number,
+    price: impl Number,
+    redemption: impl Number,
+    frequency: Frequency,
+    basis: DayCountBasis,
+) -> FNumber {
+    FNumber(func(
+        "ODDFYIELD",
+        &[
+            &settlement,
+            &maturity,
+            &issue,
+            &first_coupon,
+            &rate,
+            &price,
+            &redemption,
+            &frequency.as_param(),
+            &basis.as_param(),
+        ],
+    ))
+}
+
+/// Returns the price per 100 currency units face value of a security with an odd last
+/// period.
+#[inline]
+pub fn oddlprice(
+    settlement: impl DateTimeParam,
+    maturity: impl DateTimeParam,
+    last_interest: impl DateTimeParam,
+    rate: impl Number,
+    annual_yield: impl Number,
+    redemption: impl Number,
+    frequency: Frequency,
+) -> FNumber {
+    FNumber(func(
+        "ODDLPRICE",
+        &[
+            &settlement,
+            &maturity,
+            &last_interest,
+            &rate,
+            &annual_yield,
+            &redemption,
+            &frequency.as_param(),
+        ],
+    ))
+}
+
+/// Returns the price per 100 currency units face value of a security with an odd last
+/// period.
+#[inline]
+#[allow(clippy::too_many_arguments)]
+pub fn oddlprice_basis(
+    settlement: impl DateTimeParam,
+    maturity: impl DateTimeParam,
+    last_interest: impl DateTimeParam,
+    rate: impl Number,
+    annual_yield: impl Number,
+    redemption: impl Number,
+    frequency: Frequency,
+    basis: DayCountBasis,
+) -> FNumber {
+    FNumber(func(
+        "ODDLPRICE",
+        &[
+            &settlement,
+            &maturity,
+            &last_interest,
+            &rate,
+            &annual_yield,
+            &redemption,
+            &frequency.as_param(),
+            &basis.as_param(),
+        ],
+    ))
+}
+
+/// Returns the yield of a security with an odd last period.
+#[inline]
+pub fn oddlyield(
+    settlement: impl DateTimeParam,
+    maturity: impl DateTimeParam,
+    last_interest: impl DateTimeParam,
+    rate: impl Number,
+    price: impl Number,
+    redemption: impl Number,
+    frequency: Frequency,
+) -> FNumber {
+    FNumber(func(
+        "ODDLYIELD",
+        &[
+            &settlement,
+            &maturity,
+            &last_interest,
+            &rate,
+            &price,
+            &redemption,
+            &frequency.as_param(),
+        ],
+    ))
+}
+
+/// Returns the yield of a security with an odd last period.
+#[inline]
+#[allow(clippy::too_many_arguments)]
+pub fn oddlyield_basis(
+    settlement: impl DateTimeParam,
+    maturity: impl DateTimeParam,
+    last_interest: impl DateTimeParam,
+    rate: impl Number,
+    price: impl Number,
+    redemption: impl Number,
+    frequency: Frequency,
+    basis: DayCountBasis,
+) -> FNumber {
+    FNumber(func(
+        "ODDLYIELD",
+        &[
+            &settlement,
+            &maturity,
+            &last_interest,
+            &rate,
+            &price,
+            &redemption,
+            &frequency.as_param(),
+            &basis.as_param(),
+        ],
+    ))
+}
+
+/// Returns the number of periods required by an investment to attain a certain value.
+#[inline]
+pub fn pduration(rate: impl Number, pv: impl Number, fv: impl Number) -> FNumber {
+    FNumber(func3("PDURATION", &rate, &pv, &fv))
+}
+
+/// Returns the constant payment per period for an annuity.
+#[inline]
+pub fn pmt(rate: impl Number, nper: impl Number, pv: impl Number) -> FNumber {
+    FNumber(func3("PMT", &rate, &nper, &pv))
+}
+
+/// Returns the constant payment per period for an annuity.
+#[inline]
+pub fn pmt_fv(rate: impl Number, nper: impl Number, pv: impl Number, fv: impl Number) -> FNumber {
+    FNumber(func4("PMT", &rate, &nper, &pv, &fv))
+}
+
+/// Returns the constant payment per period for an annuity.
+#[inline]
+pub fn pmt_type(
+    rate: impl Number,
+    nper: impl Number,
+    pv: impl Number,
+    pay_type: PaymentType,
+) -> FNumber {
+    FNumber(func5("PMT", &rate, &nper, &pv, &(), &pay_type.as_param()))
+}
+
+/// Returns the constant payment per period for an annuity.
+#[inline]
+pub fn pmt_fv_type(
+    rate: impl Number,
+    nper: impl Number,
+    pv: impl Number,
+    fv: impl Number,
+    pay_type: PaymentType,
+) -> FNumber {
+    FNumber(func5("PMT", &rate, &nper, &pv, &fv, &pay_type.as_param()))
+}
+
+/// Calculates the principal portion of a payment for a given period.
+#[inline]
+pub fn ppmt(rate: impl Number, per: impl Number, nper: impl Number, pv: impl Number) -> FNumber {
+    FNumber(func4("PPMT", &rate, &per, &nper, &pv))
+}
+
+/// Calculates the principal portion of a payment for a given period.
+#[inline]
+pub fn ppmt_fv(
+    rate: impl Number,
+    per: impl Number,
+    nper: impl Number,
+    pv: impl Number,
+    fv: impl Number,
+) -> FNumber {
+    FNumber(func5("PPMT", &rate, &per, &nper, &pv, &fv))
+}
+
+/// Calculates the principal portion of a payment for a given period.
+#[inline]
+pub fn ppmt_type(
+    rate: impl Number,
+    per: impl Number,
+    nper: impl Number,
+    pv: impl Number,
+    pay_type: PaymentType,
+) -> FNumber {
+    FNumber(func(
+        "PPMT",
+        &[&rate, &per, &nper, &pv, &(), &pay_type.as_param()],
+    ))
+}
+
+/// Calculates the principal portion of a payment for a given period.
+#[inline]
+pub fn ppmt_fv_type(
+    rate: impl Number,
+    per: impl Number,
+    nper: impl Number,
+    pv: impl Number,
+    fv: impl Number,
+    pay_type: PaymentType,
+) -> FNumber {
+    FNumber(func(
+        "PPMT",
+        &[&rate, &per, &nper, &pv, &fv, &pay_type.as_param()],
+    ))
+}
+
+/// Calculates the price per 100 currency units face value of a security that pays
+/// periodic interest.
+#[inline]
+pub fn price(
+    settlement: impl DateTimeParam,
+    maturity: impl DateTimeParam,
+    rate: impl Number,
+    annual_yield: impl Number,
+    redemption: impl Number,
+    frequency: Frequency,
+) -> FNumber {
+    FNumber(func(
+        "PRICE",
+        &[
+            &settlement,
+            &maturity,
+            &rate,
+            &annual_yield,
+            &redemption,
+            &frequency.as_param(),
+        ],
+    ))
+}
+
+/// Calculates the price per 100 currency units face value of a security that pays
+/// periodic interest.
+#[inline]
+pub fn price_basis(
+    settlement: impl DateTimeParam,
+    maturity: impl DateTimeParam,
+    rate: impl Number,
+    annual_yield: impl Number,
+    redemption: impl Number,
+    frequency: Frequency,
+    basis: DayCountBasis,
+) -> FNumber {
+    FNumber(func(
+        "PRICE",
+        &[
+            &settlement,
+            &maturity,
+            &rate,
+            &annual_yield,
+            &redemption,
+            &frequency.as_param(),
+            &basis.as_param(),
+        ],
+    ))
+}
+
+/// Calculates the price per 100 currency units face value of a discounted security.
+#[inline]
+pub fn pricedisc(
+    settlement: impl DateTimeParam,
+    maturity: impl DateTimeParam,
+    discount: impl Number,
+    redemption: impl Number,
+) -> FNumber {
+    FNumber(func4(
+        "PRICEDISC",
+        &settlement,
+        &maturity,
+        &discount,
+        &redemption,
+    ))
+}
+
+/// Calculates the price per 100 currency units face value of a discounted security.
+#[inline]
+pub fn pricedisc_basis(
+    settlement: impl DateTimeParam,
+    maturity: impl DateTimeParam,
+    discount: impl Number,
+    redemption: impl Number,
+    basis: DayCountBasis,
+) -> FNumber {
+    FNumber(func5(
+        "PRICEDISC",
+        &settlement,
+        &maturity,
+        &discount,
+        &redemption,
+        &basis.as_param(),
+    ))
+}
+
+/// Calculates the price per 100 currency units face value of a security that pays
+/// interest at maturity.
+#[inline]
+pub fn pricemat(
+    settlement: impl DateTimeParam,
+    maturity: impl DateTimeParam,
+    issue: impl DateTimeParam,
+    rate: impl Number,
+    annual_yield: impl Number,
+) -> FNumber {
+    FNumber(func5(
+        "PRICEMAT",
+        &settlement,
+        &maturity,
+        &issue,
+        &rate,
+        &annual_yield,
+    ))
+}
+
+/// Calculates the price per 100 currency units face value of a security that pays
+/// interest at maturity.
+#[inline]
+pub fn pricemat_basis(
+    settlement: impl DateTimeParam,
+    maturity: impl DateTimeParam,
+    issue: impl DateTimeParam,
+    rate: impl Number,
+    annual_yield: impl Number,
+    basis: DayCountBasis,
+) -> FNumber {
+    FNumber(func(
+        "PRICEMAT",
+        &[
+            &settlement,
+            &maturity,
+            &issue,
+            &rate,
+            &annual_yield,
+            &basis.as_param(),
+        ],
+    ))
+}
+
+/// Returns the present value of an investment.
+#[inline]
+pub fn pv(rate: impl Number, nper: impl Number, pmt: impl Number) -> FNumber {
+    FNumber(func3("PV", &rate, &nper, &pmt))
+}
+
+/// Returns the present value of an investment.
+#[inline]
+pub fn pv_fv(rate: impl Number, nper: impl Number, pmt: impl Number, fv: impl Number) -> FNumber {
+    FNumber(func4("PV", &rate, &nper, &pmt, &fv))
+}
+
+/// Returns the present value of an investment.
+#[inline]
+pub fn pv_type(
+    rate: impl Number,
+    nper: impl Number,
+    pmt: impl Number,
+    pay_type: PaymentType,
+) -> FNumber {
+    FNumber(func5("PV", &rate, &nper, &pmt, &(), &pay_type.as_param()))
+}
+
+/// Returns the present value of an investment.
+#[inline]
+pub fn pv_fv_type(
+    rate: impl Number,
+    nper: impl Number,
+    pmt: impl Number,
+    fv: impl Number,
+    pay_type: PaymentType,
+) -> FNumber {
+    FNumber(func5("PV", &rate, &nper, &pmt, &fv, &pay_type.as_param()))
+}
+
+/// Returns the constant interest rate per period of an annuity.
+#[inline]
+pub fn rate(nper: impl Number, pmt: impl Number, pv: impl Number) -> FNumber {
+    FNumber(func3("RATE", &nper, &pmt, &pv))
+}
+
+/// Returns the constant interest rate per period of an annuity.
+#[inline]
+pub fn rate_fv(nper: impl Number, pmt: impl Number, pv: impl Number, fv: impl Number) -> FNumber {
+    FNumber(func4("RATE", &nper, &pmt, &pv, &fv))
+}
+
+/// Returns the constant interest rate per period of an annuity.
+#[inline]
+pub fn rate_fv_type(
+    nper: impl Number,
+    pmt: impl Number,
+    pv: impl Number,
+    fv: impl Number,
+    pay_type: PaymentType,
+) -> FNumber {
+    FNumber(func5("RATE", &nper, &pmt, &pv, &fv, &pay_type.as_param()))
+}
+
+/// Returns the constant interest rate per period of an annuity.
+#[inline]
+pub fn rate_fv_type_guess(
+    nper: impl Number,
+    pmt: impl Number,
+    pv: impl Number,
+    fv: impl Number,
+    pay_type: PaymentType,
+    guess: impl Number,
+) -> FNumber {
+    FNumber(func(
+        "RATE",
+        &[&nper, &pmt, &pv, &fv, &pay_type.as_param(), &guess],
+    ))
+}
+
+/// Calculates the amount received at maturity for a fully invested security.
+#[inline]
+pub fn received(
+    settlement: impl DateTimeParam,
+    maturity: impl DateTimeParam,
+    investment: impl Number,
+    discount: impl Number,
+) -> FNumber {
+    FNumber(func4(
+        "RECEIVED",
+        &settlement,
+        &maturity,
+        &investment,
+        &discount,
+    ))
+}
+
+/// Calculates the amount received at maturity for a fully invested security.
+#[inline]
+pub fn received_basis(
+    settlement: impl DateTimeParam,
+    maturity: impl DateTimeParam,
+    investment: impl Number,
+    discount: impl Number,
+    basis: DayCountBasis,
+) -> FNumber {
+    FNumber(func5(
+        "RECEIVED",
+        &settlement,
+        &maturity,
+        &investment,
+        &discount,
+        &basis.as_param(),
+    ))
+}
+
+/// Returns an equivalent interest rate when an investment increases in value.
+#[inline]
+pub fn rri(nper: impl Number, pv: impl Number, fv: impl Number) -> FNumber {
+    FNumber(func3("RRI", &nper, &pv, &fv))
+}
+
+/// Returns the straight-line depreciation of an asset for one period.
+#[inline]
+pub fn sln(cost: impl Number, salvage: impl Number, life_time: impl Number) -> FNumber {
+    FNumber(func3("SLN", &cost, &salvage, &life_time))
+}
+
+/// Returns the sum-of-years' digits depreciation of an asset for a specified period.
+#[inline]
+pub fn syd(
+    cost: impl Number,
+    salvage: impl Number,
+    life_time: impl Number,
+    period: impl Number,
+) -> FNumber {
+    FNumber(func4("SYD", &cost, &salvage, &life_time, &period))
+}
+
+/// Calculates the bond-equivalent yield for a treasury bill.
+#[inline]
+pub fn tbilleq(
+    settlement: impl DateTimeParam,
+    maturity: impl DateTimeParam,
+    discount: impl Number,
+) -> FNumber {
+    FNumber(func3("TBILLEQ", &settlement, &maturity, &discount))
+}
+
+/// Calculates the price of a treasury bill per 100 currency units.
+#[inline]
+pub fn tbillprice(
+    settlement: impl DateTimeParam,
+    maturity: impl DateTimeParam,
+    discount: impl Number,
+) -> FNumber {
+    FNumber(func3("TBILLPRICE", &settlement, &maturity, &discount))
+}
+
+/// Calculates the yield of a treasury bill.
+#[inline]
+pub fn tbillyield(
+    settlement: impl DateTimeParam,
+    maturity: impl DateTimeParam,
+    price: impl Number,
+) -> FNumber {
+    FNumber(func3("TBILLYIELD", &settlement, &maturity, &price))
+}
+
+/// Returns the depreciation of an asset for a partial period using the variable declining
+/// balance method.
+#[inline]
+pub fn vdb(
+    cost: impl Number,
+    salvage: impl Number,
+    life_time: impl Number,
+    start_period: impl Number,
+    end_period: impl Number,
+) -> FNumber {
+    FNumber(func5(
+        "VDB",
+        &cost,
+        &salvage,
+        &life_time,
+        &start_period,
+        &end_period,
+    ))
+}
+
+/// Returns the depreciation of an asset for a partial period using the variable declining
+/// balance method.
+#[inline]
+pub fn vdb_factor(
+    cost: impl Number,
+    salvage: impl Number,
+    life_time: impl Number,
+    start_period: impl Number,
+    end_period: impl Number,
+    factor: impl Number,
+) -> FNumber {
+    FNumber(func(
+        "VDB",
+        &[
+            &cost,
+            &salvage,
+            &life_time,
+            &start_period,
+            &end_period,
+            &factor,
+        ],
+    ))
+}
+
+/// Returns the depreciation of an asset for a partial period using the variable declining
+/// balance method. Doesn't switch to straight-line depreciation.
+#[inline]
+pub fn vdb_noswitch(
+    cost: impl Number,
+    salvage: impl Number,
+    life_time: impl Number,
+    start_period: impl Number,
+    end_period: impl Number,
+) -> FNumber {
+    FNumber(func(
+        "VDB",
+        &[
+            &cost,
+            &salvage,
+            &life_time,
+            &start_period,
+            &end_period,
+            &(),
+            &true,
+        ],
+    ))
+}
+
+/// Returns the depreciation of an asset for a partial period using the variable declining
+/// balance method. Doesn't switch to straight-line depreciation.
+#[inline]
+pub fn vdb_factor_noswitch(
+    cost: impl Number,
+    salvage: impl Number,
+    life_time: impl Number,
+    start_period: impl Number,
+    end_period: impl Number,
+    factor: impl Number,
+) -> FNumber {
+    FNumber(func(
+        "VDB",
+        &[
+            &cost,
+            &salvage,
+            &life_time,
+            &start_period,
+            &end_period,
+            &factor,
+            &true,
+        ],
+    ))
+}
+
+/// Returns the internal rate of return for a non-periodic stream of payments.
+#[inline]
+pub fn xirr(values: impl Array, dates: impl Array) -> FNumber {
+    FNumber(func2("XIRR", &values, &dates))
+}
+
+/// Returns the internal rate of return for a non-periodic stream of payments.
+#[inline]
+pub fn xirr_guess(values: impl Array, dates: impl Array, guess: impl Number) -> FNumber {
+    FNumber(func3("XIRR", &values, &dates, &guess))
+}
+
+/// Returns the net present value for a non-periodic stream of payments.
+#[inline]
+pub fn xnpv(rate: impl Number, values: impl Array, dates: impl Array) -> FNumber {
+    FNumber(func3("XNPV", &rate, &values, &dates))
+}
+
+/// Calculates the yield of a security that pays periodic interest.
+#[inline]
+pub fn yield_(
+    settlement: impl DateTimeParam,
+    maturity: impl DateTimeParam,
+    rate: impl Number,
+    price: impl Number,
+    redemption: impl Number,
+    frequency: Frequency,
+) -> FNumber {
+    FNumber(func(
+        "YIELD",
+        &[
+            &settlement,
+            &maturity,
+            &rate,
+            &price,
+            &redemption,
+            &frequency.as_param(),
+        ],
+    ))
+}
+
+/// Calculates the yield of a security that pays periodic interest.
+#[inline]
+pub fn yield_basis(
+    settlement: impl DateTimeParam,
+    maturity: impl DateTimeParam,
+    rate: impl Number,
+    price: impl Number,
+    redemption: impl Number,
+    frequency: Frequency,
+    basis: DayCountBasis,
+) -> FNumber {
+    FNumber(func(
+        "YIELD",
+        &[
+            &settlement,
+            &maturity,
+            &rate,
+            &price,
+            &redemption,
+            &frequency.as_param(),
+            &basis.as_param(),
+        ],
+    ))
+}
+
+/// Calculates the annual yield of a discounted security.
+#[inline]
+pub fn yielddisc(
+    settlement: impl DateTimeParam,
+    maturity: impl DateTimeParam,
+    price: impl Number,
+    redemption: impl Number,
+) -> FNumber {
+    FNumber(func4(
+        "YIELDDISC",
+        &settlement,
+        &maturity,
+        &price,
+        &redemption,
+    ))
+}
+
+/// Calculates the annual yield of a discounted security.
+#[inline]
+pub fn yielddisc_basis(
+    settlement: impl DateTimeParam,
+    maturity: impl DateTimeParam,
+    price: impl Number,
+    redemption: impl Number,
+    basis: DayCountBasis,
+) -> FNumber {
+    FNumber(func5(
+        "YIELDDISC",
+        &settlement,
+        &maturity,
+        &price,
+        &redemption,
+        &basis.as_param(),
+    ))
+}
+
+/// Calculates the annual yield of a security that pays interest at maturity.
+#[inline]
+pub fn yieldmat(
+    settlement: impl DateTimeParam,
+    maturity: impl DateTimeParam,
+    issue: impl DateTimeParam,
+    rate: impl Number,
+    price: impl Number,
+) -> FNumber {
+    FNumber(func5(
+        "YIELDMAT",
+        &settlement,
+        &maturity,
+        &issue,
+        &rate,
+        &price,
+    ))
+}
+
+/// Calculates the annual yield of a security that pays interest at maturity.
+#[inline]
+pub fn yieldmat_basis(
+    settlement: impl DateTimeParam,
+    maturity: impl DateTimeParam,
+    issue: impl DateTimeParam,
+    rate: impl Number,
+    price: impl Number,
+    basis: DayCountBasis,
+) -> FNumber {
+    FNumber(func(
+        "YIELDMAT",
+        &[
+            &settlement,
+            &maturity,
+            &issue,
+            &rate,
+            &price,
+            &basis.as_param(),
+        ],
+    ))
+}
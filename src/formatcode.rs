@@ -0,0 +1,333 @@
+use crate::{Any, CriterionCmp, Expr, FText, Text};
+use std::fmt::{Display, Formatter, Write};
+
+/// Colors available in a format code section.
+#[derive(Debug)]
+pub enum FormatColor {
+    Black,
+    Blue,
+    Cyan,
+    Green,
+    Magenta,
+    Red,
+    White,
+    Yellow,
+}
+
+impl Display for FormatColor {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FormatColor::Black => write!(f, "BLACK"),
+            FormatColor::Blue => write!(f, "BLUE"),
+            FormatColor::Cyan => write!(f, "CYAN"),
+            FormatColor::Green => write!(f, "GREEN"),
+            FormatColor::Magenta => write!(f, "MAGENTA"),
+            FormatColor::Red => write!(f, "RED"),
+            FormatColor::White => write!(f, "WHITE"),
+            FormatColor::Yellow => write!(f, "YELLOW"),
+        }
+    }
+}
+
+/// Builder for the format codes used by TEXT().
+///
+/// The codes are written with the en-US conventions, "." as decimal separator and
+/// "," as group separator.
+#[derive(Debug, Default, Clone)]
+pub struct FormatCode {
+    code: String,
+}
+
+impl Display for FormatCode {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.code)
+    }
+}
+
+impl Any for FormatCode {
+    fn formula(&self, buf: &mut String) {
+        self.code.as_str().formula(buf);
+    }
+
+    fn expr(&self) -> Expr {
+        Expr::Text(self.code.clone())
+    }
+}
+impl Text for FormatCode {}
+
+impl FormatCode {
+    /// Empty format code.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The format code as a text value.
+    pub fn build(&self) -> FText {
+        FText::from(self.expr())
+    }
+
+    /// The raw format code.
+    pub fn as_str(&self) -> &str {
+        self.code.as_str()
+    }
+
+    fn digits(&mut self, min_int: u32, decimals: u32) {
+        if min_int == 0 {
+            self.code.push('#');
+        }
+        for _ in 0..min_int {
+            self.code.push('0');
+        }
+        self.decimals(decimals);
+    }
+
+    fn decimals(&mut self, decimals: u32) {
+        if decimals > 0 {
+            self.code.push('.');
+            for _ in 0..decimals {
+                self.code.push('0');
+            }
+        }
+    }
+
+    /// A number with at least min_int integer digits and exactly the given number of
+    /// decimals.
+    pub fn number(mut self, min_int: u32, decimals: u32) -> Self {
+        self.digits(min_int, decimals);
+        self
+    }
+
+    /// A number with thousands separators.
+    pub fn grouped(mut self, min_int: u32, decimals: u32) -> Self {
+        let n = min_int.max(4);
+        for i in (0..n).rev() {
+            self.code.push(if i < min_int { '0' } else { '#' });
+            if i == 3 {
+                self.code.push(',');
+            }
+        }
+        self.decimals(decimals);
+        self
+    }
+
+    /// Additional decimal digits that are only shown if they are not zero.
+    /// Must follow number() or grouped().
+    pub fn optional_decimals(mut self, n: u32) -> Self {
+        let section = self.code.rsplit(';').next().unwrap_or_default();
+        if !section.contains('.') {
+            self.code.push('.');
+        }
+        for _ in 0..n {
+            self.code.push('#');
+        }
+        self
+    }
+
+    /// Scientific notation with the given number of exponent digits.
+    pub fn scientific(mut self, min_int: u32, decimals: u32, exp_digits: u32) -> Self {
+        self.digits(min_int, decimals);
+        self.code.push_str("E+");
+        for _ in 0..exp_digits.max(1) {
+            self.code.push('0');
+        }
+        self
+    }
+
+    /// A fraction with up to the given number of digits in the denominator.
+    pub fn fraction(mut self, digits: u32) -> Self {
+        self.code.push_str("# ");
+        for _ in 0..digits.max(1) {
+            self.code.push('?');
+        }
+        self.code.push('/');
+        for _ in 0..digits.max(1) {
+            self.code.push('?');
+        }
+        self
+    }
+
+    /// Multiply by 100 and append a percent sign.
+    pub fn percent(mut self) -> Self {
+        self.code.push('%');
+        self
+    }
+
+    /// The text value itself.
+    pub fn text_value(mut self) -> Self {
+        self.code.push('@');
+        self
+    }
+
+    /// Two digit year.
+    pub fn year2(mut self) -> Self {
+        self.code.push_str("YY");
+        self
+    }
+
+    /// Four digit year.
+    pub fn year4(mut self) -> Self {
+        self.code.push_str("YYYY");
+        self
+    }
+
+    /// Quarter, abbreviated.
+    pub fn quarter(mut self) -> Self {
+        self.code.push('Q');
+        self
+    }
+
+    /// Month as number.
+    pub fn month(mut self) -> Self {
+        self.code.push('M');
+        self
+    }
+
+    /// Month as two digit number.
+    pub fn month2(mut self) -> Self {
+        self.code.push_str("MM");
+        self
+    }
+
+    /// Abbreviated month name.
+    pub fn month_short(mut self) -> Self {
+        self.code.push_str("MMM");
+        self
+    }
+
+    /// Full month name.
+    pub fn month_long(mut self) -> Self {
+        self.code.push_str("MMMM");
+        self
+    }
+
+    /// Day of month as number.
+    pub fn day(mut self) -> Self {
+        self.code.push('D');
+        self
+    }
+
+    /// Day of month as two digit number.
+    pub fn day2(mut self) -> Self {
+        self.code.push_str("DD");
+        self
+    }
+
+    /// Abbreviated day of the week.
+    pub fn weekday_short(mut self) -> Self {
+        self.code.push_str("DDD");
+        self
+    }
+
+    /// Full day of the week.
+    pub fn weekday_long(mut self) -> Self {
+        self.code.push_str("DDDD");
+        self
+    }
+
+    /// Hours.
+    pub fn hours(mut self) -> Self {
+        self.code.push('H');
+        self
+    }
+
+    /// Hours as two digit number.
+    pub fn hours2(mut self) -> Self {
+        self.code.push_str("HH");
+        self
+    }
+
+    /// Elapsed hours, not wrapped at 24.
+    pub fn elapsed_hours(mut self) -> Self {
+        self.code.push_str("[HH]");
+        self
+    }
+
+    /// Minutes as two digit number.
+    ///
+    /// The code MM is the same as for month2(). It is read as minutes only if it
+    /// follows hours*() or precedes seconds*(), with separators in between.
+    /// Anywhere else it shows the month.
+    pub fn minutes2(mut self) -> Self {
+        self.code.push_str("MM");
+        self
+    }
+
+    /// Elapsed minutes, not wrapped at 60.
+    pub fn elapsed_minutes(mut self) -> Self {
+        self.code.push_str("[MM]");
+        self
+    }
+
+    /// Seconds as two digit number.
+    pub fn seconds2(mut self) -> Self {
+        self.code.push_str("SS");
+        self
+    }
+
+    /// Seconds as two digit number with fractional digits.
+    pub fn seconds2_fraction(mut self, decimals: u32) -> Self {
+        self.code.push_str("SS");
+        self.decimals(decimals);
+        self
+    }
+
+    /// 12 hour clock with AM/PM.
+    pub fn am_pm(mut self) -> Self {
+        self.code.push_str("AM/PM");
+        self
+    }
+
+    /// Separator character. Characters that have no meaning in a format code
+    /// are written as is, all others are escaped.
+    pub fn sep(mut self, c: char) -> Self {
+        match c {
+            '-' | '/' | ':' | ' ' | '(' | ')' | '+' | '$' => self.code.push(c),
+            _ => {
+                self.code.push('\\');
+                self.code.push(c);
+            }
+        }
+        self
+    }
+
+    /// Literal text.
+    pub fn literal<S: AsRef<str>>(mut self, text: S) -> Self {
+        let text = text.as_ref();
+        self.code.push('"');
+        for (i, s) in text.split('"').enumerate() {
+            if i > 0 {
+                // A quote can't be part of a quoted literal, so it goes in escaped.
+                self.code.push_str("\"\\\"\"");
+            }
+            self.code.push_str(s);
+        }
+        self.code.push('"');
+        self
+    }
+
+    /// Color for the current section.
+    pub fn color(mut self, color: FormatColor) -> Self {
+        let _ = write!(self.code, "[{}]", color);
+        self
+    }
+
+    /// Condition for the current section.
+    pub fn condition(mut self, cmp: CriterionCmp, value: f64) -> Self {
+        match cmp {
+            CriterionCmp::Cmp => {
+                let _ = write!(self.code, "[={}]", value);
+            }
+            _ => {
+                let _ = write!(self.code, "[{}{}]", cmp, value);
+            }
+        }
+        self
+    }
+
+    /// Starts the next section. Without conditions the sections are used for
+    /// positive numbers, negative numbers, zero and text in this order.
+    pub fn section(mut self) -> Self {
+        self.code.push(';');
+        self
+    }
+}
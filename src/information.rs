@@ -1,6 +1,6 @@
 use crate::{
-    create_param, func, func0, func1, func2, func3, param_assume_init, Any, Criterion, FCriterion,
-    FLogical, FNumber, FReference, FText, Number, Reference, Sequence, Text,
+    eq, func, func0, func1, func2, func3, Any, Criterion, ErrorValue, FCriterion, FLogical,
+    FNumber, FReference, FText, Number, Reference, Sequence, Text,
 };
 
 ///  Returns the number of areas in a given list of references.
@@ -120,20 +120,19 @@ pub fn countblank(seq: impl Sequence) -> FNumber {
 /// Count the number of cells in a range that meet a criteria.
 #[inline]
 pub fn countif(seq: impl Sequence, criterion: impl Criterion) -> FNumber {
-    FNumber(func2("COUNTBLANK", &seq, &criterion))
+    FNumber(func2("COUNTIF", &seq, &criterion))
 }
 
 /// Count the number of cells that meet multiple criteria in multiple ranges.
 #[inline]
 pub fn countifs(list: &[(FReference, FCriterion)]) -> FNumber {
-    let mut param = create_param(list.len() * 2);
-    for (i, (r, c)) in list.iter().enumerate() {
-        param[2 * i].write(r);
-        param[2 * i + 1].write(c);
+    let mut param: Vec<&dyn Any> = Vec::with_capacity(list.len() * 2);
+    for (r, c) in list {
+        param.push(r);
+        param.push(c);
     }
-    let param = unsafe { param_assume_init(param) };
 
-    FNumber(func("COUNTIFS", param.as_ref()))
+    FNumber(func("COUNTIFS", &param))
 }
 
 /// Returns Number representing the specific Error type
@@ -142,6 +141,12 @@ pub fn error_type(error: impl Any) -> FNumber {
     FNumber(func1("ERROR.TYPE", &error))
 }
 
+/// Tests for a specific error value via ERROR.TYPE. FALSE() if x is not an error.
+#[inline]
+pub fn is_error_type(x: impl Any, error: ErrorValue) -> FLogical {
+    FLogical(func2("IFERROR", &eq(error_type(x), error.code()), &false))
+}
+
 ///  Returns formula at given reference as text.
 #[inline]
 pub fn cell_formula(refs: impl Reference) -> FText {
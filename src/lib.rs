@@ -1,43 +1,67 @@
 use spreadsheet_ods::{CellRange, CellRef};
 use std::borrow::Cow;
 use std::fmt::{Display, Formatter, Write};
-use std::mem::MaybeUninit;
 use std::ops::{Add, BitAnd, BitXor, Div, Mul, Neg, Sub};
-use std::{alloc, mem, slice};
 
 pub mod bitop;
+pub mod catalog;
 pub mod complex;
 pub mod database;
 pub mod date;
+#[cfg(feature = "chrono")]
+pub mod datetime;
+pub mod engineering;
+pub mod eval;
+pub mod excel;
+pub mod expr;
 pub mod extaccess;
+pub mod financial;
+pub mod formatcode;
 pub mod information;
 pub mod logical;
 pub mod lookup;
 pub mod math;
 pub mod matrix;
+pub mod parse;
 pub mod rounding;
 pub mod statistic;
+pub mod text;
+pub mod ui;
+pub mod validate;
 
 pub use bitop::*;
+pub use catalog::*;
 pub use complex::*;
 pub use database::*;
 pub use date::*;
+#[cfg(feature = "chrono")]
+pub use datetime::*;
+pub use engineering::*;
+pub use eval::*;
+pub use excel::*;
+pub use expr::*;
 pub use extaccess::*;
+pub use financial::*;
+pub use formatcode::*;
 pub use information::*;
 pub use logical::*;
 pub use lookup::*;
 pub use math::*;
 pub use matrix::*;
+pub use parse::*;
 pub use rounding::*;
 pub use statistic::*;
+pub use text::*;
+pub use ui::*;
+pub use validate::*;
 
 /// The traits for this crate.
-/// And the function p() for parentheses.
+/// And the function p() for explicit parentheses.
 pub mod prelude {
     pub use super::parentheses as p;
     pub use super::{
-        Any, Criterion, DateTimeParam, Field, Logical, Matrix, Number, Reference, Scalar, Sequence,
-        Text, TextOrNumber,
+        Any, Complex, Criterion, DateTimeParam, Field, Logical, Matrix, Number, Reference, Scalar,
+        Sequence, Text, TextOrNumber,
     };
     pub use super::{AnyOp, LogicalOp, NumberOp, ReferenceOp, TextOp};
 }
@@ -45,14 +69,20 @@ pub mod prelude {
 /// Base trait for output to a String.
 pub trait Any {
     fn formula(&self, buf: &mut String);
+
+    /// The expression tree for this value.
+    /// Defaults to the rendered formula as Expr::Raw.
+    fn expr(&self) -> Expr {
+        let mut buf = String::new();
+        self.formula(&mut buf);
+        Expr::Raw(buf)
+    }
 }
 /// A number-like parameter. This is also used for date, time etc.
 pub trait Number: Any {
     /// Convert to FNumber.
     fn n(&self) -> FNumber {
-        let mut buf = String::new();
-        let _ = self.formula(&mut buf);
-        FNumber(buf)
+        FNumber(self.expr())
     }
 }
 /// A text-like parameter.
@@ -75,6 +105,8 @@ pub trait Scalar: Any {}
 pub trait Field: Any {}
 /// A date or time-like parameter.
 pub trait DateTimeParam: Any {}
+/// A complex number. Text like "1+2i" or a real number.
+pub trait Complex: Any {}
 
 /// Alias for Matrix
 pub use Matrix as Array;
@@ -231,8 +263,8 @@ impl<T: Reference> ReferenceOp<T> for T {
 // -----------------------------------------------------------------------
 
 /// Any value.
-#[derive(Debug)]
-pub struct FAny(String);
+#[derive(Debug, Clone)]
+pub struct FAny(Expr);
 impl Display for FAny {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         write!(f, "{}", self.0)
@@ -240,7 +272,11 @@ impl Display for FAny {
 }
 impl Any for FAny {
     fn formula(&self, buf: &mut String) {
-        buf.push_str(self.0.as_ref());
+        self.0.formula(buf);
+    }
+
+    fn expr(&self) -> Expr {
+        self.0.clone()
     }
 }
 impl Number for FAny {}
@@ -251,10 +287,11 @@ impl TextOrNumber for FAny {}
 impl Field for FAny {}
 impl Scalar for FAny {}
 impl DateTimeParam for FAny {}
+impl Complex for FAny {}
 
 /// Number value.
-#[derive(Debug)]
-pub struct FNumber(String);
+#[derive(Debug, Clone)]
+pub struct FNumber(Expr);
 impl Display for FNumber {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         write!(f, "{}", self.0)
@@ -262,7 +299,11 @@ impl Display for FNumber {
 }
 impl Any for FNumber {
     fn formula(&self, buf: &mut String) {
-        buf.push_str(self.0.as_ref());
+        self.0.formula(buf);
+    }
+
+    fn expr(&self) -> Expr {
+        self.0.clone()
     }
 }
 impl Number for FNumber {}
@@ -272,10 +313,11 @@ impl TextOrNumber for FNumber {}
 impl Field for FNumber {}
 impl Scalar for FNumber {}
 impl DateTimeParam for FNumber {}
+impl Complex for FNumber {}
 
 /// Text value.
-#[derive(Debug)]
-pub struct FText(String);
+#[derive(Debug, Clone)]
+pub struct FText(Expr);
 impl Display for FText {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         write!(f, "{}", self.0)
@@ -283,7 +325,11 @@ impl Display for FText {
 }
 impl Any for FText {
     fn formula(&self, buf: &mut String) {
-        buf.push_str(self.0.as_ref());
+        self.0.formula(buf);
+    }
+
+    fn expr(&self) -> Expr {
+        self.0.clone()
     }
 }
 impl Text for FText {}
@@ -292,10 +338,11 @@ impl TextOrNumber for FText {}
 impl Field for FText {}
 impl Scalar for FText {}
 impl DateTimeParam for FText {}
+impl Complex for FText {}
 
 /// Logical value.
-#[derive(Debug)]
-pub struct FLogical(String);
+#[derive(Debug, Clone)]
+pub struct FLogical(Expr);
 impl Display for FLogical {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         write!(f, "{}", self.0)
@@ -303,7 +350,11 @@ impl Display for FLogical {
 }
 impl Any for FLogical {
     fn formula(&self, buf: &mut String) {
-        buf.push_str(self.0.as_ref());
+        self.0.formula(buf);
+    }
+
+    fn expr(&self) -> Expr {
+        self.0.clone()
     }
 }
 impl Logical for FLogical {}
@@ -313,8 +364,8 @@ impl Scalar for FLogical {}
 impl TextOrNumber for FLogical {}
 
 /// Matrix value.
-#[derive(Debug)]
-pub struct FMatrix(String);
+#[derive(Debug, Clone)]
+pub struct FMatrix(Expr);
 impl Display for FMatrix {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         write!(f, "{}", self.0)
@@ -322,14 +373,45 @@ impl Display for FMatrix {
 }
 impl Any for FMatrix {
     fn formula(&self, buf: &mut String) {
-        buf.push_str(self.0.as_ref());
+        self.0.formula(buf);
+    }
+
+    fn expr(&self) -> Expr {
+        self.0.clone()
     }
 }
 impl Matrix for FMatrix {}
 
+/// Alias for FMatrix
+pub use FMatrix as FArray;
+
+/// Complex number value.
+#[derive(Debug, Clone)]
+pub struct FComplex(Expr);
+impl Display for FComplex {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+impl Any for FComplex {
+    fn formula(&self, buf: &mut String) {
+        self.0.formula(buf);
+    }
+
+    fn expr(&self) -> Expr {
+        self.0.clone()
+    }
+}
+impl Complex for FComplex {}
+impl Sequence for FComplex {}
+impl Scalar for FComplex {}
+
+/// Alias for ErrorValue
+pub use ErrorValue as FormulaError;
+
 /// Reference value.
-#[derive(Debug)]
-pub struct FReference(String);
+#[derive(Debug, Clone)]
+pub struct FReference(Expr);
 impl Display for FReference {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         write!(f, "{}", self.0)
@@ -337,7 +419,11 @@ impl Display for FReference {
 }
 impl Any for FReference {
     fn formula(&self, buf: &mut String) {
-        buf.push_str(self.0.as_ref());
+        self.0.formula(buf);
+    }
+
+    fn expr(&self) -> Expr {
+        self.0.clone()
     }
 }
 impl Reference for FReference {}
@@ -350,6 +436,67 @@ impl TextOrNumber for FReference {}
 impl Field for FReference {}
 impl Scalar for FReference {}
 impl DateTimeParam for FReference {}
+impl Complex for FReference {}
+
+macro_rules! value_expr {
+    ($t:ident) => {
+        impl From<Expr> for $t {
+            fn from(expr: Expr) -> Self {
+                $t(expr)
+            }
+        }
+        impl From<$t> for Expr {
+            fn from(value: $t) -> Self {
+                value.0
+            }
+        }
+    };
+}
+
+/// Error value.
+#[derive(Debug, Clone)]
+pub struct FError(Expr);
+impl Display for FError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+impl Any for FError {
+    fn formula(&self, buf: &mut String) {
+        self.0.formula(buf);
+    }
+
+    fn expr(&self) -> Expr {
+        self.0.clone()
+    }
+}
+impl Number for FError {}
+impl Text for FError {}
+impl Logical for FError {}
+impl Reference for FError {}
+impl Matrix for FError {}
+impl Criterion for FError {}
+impl Sequence for FError {}
+impl TextOrNumber for FError {}
+impl Field for FError {}
+impl Scalar for FError {}
+impl DateTimeParam for FError {}
+impl Complex for FError {}
+
+impl From<ErrorValue> for FError {
+    fn from(value: ErrorValue) -> Self {
+        FError(Expr::Error(value))
+    }
+}
+
+value_expr!(FAny);
+value_expr!(FNumber);
+value_expr!(FText);
+value_expr!(FLogical);
+value_expr!(FMatrix);
+value_expr!(FReference);
+value_expr!(FError);
+value_expr!(FComplex);
 
 /// Filter criteria.
 #[derive(Debug)]
@@ -378,13 +525,11 @@ impl Display for CriterionCmp {
 }
 
 /// Filter/search
-#[derive(Debug)]
-pub struct FCriterion(String);
+#[derive(Debug, Clone)]
+pub struct FCriterion(Expr);
 impl FCriterion {
     pub fn new<T: Any>(op: CriterionCmp, f: T) -> Self {
-        let mut buf = String::new();
-        (op, f).formula(&mut buf);
-        Self(buf)
+        Self((op, f).expr())
     }
 }
 
@@ -396,16 +541,26 @@ impl Display for FCriterion {
 
 impl Any for FCriterion {
     fn formula(&self, buf: &mut String) {
-        buf.push_str(self.0.as_str());
+        self.0.formula(buf);
+    }
+
+    fn expr(&self) -> Expr {
+        self.0.clone()
     }
 }
 impl Criterion for FCriterion {}
 
 impl<A: Any> Any for (CriterionCmp, A) {
     fn formula(&self, buf: &mut String) {
-        let _ = write!(buf, "\"{}\"", self.0);
-        buf.push('&');
-        self.1.formula(buf);
+        self.expr().formula(buf);
+    }
+
+    fn expr(&self) -> Expr {
+        Expr::infix(
+            Expr::Text(self.0.to_string()),
+            InfixOp::Concat,
+            self.1.expr(),
+        )
     }
 }
 impl<A: Any> Criterion for (CriterionCmp, A) {}
@@ -416,159 +571,128 @@ impl<A: Any> Criterion for (CriterionCmp, A) {}
 /// Creates a formula from any formula expression.
 pub fn formula<T: Any>(f: T) -> String {
     let mut buf = String::new();
-    buf.push_str("of=");
-    let _ = f.formula(&mut buf);
+    let _ = formula_into(&mut buf, f);
     buf
 }
 
-#[inline(never)]
-fn func0(name: &str) -> String {
+/// Creates a formula from any formula expression, if it passes validate().
+pub fn try_formula<T: Any>(f: T) -> Result<String, ValidationError> {
+    let expr = f.expr();
+    validate(&expr)?;
     let mut buf = String::new();
-    buf.push_str(name);
-    buf.push('(');
-    buf.push(')');
-    buf
+    let _ = formula_into(&mut buf, expr);
+    Ok(buf)
 }
 
-#[inline(never)]
-fn func1(name: &str, arg0: &dyn Any) -> String {
+/// Creates the formula text with R1C1 references relative to the anchor cell,
+/// e.g. [.R[-1]C]*2. This is no OpenFormula and has no "of=" prefix. It is meant
+/// for comparing formulas row to row, parse_expr_r1c1() reads it back.
+pub fn formula_r1c1<T: Any>(f: T, anchor: &CellRef) -> String {
     let mut buf = String::new();
-    buf.push_str(name);
-    buf.push('(');
-    arg0.formula(&mut buf);
-    buf.push(')');
+    let _ = f.expr().formula_styled(
+        &mut buf,
+        RefStyle::R1C1 {
+            row: anchor.row(),
+            col: anchor.col(),
+        },
+    );
     buf
 }
 
+/// Writes the formula for any formula expression into the buffer.
+/// The expression is written in one pass, no intermediate strings are created.
+pub fn formula_into<W: Write + ?Sized, T: Any>(buf: &mut W, f: T) -> std::fmt::Result {
+    buf.write_str("of=")?;
+    f.expr().formula_into(buf)
+}
+
+// All builders go through here. Checks that the function is known.
+#[inline]
+fn catalog_name(name: &'static str) -> &'static str {
+    debug_assert!(
+        function_info(name).is_some(),
+        "{} is missing in the function catalog",
+        name
+    );
+    name
+}
+
 #[inline(never)]
-fn func2(name: &str, arg0: &dyn Any, arg1: &dyn Any) -> String {
-    let mut buf = String::new();
-    buf.push_str(name);
-    buf.push('(');
-    arg0.formula(&mut buf);
-    buf.push(';');
-    arg1.formula(&mut buf);
-    buf.push(')');
-    buf
+fn func0(name: &'static str) -> Expr {
+    Expr::func(catalog_name(name), Vec::new())
 }
 
 #[inline(never)]
-fn func3(name: &str, arg0: &dyn Any, arg1: &dyn Any, arg2: &dyn Any) -> String {
-    let mut buf = String::new();
-    buf.push_str(name);
-    buf.push('(');
-    arg0.formula(&mut buf);
-    buf.push(';');
-    arg1.formula(&mut buf);
-    buf.push(';');
-    arg2.formula(&mut buf);
-    buf.push(')');
-    buf
+fn func1(name: &'static str, arg0: &dyn Any) -> Expr {
+    Expr::func(catalog_name(name), vec![arg0.expr()])
 }
 
 #[inline(never)]
-fn func4(name: &str, arg0: &dyn Any, arg1: &dyn Any, arg2: &dyn Any, arg3: &dyn Any) -> String {
-    let mut buf = String::new();
-    buf.push_str(name);
-    buf.push('(');
-    arg0.formula(&mut buf);
-    buf.push(';');
-    arg1.formula(&mut buf);
-    buf.push(';');
-    arg2.formula(&mut buf);
-    buf.push(';');
-    arg3.formula(&mut buf);
-    buf.push(')');
-    buf
+fn func2(name: &'static str, arg0: &dyn Any, arg1: &dyn Any) -> Expr {
+    Expr::func(catalog_name(name), vec![arg0.expr(), arg1.expr()])
 }
 
 #[inline(never)]
-fn func5(
-    name: &str,
+fn func3(name: &'static str, arg0: &dyn Any, arg1: &dyn Any, arg2: &dyn Any) -> Expr {
+    Expr::func(
+        catalog_name(name),
+        vec![arg0.expr(), arg1.expr(), arg2.expr()],
+    )
+}
+
+#[inline(never)]
+fn func4(
+    name: &'static str,
     arg0: &dyn Any,
     arg1: &dyn Any,
     arg2: &dyn Any,
     arg3: &dyn Any,
-    arg4: &dyn Any,
-) -> String {
-    let mut buf = String::new();
-    buf.push_str(name);
-    buf.push('(');
-    arg0.formula(&mut buf);
-    buf.push(';');
-    arg1.formula(&mut buf);
-    buf.push(';');
-    arg2.formula(&mut buf);
-    buf.push(';');
-    arg3.formula(&mut buf);
-    buf.push(';');
-    arg4.formula(&mut buf);
-    buf.push(')');
-    buf
-}
-
-// create a array for the parameters.
-fn create_param<'a>(n: usize) -> Box<[MaybeUninit<&'a dyn Any>]> {
-    let layout = alloc::Layout::array::<MaybeUninit<&dyn Any>>(n).unwrap();
-
-    assert!(layout.size() > 0);
-
-    let sl = unsafe {
-        let ptr = std::alloc::alloc(layout) as *mut MaybeUninit<&dyn Any>;
-        if ptr.is_null() {
-            alloc::handle_alloc_error(layout);
-        }
-
-        slice::from_raw_parts(ptr, n)
-    };
-
-    Box::from(sl)
+) -> Expr {
+    Expr::func(
+        catalog_name(name),
+        vec![arg0.expr(), arg1.expr(), arg2.expr(), arg3.expr()],
+    )
 }
 
-// assume init
-#[inline]
-unsafe fn param_assume_init(p: Box<[MaybeUninit<&dyn Any>]>) -> Box<[&dyn Any]> {
-    mem::transmute(p)
+#[inline(never)]
+fn func5(
+    name: &'static str,
+    arg0: &dyn Any,
+    arg1: &dyn Any,
+    arg2: &dyn Any,
+    arg3: &dyn Any,
+    arg4: &dyn Any,
+) -> Expr {
+    Expr::func(
+        catalog_name(name),
+        vec![
+            arg0.expr(),
+            arg1.expr(),
+            arg2.expr(),
+            arg3.expr(),
+            arg4.expr(),
+        ],
+    )
 }
 
 #[inline(never)]
-fn func(name: &str, args: &[&dyn Any]) -> String {
-    let mut buf = String::new();
-    buf.push_str(name);
-    buf.push('(');
-    for (i, v) in args.iter().enumerate() {
-        if i > 0 {
-            buf.push(';');
-        }
-        let _ = v.formula(&mut buf);
-    }
-    buf.push(')');
-    buf
+fn func(name: &'static str, args: &[&dyn Any]) -> Expr {
+    Expr::func(catalog_name(name), args.iter().map(|v| v.expr()).collect())
 }
 
 #[inline]
-fn infix<'a, A: Any, B: Any>(a: A, op: &str, b: B) -> String {
-    let mut buf = String::new();
-    a.formula(&mut buf);
-    buf.push_str(op);
-    b.formula(&mut buf);
-    buf
+fn infix<A: Any, B: Any>(a: A, op: InfixOp, b: B) -> Expr {
+    Expr::infix(a.expr(), op, b.expr())
 }
 
 #[inline]
-fn prefix<'a, A: Any>(op: &str, a: A) -> String {
-    let mut buf = String::new();
-    buf.push_str(op);
-    a.formula(&mut buf);
-    buf
+fn prefix<A: Any>(op: PrefixOp, a: A) -> Expr {
+    Expr::prefix(op, a.expr())
 }
 
 #[inline]
-fn postfix<'a, A: Any>(a: A, op: &str) -> String {
-    let mut buf = String::new();
-    a.formula(&mut buf);
-    buf.push_str(op);
-    buf
+fn postfix<A: Any>(a: A, op: PostfixOp) -> Expr {
+    Expr::postfix(a.expr(), op)
 }
 
 // -----------------------------------------------------------------------
@@ -578,6 +702,10 @@ impl<T: Any + ?Sized> Any for &T {
     fn formula(&self, buf: &mut String) {
         (*self).formula(buf);
     }
+
+    fn expr(&self) -> Expr {
+        (*self).expr()
+    }
 }
 impl<T: Number + Any + ?Sized> Number for &T {}
 impl<T: Text + Any + ?Sized> Text for &T {}
@@ -589,6 +717,7 @@ impl<T: Matrix + Any + ?Sized> Matrix for &T {}
 impl<T: TextOrNumber + Any + ?Sized> TextOrNumber for &T {}
 impl<T: Field + Any + ?Sized> Field for &T {}
 impl<T: DateTimeParam + Any + ?Sized> DateTimeParam for &T {}
+impl<T: Complex + Any + ?Sized> Complex for &T {}
 
 impl<T: Any + Sized> Any for Option<T> {
     fn formula(&self, buf: &mut String) {
@@ -596,6 +725,13 @@ impl<T: Any + Sized> Any for Option<T> {
             v.formula(buf);
         }
     }
+
+    fn expr(&self) -> Expr {
+        match self {
+            Some(v) => v.expr(),
+            None => Expr::Missing,
+        }
+    }
 }
 impl<T: Number + Any + Sized> Number for Option<T> {}
 impl<T: Text + Any + Sized> Text for Option<T> {}
@@ -607,6 +743,7 @@ impl<T: Matrix + Any + Sized> Matrix for Option<T> {}
 impl<T: TextOrNumber + Any + Sized> TextOrNumber for Option<T> {}
 impl<T: Field + Any + Sized> Field for Option<T> {}
 impl<T: DateTimeParam + Any + Sized> DateTimeParam for Option<T> {}
+impl<T: Complex + Any + Sized> Complex for Option<T> {}
 
 impl<T: Any, const N: usize, const M: usize> Any for [[T; M]; N] {
     fn formula(&self, buf: &mut String) {
@@ -624,12 +761,24 @@ impl<T: Any, const N: usize, const M: usize> Any for [[T; M]; N] {
         }
         buf.push('}');
     }
+
+    fn expr(&self) -> Expr {
+        Expr::Array(
+            self.iter()
+                .map(|r| r.iter().map(|c| c.expr()).collect())
+                .collect(),
+        )
+    }
 }
 impl<T: Any, const N: usize, const M: usize> Matrix for [[T; M]; N] {}
 impl<T: Any, const N: usize, const M: usize> Sequence for [[T; M]; N] {}
 
 impl Any for () {
     fn formula(&self, _buf: &mut String) {}
+
+    fn expr(&self) -> Expr {
+        Expr::Missing
+    }
 }
 
 impl<T: Any> Any for Vec<T> {
@@ -641,6 +790,10 @@ impl<T: Any> Any for Vec<T> {
             v.formula(buf);
         }
     }
+
+    fn expr(&self) -> Expr {
+        Expr::List(self.iter().map(|v| v.expr()).collect())
+    }
 }
 impl<T: Any> Sequence for Vec<T> {}
 
@@ -654,6 +807,10 @@ macro_rules! tup {
                     self . $tnum .formula(buf);
                 )*
             }
+
+            fn expr(&self) -> Expr {
+                Expr::List(vec![self.0.expr() $(, self . $tnum .expr())*].into())
+            }
         }
         impl<$tzero: Any, $($tname: Any,)*> Sequence for ($tzero, $($tname,)*) {}
     }
@@ -683,6 +840,9 @@ tup!(A B 1 C 2 D 3 E 4 F 5 G 6 H 7 I 8 J 9 K 10 L 11 M 12 N 13 O 14 P 15 Q 16 R
 
 /// An expression in parentheses.
 /// Use p() / parentheses() to create one.
+///
+/// Operators insert the necessary parentheses themselves, this is only needed
+/// to force them.
 pub struct FParentheses<A>(A);
 impl<A: Any> Any for FParentheses<A> {
     fn formula(&self, buf: &mut String) {
@@ -690,6 +850,10 @@ impl<A: Any> Any for FParentheses<A> {
         self.0.formula(buf);
         buf.push(')');
     }
+
+    fn expr(&self) -> Expr {
+        Expr::parentheses(self.0.expr())
+    }
 }
 impl<A: Number> Number for FParentheses<A> {}
 impl<A: Text> Text for FParentheses<A> {}
@@ -699,6 +863,7 @@ impl<A: Sequence> Sequence for FParentheses<A> {}
 impl<A: TextOrNumber> TextOrNumber for FParentheses<A> {}
 impl<A: Field> Field for FParentheses<A> {}
 impl<A: DateTimeParam> DateTimeParam for FParentheses<A> {}
+impl<A: Complex> Complex for FParentheses<A> {}
 
 /// Creates an expression in parentheses. Aliased as p().
 pub fn parentheses<A: Any>(a: A) -> FParentheses<A> {
@@ -714,6 +879,10 @@ macro_rules! value_number {
             fn formula(&self, buf: &mut String) {
                 let _ = write!(buf, "{}", self);
             }
+
+            fn expr(&self) -> Expr {
+                Expr::Number(self.to_string())
+            }
         }
         impl Number for $t {}
         impl Logical for $t {}
@@ -722,6 +891,7 @@ macro_rules! value_number {
         impl Field for $t {}
         impl Scalar for $t {}
         impl DateTimeParam for $t {}
+        impl Complex for $t {}
     };
 }
 
@@ -737,35 +907,88 @@ value_number!(u32);
 value_number!(u64);
 value_number!(u128);
 value_number!(usize);
-value_number!(f32);
-value_number!(f64);
+
+// Shortest representation that reads back as the same value. Very large
+// and small numbers use the exponent form, NaN and infinity are #NUM!.
+macro_rules! value_float {
+    ($t:ty) => {
+        impl Any for $t {
+            fn formula(&self, buf: &mut String) {
+                let _ = self.expr().formula_into(buf);
+            }
+
+            fn expr(&self) -> Expr {
+                if !self.is_finite() {
+                    Expr::Error(ErrorValue::Num)
+                } else if *self == 0.0 {
+                    Expr::Number("0".to_string())
+                } else if (1e-5..1e15).contains(&self.abs()) {
+                    Expr::Number(self.to_string())
+                } else {
+                    Expr::Number(format!("{:E}", self))
+                }
+            }
+        }
+        impl Number for $t {}
+        impl Logical for $t {}
+        impl Sequence for $t {}
+        impl TextOrNumber for $t {}
+        impl Field for $t {}
+        impl Scalar for $t {}
+        impl DateTimeParam for $t {}
+        impl Complex for $t {}
+    };
+}
+
+value_float!(f32);
+value_float!(f64);
+
+#[cfg(feature = "rust_decimal")]
+value_number!(rust_decimal::Decimal);
 
 impl Any for bool {
     fn formula(&self, buf: &mut String) {
         buf.push_str(if *self { "TRUE()" } else { "FALSE()" });
     }
+
+    fn expr(&self) -> Expr {
+        Expr::Logical(*self)
+    }
 }
 impl Logical for bool {}
 impl Number for bool {}
 impl Scalar for bool {}
 impl Sequence for bool {}
 
+impl Any for ErrorValue {
+    fn formula(&self, buf: &mut String) {
+        buf.push_str(self.as_str());
+    }
+
+    fn expr(&self) -> Expr {
+        Expr::Error(*self)
+    }
+}
+impl Number for ErrorValue {}
+impl Text for ErrorValue {}
+impl Logical for ErrorValue {}
+impl Reference for ErrorValue {}
+impl Matrix for ErrorValue {}
+impl Criterion for ErrorValue {}
+impl Sequence for ErrorValue {}
+impl TextOrNumber for ErrorValue {}
+impl Field for ErrorValue {}
+impl Scalar for ErrorValue {}
+impl DateTimeParam for ErrorValue {}
+impl Complex for ErrorValue {}
+
 impl Any for &str {
     fn formula(&self, buf: &mut String) {
-        if self.contains('"') {
-            buf.push('"');
-            for (i, s) in self.split('"').enumerate() {
-                if i > 0 {
-                    buf.push_str("\"\"");
-                }
-                buf.push_str(s);
-            }
-            buf.push('"');
-        } else {
-            buf.push('"');
-            buf.push_str(self);
-            buf.push('"');
-        }
+        let _ = text_literal(buf, self);
+    }
+
+    fn expr(&self) -> Expr {
+        Expr::Text(self.to_string())
     }
 }
 impl Text for &str {}
@@ -774,24 +997,15 @@ impl TextOrNumber for &str {}
 impl Field for &str {}
 impl Scalar for &str {}
 impl DateTimeParam for &str {}
+impl Complex for &str {}
 
 impl<'a> Any for Cow<'a, str> {
     fn formula(&self, buf: &mut String) {
-        let str = self.as_ref();
-        if str.contains('"') {
-            buf.push('"');
-            for (i, s) in str.split('"').enumerate() {
-                if i > 0 {
-                    buf.push_str("\"\"");
-                }
-                buf.push_str(s);
-            }
-            buf.push('"');
-        } else {
-            buf.push('"');
-            buf.push_str(str);
-            buf.push('"');
-        }
+        let _ = text_literal(buf, self);
+    }
+
+    fn expr(&self) -> Expr {
+        Expr::Text(self.to_string())
     }
 }
 impl<'a> Text for Cow<'a, str> {}
@@ -800,23 +1014,15 @@ impl<'a> TextOrNumber for Cow<'a, str> {}
 impl<'a> Field for Cow<'a, str> {}
 impl<'a> Scalar for Cow<'a, str> {}
 impl<'a> DateTimeParam for Cow<'a, str> {}
+impl<'a> Complex for Cow<'a, str> {}
 
 impl Any for String {
     fn formula(&self, buf: &mut String) {
-        if self.contains('"') {
-            buf.push('"');
-            for (i, s) in self.split('"').enumerate() {
-                if i > 0 {
-                    buf.push_str("\"\"");
-                }
-                buf.push_str(s);
-            }
-            buf.push('"');
-        } else {
-            buf.push('"');
-            buf.push_str(self);
-            buf.push('"');
-        }
+        let _ = text_literal(buf, self);
+    }
+
+    fn expr(&self) -> Expr {
+        Expr::Text(self.clone())
     }
 }
 impl Text for String {}
@@ -825,11 +1031,16 @@ impl TextOrNumber for String {}
 impl Field for String {}
 impl Scalar for String {}
 impl DateTimeParam for String {}
+impl Complex for String {}
 
 impl Any for CellRef {
     fn formula(&self, buf: &mut String) {
         buf.push_str(self.to_formula().as_str())
     }
+
+    fn expr(&self) -> Expr {
+        Expr::CellRef(self.clone())
+    }
 }
 impl Reference for CellRef {}
 impl Number for CellRef {}
@@ -841,11 +1052,16 @@ impl Field for CellRef {}
 impl Scalar for CellRef {}
 impl Matrix for CellRef {}
 impl DateTimeParam for CellRef {}
+impl Complex for CellRef {}
 
 impl Any for CellRange {
     fn formula(&self, buf: &mut String) {
         buf.push_str(self.to_formula().as_str())
     }
+
+    fn expr(&self) -> Expr {
+        Expr::CellRange(self.clone())
+    }
 }
 impl Reference for CellRange {}
 impl Number for CellRange {}
@@ -857,111 +1073,191 @@ impl Field for CellRange {}
 impl Scalar for CellRange {}
 impl Matrix for CellRange {}
 impl DateTimeParam for CellRange {}
+impl Complex for CellRange {}
+
+/// Writes a cell reference as [.R[-1]C] relative to the anchor cell.
+pub(crate) fn cellref_r1c1<W: Write + ?Sized>(
+    buf: &mut W,
+    r: &CellRef,
+    row: u32,
+    col: u32,
+) -> std::fmt::Result {
+    buf.write_char('[')?;
+    if let Some(iri) = r.iri() {
+        quoted_name(buf, iri, true)?;
+        buf.write_char('#')?;
+    }
+    if let Some(table) = r.table() {
+        quoted_name(buf, table, false)?;
+    }
+    buf.write_char('.')?;
+    r1c1(buf, r.row_abs(), r.row(), r.col_abs(), r.col(), row, col)?;
+    buf.write_char(']')
+}
+
+/// Writes a cell range as [.R1C1:.R[2]C] relative to the anchor cell.
+pub(crate) fn cellrange_r1c1<W: Write + ?Sized>(
+    buf: &mut W,
+    r: &CellRange,
+    row: u32,
+    col: u32,
+) -> std::fmt::Result {
+    buf.write_char('[')?;
+    if let Some(iri) = r.iri() {
+        quoted_name(buf, iri, true)?;
+        buf.write_char('#')?;
+    }
+    if let Some(table) = r.table() {
+        quoted_name(buf, table, false)?;
+    }
+    buf.write_char('.')?;
+    r1c1(buf, r.row_abs(), r.row(), r.col_abs(), r.col(), row, col)?;
+    buf.write_char(':')?;
+    if let Some(table) = r.to_table() {
+        quoted_name(buf, table, false)?;
+    }
+    buf.write_char('.')?;
+    r1c1(
+        buf,
+        r.to_row_abs(),
+        r.to_row(),
+        r.to_col_abs(),
+        r.to_col(),
+        row,
+        col,
+    )?;
+    buf.write_char(']')
+}
+
+/// Writes R1C1, R[-1]C[2] or RC. Relative parts are offsets to the anchor.
+pub(crate) fn r1c1<W: Write + ?Sized>(
+    buf: &mut W,
+    row_abs: bool,
+    row: u32,
+    col_abs: bool,
+    col: u32,
+    anchor_row: u32,
+    anchor_col: u32,
+) -> std::fmt::Result {
+    for (c, abs, v, anchor) in [
+        ('R', row_abs, row, anchor_row),
+        ('C', col_abs, col, anchor_col),
+    ] {
+        buf.write_char(c)?;
+        if abs {
+            write!(buf, "{}", v + 1)?;
+        } else if v != anchor {
+            write!(buf, "[{}]", v as i64 - anchor as i64)?;
+        }
+    }
+    Ok(())
+}
+
+// Table names and iris in references, quoted if necessary.
+fn quoted_name<W: Write + ?Sized>(buf: &mut W, name: &str, always: bool) -> std::fmt::Result {
+    if always || !name.chars().all(|c| c.is_alphanumeric() || c == '_') {
+        buf.write_char('\'')?;
+        for (i, s) in name.split('\'').enumerate() {
+            if i > 0 {
+                buf.write_str("''")?;
+            }
+            buf.write_str(s)?;
+        }
+        buf.write_char('\'')
+    } else {
+        buf.write_str(name)
+    }
+}
 
 // -----------------------------------------------------------------------
 // -----------------------------------------------------------------------
 
 /// Adds two numbers. Also available as postfix add() and as operator +.
 pub fn add<'a, A: Number, B: Number>(a: A, b: B) -> FNumber {
-    FNumber(infix(a, "+", b))
+    FNumber(infix(a, InfixOp::Add, b))
 }
 impl<'a, A: Number> Add<A> for FNumber {
     type Output = FNumber;
 
-    fn add(mut self, rhs: A) -> Self::Output {
-        let buf = &mut self.0;
-        buf.push('+');
-        let _ = rhs.formula(buf);
-        self
+    fn add(self, rhs: A) -> Self::Output {
+        FNumber(Expr::infix(self.0, InfixOp::Add, rhs.expr()))
     }
 }
 impl<A: Number> Add<A> for FParentheses<A> {
     type Output = FNumber;
 
     fn add(self, rhs: A) -> Self::Output {
-        FNumber(infix(self, "+", rhs))
+        FNumber(infix(self, InfixOp::Add, rhs))
     }
 }
 
 /// Subtracts two numbers. Also available as postfix sub() and as operator -.
 pub fn sub<'a, A: Number, B: Number>(a: A, b: B) -> FNumber {
-    FNumber(infix(a, "-", b))
+    FNumber(infix(a, InfixOp::Sub, b))
 }
 impl<'a, A: Number> Sub<A> for FNumber {
     type Output = FNumber;
 
-    fn sub(mut self, rhs: A) -> Self::Output {
-        let buf = &mut self.0;
-        buf.push('-');
-        let _ = rhs.formula(buf);
-        self
+    fn sub(self, rhs: A) -> Self::Output {
+        FNumber(Expr::infix(self.0, InfixOp::Sub, rhs.expr()))
     }
 }
 impl<A: Number> Sub<A> for FParentheses<A> {
     type Output = FNumber;
 
     fn sub(self, rhs: A) -> Self::Output {
-        FNumber(infix(self, "-", rhs))
+        FNumber(infix(self, InfixOp::Sub, rhs))
     }
 }
 
 /// Multiplies to numbers. Also available as postfix mul() and as operator *;
 pub fn mul<'a, A: Number, B: Number>(a: A, b: B) -> FNumber {
-    FNumber(infix(a, "*", b))
+    FNumber(infix(a, InfixOp::Mul, b))
 }
 impl<'a, A: Number> Mul<A> for FNumber {
     type Output = FNumber;
 
-    fn mul(mut self, rhs: A) -> Self::Output {
-        let buf = &mut self.0;
-        buf.push('*');
-        let _ = rhs.formula(buf);
-        self
+    fn mul(self, rhs: A) -> Self::Output {
+        FNumber(Expr::infix(self.0, InfixOp::Mul, rhs.expr()))
     }
 }
 impl<A: Number> Mul<A> for FParentheses<A> {
     type Output = FNumber;
 
     fn mul(self, rhs: A) -> Self::Output {
-        FNumber(infix(self, "*", rhs))
+        FNumber(infix(self, InfixOp::Mul, rhs))
     }
 }
 
 /// Divides to numbers. Also available as postfix div() and as operator /.
 pub fn div<'a, A: Number, B: Number>(a: A, b: B) -> FNumber {
-    FNumber(infix(a, "/", b))
+    FNumber(infix(a, InfixOp::Div, b))
 }
 impl<'a, A: Number> Div<A> for FNumber {
     type Output = FNumber;
 
-    fn div(mut self, rhs: A) -> Self::Output {
-        let buf = &mut self.0;
-        buf.push('/');
-        let _ = rhs.formula(buf);
-        self
+    fn div(self, rhs: A) -> Self::Output {
+        FNumber(Expr::infix(self.0, InfixOp::Div, rhs.expr()))
     }
 }
 impl<A: Number> Div<A> for FParentheses<A> {
     type Output = FNumber;
 
     fn div(self, rhs: A) -> Self::Output {
-        FNumber(infix(self, "/", rhs))
+        FNumber(infix(self, InfixOp::Div, rhs))
     }
 }
 
 /// Exponential function. Also available as postfix pow() and as operator ^.
 pub fn pow<'a, A: Number, B: Number>(a: A, b: B) -> FNumber {
-    FNumber(infix(a, "^", b))
+    FNumber(infix(a, InfixOp::Pow, b))
 }
 /// Not bitwise xor but exponential.
 impl<'a, A: Number> BitXor<A> for FNumber {
     type Output = FNumber;
 
-    fn bitxor(mut self, rhs: A) -> Self::Output {
-        let buf = &mut self.0;
-        buf.push('^');
-        let _ = rhs.formula(buf);
-        self
+    fn bitxor(self, rhs: A) -> Self::Output {
+        FNumber(Expr::infix(self.0, InfixOp::Pow, rhs.expr()))
     }
 }
 /// Not bitwise xor but exponential.
@@ -969,87 +1265,82 @@ impl<A: Number> BitXor<A> for FParentheses<A> {
     type Output = FNumber;
 
     fn bitxor(self, rhs: A) -> Self::Output {
-        FNumber(infix(self, "^", rhs))
+        FNumber(infix(self, InfixOp::Pow, rhs))
     }
 }
 
 /// Negates as number. Also available as prefix operator -.
 pub fn neg<'a, A: Number>(a: A) -> FNumber {
-    FNumber(prefix("-", a))
+    FNumber(prefix(PrefixOp::Neg, a))
 }
 impl Neg for FNumber {
     type Output = FNumber;
 
-    fn neg(mut self) -> Self::Output {
-        let buf = &mut self.0;
-        buf.insert(0, '-');
-        self
+    fn neg(self) -> Self::Output {
+        FNumber(Expr::prefix(PrefixOp::Neg, self.0))
     }
 }
 impl<A: Number> Neg for FParentheses<A> {
     type Output = FNumber;
 
     fn neg(self) -> Self::Output {
-        FNumber(prefix("-", self.0))
+        FNumber(prefix(PrefixOp::Neg, self))
     }
 }
 
 /// equal
 pub fn eq<'a, A: Any, B: Any>(a: A, b: B) -> FLogical {
-    FLogical(infix(a, "=", b))
+    FLogical(infix(a, InfixOp::Eq, b))
 }
 
 /// inequal
 pub fn ne<'a, A: Any, B: Any>(a: A, b: B) -> FLogical {
-    FLogical(infix(a, "<>", b))
+    FLogical(infix(a, InfixOp::Ne, b))
 }
 
 /// less than
 pub fn lt<'a, A: Any, B: Any>(a: A, b: B) -> FLogical {
-    FLogical(infix(a, "<", b))
+    FLogical(infix(a, InfixOp::Lt, b))
 }
 
 /// less than or equal
 pub fn le<'a, A: Any, B: Any>(a: A, b: B) -> FLogical {
-    FLogical(infix(a, "<=", b))
+    FLogical(infix(a, InfixOp::Le, b))
 }
 
 /// greater than
 pub fn gt<'a, A: Any, B: Any>(a: A, b: B) -> FLogical {
-    FLogical(infix(a, ">", b))
+    FLogical(infix(a, InfixOp::Gt, b))
 }
 
 /// greater than or equal
 pub fn ge<'a, A: Any, B: Any>(a: A, b: B) -> FLogical {
-    FLogical(infix(a, ">=", b))
+    FLogical(infix(a, InfixOp::Ge, b))
 }
 
 /// percentage. Also available as postfix percent()
 pub fn percent<'a, A: Number>(a: A) -> FNumber {
-    FNumber(postfix(a, "%"))
+    FNumber(postfix(a, PostfixOp::Percent))
 }
 
 /// concatenates two strings. Also available as postfix concat() and as operator &.
 pub fn concat<'a, A: Text, B: Text>(a: A, b: B) -> FText {
-    FText(infix(a, "&", b))
+    FText(infix(a, InfixOp::Concat, b))
 }
 
 impl<'a, A: Text> BitAnd<A> for FText {
     type Output = FText;
 
-    fn bitand(mut self, rhs: A) -> Self::Output {
-        let buf = &mut self.0;
-        buf.push('&');
-        let _ = rhs.formula(buf);
-        self
+    fn bitand(self, rhs: A) -> Self::Output {
+        FText(Expr::infix(self.0, InfixOp::Concat, rhs.expr()))
     }
 }
 
 pub fn intersect<'a, A: Reference, B: Reference>(a: A, b: B) -> FReference {
-    FReference(infix(a, "!", b))
+    FReference(infix(a, InfixOp::Intersect, b))
 }
 pub fn refcat<'a, A: Reference, B: Reference>(a: A, b: B) -> FReference {
-    FReference(infix(a, "~", b))
+    FReference(infix(a, InfixOp::RefConcat, b))
 }
 
 // -----------------------------------------------------------------------
@@ -1,6 +1,6 @@
 use crate::{
-    create_param, func, func0, func1, func2, func3, func4, param_assume_init, Array, Criterion,
-    FNumber, Number, Param, Reference, Sequence,
+    func, func0, func1, func2, func3, func4, Any, Array, Criterion, FNumber, Number, Param,
+    Reference, Sequence,
 };
 use std::borrow::Cow;
 use std::fmt::Write;
@@ -704,15 +704,14 @@ pub fn sumif2(range: impl Reference, criterion: impl Criterion, sum: impl Refere
 
 #[inline]
 pub fn sumifs(range: impl Reference, criterion: &[(impl Reference, impl Criterion)]) -> FNumber {
-    let mut param = create_param(criterion.len() * 2 + 1);
-    param[0].write(&range);
-    for (i, (r, c)) in criterion.iter().enumerate() {
-        param[1 + 2 * i].write(r);
-        param[1 + 2 * i + 1].write(c);
+    let mut param: Vec<&dyn Any> = Vec::with_capacity(criterion.len() * 2 + 1);
+    param.push(&range);
+    for (r, c) in criterion {
+        param.push(r);
+        param.push(c);
     }
-    let param = unsafe { param_assume_init(param) };
 
-    FNumber(func("SUMIFS", param.as_ref()))
+    FNumber(func("SUMIFS", &param))
 }
 
 #[inline]
@@ -16,7 +16,7 @@ pub fn minverse(a: impl Matrix) -> FMatrix {
 /// Multiplies the matrices A and B.
 #[inline]
 pub fn mmult(a: impl Matrix, b: impl Matrix) -> FMatrix {
-    FMatrix(func2("MMUL", &a, &b))
+    FMatrix(func2("MMULT", &a, &b))
 }
 
 /// Creates a unit matrix of a specified dimension N.
@@ -0,0 +1,1046 @@
+//!
+//! Parser for OpenFormula expressions.
+//!
+//! ```
+//! use ods_formula::parse;
+//!
+//! let f = parse("of=SUM([.A1:.B3])*2").unwrap();
+//! assert_eq!(ods_formula::formula(f), "of=SUM([.A1:.B3])*2");
+//! ```
+//!
+
+use crate::{ErrorValue, Expr, FAny, InfixOp, PostfixOp, PrefixOp, MAX_COLS, MAX_ROWS};
+use spreadsheet_ods::{CellRange, CellRef};
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::ops::Range;
+
+/// Kind of parse error.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseErrorKind {
+    /// The formula ended too early.
+    UnexpectedEnd,
+    /// A character that is not valid at this position.
+    UnexpectedChar(char),
+    /// Missing closing quote, bracket, brace or parenthesis.
+    Unclosed(char),
+    /// Invalid number literal.
+    InvalidNumber,
+    /// Invalid cell reference.
+    InvalidReference,
+    /// Unknown error literal.
+    InvalidError,
+    /// The rows of an inline array differ in length.
+    ArrayShape,
+    /// Parentheses, function calls, arrays or signs are nested deeper
+    /// than MAX_DEPTH.
+    TooDeep,
+}
+
+impl Display for ParseErrorKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseErrorKind::UnexpectedEnd => write!(f, "unexpected end of formula"),
+            ParseErrorKind::UnexpectedChar(c) => write!(f, "unexpected character '{}'", c),
+            ParseErrorKind::Unclosed(c) => write!(f, "missing '{}'", c),
+            ParseErrorKind::InvalidNumber => write!(f, "invalid number"),
+            ParseErrorKind::InvalidReference => write!(f, "invalid reference"),
+            ParseErrorKind::InvalidError => write!(f, "unknown error literal"),
+            ParseErrorKind::ArrayShape => write!(f, "array rows differ in length"),
+            ParseErrorKind::TooDeep => write!(f, "formula is nested too deeply"),
+        }
+    }
+}
+
+/// Parse error with the byte span in the parsed text.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub kind: ParseErrorKind,
+    pub span: Range<usize>,
+}
+
+impl ParseError {
+    fn new(kind: ParseErrorKind, span: Range<usize>) -> Self {
+        Self { kind, span }
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} at {}..{}", self.kind, self.span.start, self.span.end)
+    }
+}
+
+impl Error for ParseError {}
+
+/// Parses an OpenFormula expression. The prefix "of:=", "of=" or "=" is optional.
+pub fn parse(formula: &str) -> Result<FAny, ParseError> {
+    parse_expr(formula).map(FAny::from)
+}
+
+/// Parses an OpenFormula expression into an expression tree.
+/// The prefix "of:=", "of=" or "=" is optional.
+pub fn parse_expr(formula: &str) -> Result<Expr, ParseError> {
+    let start = ["of:=", "of=", "="]
+        .iter()
+        .find(|v| formula.starts_with(*v))
+        .map(|v| v.len())
+        .unwrap_or(0);
+
+    parse_with(&formula[start..], &OPENFORMULA).map_err(|mut e| {
+        e.span = e.span.start + start..e.span.end + start;
+        e
+    })
+}
+
+/// Parses the text written by formula_r1c1(), without prefix. The R1C1
+/// references are resolved relative to the anchor cell.
+pub fn parse_expr_r1c1(formula: &str, anchor: &CellRef) -> Result<Expr, ParseError> {
+    let syntax = Syntax {
+        anchor: Some((anchor.row(), anchor.col())),
+        ..OPENFORMULA
+    };
+    parse_with(formula, &syntax)
+}
+
+/// How references are written.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum RefNotation {
+    /// OpenFormula [.A1]
+    Bracketed,
+    /// LibreOffice UI $Sheet1.A1
+    Calc,
+    /// Excel Sheet1!A1
+    Excel,
+}
+
+/// Separators and notation of a formula syntax.
+pub(crate) struct Syntax<'a> {
+    pub(crate) arg_sep: char,
+    pub(crate) decimal: char,
+    pub(crate) array_col: char,
+    pub(crate) array_row: char,
+    pub(crate) refs: RefNotation,
+    /// Anchor row and column for bracketed R1C1 references, None for A1.
+    pub(crate) anchor: Option<(u32, u32)>,
+    /// Maps a function name as written to the OpenFormula name.
+    pub(crate) func_name: &'a dyn Fn(&str) -> String,
+}
+
+const OPENFORMULA: Syntax<'static> = Syntax {
+    arg_sep: ';',
+    decimal: '.',
+    array_col: ';',
+    array_row: '|',
+    refs: RefNotation::Bracketed,
+    anchor: None,
+    func_name: &|name| name.to_ascii_uppercase(),
+};
+
+/// Excel A1 syntax. Function names are uppercased and mapped afterwards.
+pub(crate) const EXCEL: Syntax<'static> = Syntax {
+    arg_sep: ',',
+    decimal: '.',
+    array_col: ',',
+    array_row: ';',
+    refs: RefNotation::Excel,
+    anchor: None,
+    func_name: &|name| name.to_ascii_uppercase(),
+};
+
+/// Maximum nesting of parentheses, function calls, arrays and signs. Excel has
+/// the same limit for nested functions.
+pub const MAX_DEPTH: usize = 64;
+
+/// Parses a formula without prefix.
+pub(crate) fn parse_with(formula: &str, syntax: &Syntax<'_>) -> Result<Expr, ParseError> {
+    let mut p = Parser {
+        txt: formula,
+        pos: 0,
+        depth: 0,
+        syntax,
+    };
+    let expr = p.comparison()?;
+    p.skip_ws();
+    match p.peek() {
+        None => Ok(expr),
+        Some(c) => Err(p.unexpected(c)),
+    }
+}
+
+struct Parser<'s, 'a> {
+    txt: &'s str,
+    pos: usize,
+    depth: usize,
+    syntax: &'a Syntax<'a>,
+}
+
+impl<'s, 'a> Parser<'s, 'a> {
+    fn rest(&self) -> &'s str {
+        &self.txt[self.pos..]
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.rest().chars().next()
+    }
+
+    fn skip_ws(&mut self) {
+        let rest = self.rest();
+        self.pos += rest.len() - rest.trim_start().len();
+    }
+
+    fn bump(&mut self, c: char) {
+        self.pos += c.len_utf8();
+    }
+
+    /// Skips whitespace and consumes the operator if it's next.
+    /// Otherwise the whitespace is kept, it's an operator in Excel.
+    fn eat(&mut self, op: &str) -> bool {
+        let start = self.pos;
+        self.skip_ws();
+        if self.rest().starts_with(op) {
+            self.pos += op.len();
+            true
+        } else {
+            self.pos = start;
+            false
+        }
+    }
+
+    fn eat_char(&mut self, c: char) -> bool {
+        let mut buf = [0u8; 4];
+        self.eat(c.encode_utf8(&mut buf))
+    }
+
+    fn unexpected(&self, c: char) -> ParseError {
+        ParseError::new(
+            ParseErrorKind::UnexpectedChar(c),
+            self.pos..self.pos + c.len_utf8(),
+        )
+    }
+
+    fn unexpected_here(&self) -> ParseError {
+        match self.peek() {
+            Some(c) => self.unexpected(c),
+            None => ParseError::new(ParseErrorKind::UnexpectedEnd, self.pos..self.pos),
+        }
+    }
+
+    /// Parses a nested expression, limits the recursion.
+    fn nested(&mut self, f: fn(&mut Self) -> Result<Expr, ParseError>) -> Result<Expr, ParseError> {
+        if self.depth >= MAX_DEPTH {
+            return Err(ParseError::new(ParseErrorKind::TooDeep, self.pos..self.pos));
+        }
+        self.depth += 1;
+        let expr = f(self);
+        self.depth -= 1;
+        expr
+    }
+
+    // = <> < <= > >=
+    fn comparison(&mut self) -> Result<Expr, ParseError> {
+        let mut lhs = self.concat()?;
+        loop {
+            let op = if self.eat("<>") {
+                InfixOp::Ne
+            } else if self.eat("<=") {
+                InfixOp::Le
+            } else if self.eat(">=") {
+                InfixOp::Ge
+            } else if self.eat("=") {
+                InfixOp::Eq
+            } else if self.eat("<") {
+                InfixOp::Lt
+            } else if self.eat(">") {
+                InfixOp::Gt
+            } else {
+                return Ok(lhs);
+            };
+            let rhs = self.concat()?;
+            lhs = Expr::infix(lhs, op, rhs);
+        }
+    }
+
+    // &
+    fn concat(&mut self) -> Result<Expr, ParseError> {
+        let mut lhs = self.additive()?;
+        while self.eat("&") {
+            let rhs = self.additive()?;
+            lhs = Expr::infix(lhs, InfixOp::Concat, rhs);
+        }
+        Ok(lhs)
+    }
+
+    // + -
+    fn additive(&mut self) -> Result<Expr, ParseError> {
+        let mut lhs = self.multiplicative()?;
+        loop {
+            let op = if self.eat("+") {
+                InfixOp::Add
+            } else if self.eat("-") {
+                InfixOp::Sub
+            } else {
+                return Ok(lhs);
+            };
+            let rhs = self.multiplicative()?;
+            lhs = Expr::infix(lhs, op, rhs);
+        }
+    }
+
+    // * /
+    fn multiplicative(&mut self) -> Result<Expr, ParseError> {
+        let mut lhs = self.power()?;
+        loop {
+            let op = if self.eat("*") {
+                InfixOp::Mul
+            } else if self.eat("/") {
+                InfixOp::Div
+            } else {
+                return Ok(lhs);
+            };
+            let rhs = self.power()?;
+            lhs = Expr::infix(lhs, op, rhs);
+        }
+    }
+
+    // ^ is left associative.
+    fn power(&mut self) -> Result<Expr, ParseError> {
+        let mut lhs = self.postfix()?;
+        while self.eat("^") {
+            let rhs = self.postfix()?;
+            lhs = Expr::infix(lhs, InfixOp::Pow, rhs);
+        }
+        Ok(lhs)
+    }
+
+    // %
+    fn postfix(&mut self) -> Result<Expr, ParseError> {
+        let mut expr = self.prefix()?;
+        while self.eat("%") {
+            expr = Expr::postfix(expr, PostfixOp::Percent);
+        }
+        Ok(expr)
+    }
+
+    // - +
+    fn prefix(&mut self) -> Result<Expr, ParseError> {
+        if self.eat("-") {
+            Ok(Expr::prefix(PrefixOp::Neg, self.nested(Self::prefix)?))
+        } else if self.eat("+") {
+            Ok(Expr::prefix(PrefixOp::Plus, self.nested(Self::prefix)?))
+        } else {
+            self.refconcat()
+        }
+    }
+
+    // ~
+    fn refconcat(&mut self) -> Result<Expr, ParseError> {
+        let mut lhs = self.intersect()?;
+        while self.eat("~") {
+            let rhs = self.intersect()?;
+            lhs = Expr::infix(lhs, InfixOp::RefConcat, rhs);
+        }
+        Ok(lhs)
+    }
+
+    // !
+    fn intersect(&mut self) -> Result<Expr, ParseError> {
+        let mut lhs = self.range()?;
+        loop {
+            if self.eat("!") {
+                let rhs = self.range()?;
+                lhs = Expr::infix(lhs, InfixOp::Intersect, rhs);
+            } else if let Some(rhs) = self.excel_intersect()? {
+                lhs = Expr::infix(lhs, InfixOp::Intersect, rhs);
+            } else {
+                break;
+            }
+        }
+        Ok(lhs)
+    }
+
+    /// Excel uses a space as intersection operator. Only a reference
+    /// after the space is taken as intersection.
+    fn excel_intersect(&mut self) -> Result<Option<Expr>, ParseError> {
+        if self.syntax.refs != RefNotation::Excel || !self.rest().starts_with(char::is_whitespace) {
+            return Ok(None);
+        }
+        let start = self.pos;
+        self.skip_ws();
+        match self.excel_reference() {
+            Some(rhs) => rhs.map(Some),
+            None => {
+                self.pos = start;
+                Ok(None)
+            }
+        }
+    }
+
+    // :
+    fn range(&mut self) -> Result<Expr, ParseError> {
+        let mut lhs = self.primary()?;
+        while self.eat(":") {
+            let rhs = self.primary()?;
+            lhs = Expr::infix(lhs, InfixOp::Range, rhs);
+        }
+        Ok(lhs)
+    }
+
+    fn primary(&mut self) -> Result<Expr, ParseError> {
+        self.skip_ws();
+        match self.peek() {
+            Some('(') => {
+                let start = self.pos;
+                self.bump('(');
+                let mut expr = self.nested(Self::comparison)?;
+                // Excel union (A1,B2)
+                while self.syntax.refs == RefNotation::Excel && self.eat(",") {
+                    let rhs = self.nested(Self::comparison)?;
+                    expr = Expr::infix(expr, InfixOp::RefConcat, rhs);
+                }
+                if !self.eat(")") {
+                    return Err(self.unclosed(')', start));
+                }
+                Ok(Expr::parentheses(expr))
+            }
+            Some('"') => self.text(),
+            Some('[') if self.syntax.refs == RefNotation::Bracketed => self.reference(),
+            Some('{') => self.array(),
+            Some('#') => self.error(),
+            Some(c) => match self.bare_reference() {
+                Some(r) => r,
+                None if c.is_ascii_digit() || c == self.syntax.decimal => self.number(),
+                None if c.is_alphabetic() || c == '_' => self.identifier(),
+                None => Err(self.unexpected_here()),
+            },
+            _ => Err(self.unexpected_here()),
+        }
+    }
+
+    fn unclosed(&self, c: char, start: usize) -> ParseError {
+        match self.peek() {
+            Some(_) => ParseError::new(ParseErrorKind::Unclosed(c), start..self.pos),
+            None => ParseError::new(ParseErrorKind::Unclosed(c), start..self.txt.len()),
+        }
+    }
+
+    fn number(&mut self) -> Result<Expr, ParseError> {
+        let start = self.pos;
+        let bytes = self.txt.as_bytes();
+        let mut end = start;
+        while end < bytes.len() && bytes[end].is_ascii_digit() {
+            end += 1;
+        }
+        let mut decimal = None;
+        if end < bytes.len() && bytes[end] as char == self.syntax.decimal {
+            decimal = Some(end);
+            end += 1;
+            while end < bytes.len() && bytes[end].is_ascii_digit() {
+                end += 1;
+            }
+        }
+        if end < bytes.len() && (bytes[end] == b'e' || bytes[end] == b'E') {
+            end += 1;
+            if end < bytes.len() && (bytes[end] == b'+' || bytes[end] == b'-') {
+                end += 1;
+            }
+            let exp = end;
+            while end < bytes.len() && bytes[end].is_ascii_digit() {
+                end += 1;
+            }
+            if exp == end {
+                return Err(ParseError::new(ParseErrorKind::InvalidNumber, start..end));
+            }
+        }
+        let mut number = self.txt[start..end].to_string();
+        if let Some(decimal) = decimal {
+            if end - start == 1 {
+                return Err(ParseError::new(ParseErrorKind::InvalidNumber, start..end));
+            }
+            number.replace_range(decimal - start..decimal - start + 1, ".");
+        }
+        self.pos = end;
+        Ok(Expr::Number(number))
+    }
+
+    fn text(&mut self) -> Result<Expr, ParseError> {
+        let start = self.pos;
+        self.bump('"');
+        let mut text = String::new();
+        loop {
+            match self.peek() {
+                Some('"') => {
+                    self.bump('"');
+                    if self.peek() == Some('"') {
+                        self.bump('"');
+                        text.push('"');
+                    } else {
+                        return Ok(Expr::Text(text));
+                    }
+                }
+                Some(c) => {
+                    self.bump(c);
+                    text.push(c);
+                }
+                None => {
+                    return Err(ParseError::new(
+                        ParseErrorKind::Unclosed('"'),
+                        start..self.pos,
+                    ))
+                }
+            }
+        }
+    }
+
+    fn error(&mut self) -> Result<Expr, ParseError> {
+        let start = self.pos;
+        match ErrorValue::ALL
+            .iter()
+            .find(|v| self.rest().starts_with(v.as_str()))
+        {
+            Some(v) => {
+                self.pos += v.as_str().len();
+                Ok(Expr::Error(*v))
+            }
+            None => {
+                let len = self
+                    .rest()
+                    .find(|c: char| !(c.is_alphanumeric() || "#/!?_".contains(c)))
+                    .unwrap_or(self.rest().len());
+                Err(ParseError::new(
+                    ParseErrorKind::InvalidError,
+                    start..start + len.max(1),
+                ))
+            }
+        }
+    }
+
+    fn identifier(&mut self) -> Result<Expr, ParseError> {
+        let start = self.pos;
+        let len = self
+            .rest()
+            .find(|c: char| !(c.is_alphanumeric() || c == '_' || c == '.'))
+            .unwrap_or(self.rest().len());
+        let name = &self.txt[start..start + len];
+        self.pos += len;
+
+        if !self.eat("(") {
+            return Ok(Expr::Name(name.to_string()));
+        }
+
+        let mut args = Vec::new();
+        if !self.eat(")") {
+            loop {
+                self.skip_ws();
+                let arg = match self.peek() {
+                    Some(c) if c == self.syntax.arg_sep || c == ')' => Expr::Missing,
+                    _ => self.nested(Self::comparison)?,
+                };
+                args.push(arg);
+                if self.eat_char(self.syntax.arg_sep) {
+                    continue;
+                } else if self.eat(")") {
+                    break;
+                } else if self.peek().is_none() {
+                    return Err(self.unclosed(')', start));
+                } else {
+                    return Err(self.unexpected_here());
+                }
+            }
+        }
+
+        let name = (self.syntax.func_name)(name);
+        match (name.as_str(), args.is_empty()) {
+            ("TRUE", true) => Ok(Expr::Logical(true)),
+            ("FALSE", true) => Ok(Expr::Logical(false)),
+            _ => Ok(Expr::func(name, args)),
+        }
+    }
+
+    fn array(&mut self) -> Result<Expr, ParseError> {
+        let start = self.pos;
+        self.bump('{');
+        let mut rows: Vec<Vec<Expr>> = Vec::new();
+        let mut row = Vec::new();
+        loop {
+            row.push(self.nested(Self::comparison)?);
+            if self.eat_char(self.syntax.array_col) {
+                continue;
+            } else if self.eat_char(self.syntax.array_row) {
+                rows.push(row);
+                row = Vec::new();
+            } else if self.eat("}") {
+                rows.push(row);
+                break;
+            } else if self.peek().is_none() {
+                return Err(self.unclosed('}', start));
+            } else {
+                return Err(self.unexpected_here());
+            }
+        }
+        if rows.iter().any(|r| r.len() != rows[0].len()) {
+            return Err(ParseError::new(ParseErrorKind::ArrayShape, start..self.pos));
+        }
+        Ok(Expr::Array(rows.into()))
+    }
+
+    fn reference(&mut self) -> Result<Expr, ParseError> {
+        let start = self.pos;
+        let end = match self.find_close_bracket() {
+            Some(end) => end,
+            None => {
+                return Err(ParseError::new(
+                    ParseErrorKind::Unclosed(']'),
+                    start..self.txt.len(),
+                ))
+            }
+        };
+        let invalid = ParseError::new(ParseErrorKind::InvalidReference, start..end + 1);
+        let content = &self.txt[start + 1..end];
+        self.pos = end + 1;
+
+        let (iri, content) = split_iri(content).ok_or_else(|| invalid.clone())?;
+        let (from, to) = match split_unquoted(content, ':') {
+            Some((from, to)) => (from, Some(to)),
+            None => (content, None),
+        };
+        let cell = |s: &str| match self.syntax.anchor {
+            Some((row, col)) => parse_r1c1_cell(s, row, col),
+            None => parse_cell(s),
+        };
+        let (table, row_abs, row, col_abs, col) = cell(from).ok_or_else(|| invalid.clone())?;
+        match to {
+            None => Ok(Expr::CellRef(CellRef::new_all(
+                iri, table, row_abs, row, col_abs, col,
+            ))),
+            Some(to) => {
+                let (to_table, to_row_abs, to_row, to_col_abs, to_col) = cell(to).ok_or(invalid)?;
+                Ok(Expr::CellRange(CellRange::new_all(
+                    iri, table, row_abs, row, col_abs, col, to_table, to_row_abs, to_row,
+                    to_col_abs, to_col,
+                )))
+            }
+        }
+    }
+
+    /// Reference without brackets, None if the text at the position
+    /// is no reference.
+    fn bare_reference(&mut self) -> Option<Result<Expr, ParseError>> {
+        match self.syntax.refs {
+            RefNotation::Bracketed => None,
+            RefNotation::Calc => self.calc_reference(),
+            RefNotation::Excel => self.excel_reference(),
+        }
+    }
+
+    /// Reference in LibreOffice UI notation, A1, $Sheet1.$A$1 or A1:B3.
+    fn calc_reference(&mut self) -> Option<Result<Expr, ParseError>> {
+        let start = self.pos;
+        let rest = self.rest();
+        let (iri, offset) = match split_iri(rest) {
+            Some((Some(iri), cell)) => (Some(iri), rest.len() - cell.len()),
+            _ => (None, 0),
+        };
+        let from_len = calc_cell_len(&rest[offset..])?;
+        let from_end = offset + from_len;
+        let mut end = from_end;
+        let mut to_range = None;
+        if let Some(to) = rest[from_end..].strip_prefix(':') {
+            if let Some(to_len) = calc_cell_len(to) {
+                if !is_name_char(to[to_len..].chars().next()) {
+                    to_range = Some(from_end + 1..from_end + 1 + to_len);
+                    end = from_end + 1 + to_len;
+                }
+            }
+        }
+        if to_range.is_none() && is_name_char(rest[from_end..].chars().next()) {
+            return None;
+        }
+
+        let invalid = ParseError::new(ParseErrorKind::InvalidReference, start..start + end);
+        self.pos = start + end;
+        let Some((table, row_abs, row, col_abs, col)) = parse_calc_cell(&rest[offset..from_end])
+        else {
+            return Some(Err(invalid));
+        };
+        Some(match to_range {
+            None => Ok(Expr::CellRef(CellRef::new_all(
+                iri, table, row_abs, row, col_abs, col,
+            ))),
+            Some(to) => match parse_calc_cell(&rest[to]) {
+                Some((to_table, to_row_abs, to_row, to_col_abs, to_col)) => {
+                    Ok(Expr::CellRange(CellRange::new_all(
+                        iri, table, row_abs, row, col_abs, col, to_table, to_row_abs, to_row,
+                        to_col_abs, to_col,
+                    )))
+                }
+                None => Err(invalid),
+            },
+        })
+    }
+
+    /// Reference in Excel notation, A1, Sheet1!$A$1, 'My Sheet'!A1:B3,
+    /// Sheet1:Sheet3!A1 or whole columns and rows A:B, 1:3.
+    fn excel_reference(&mut self) -> Option<Result<Expr, ParseError>> {
+        let start = self.pos;
+        let rest = self.rest();
+        let (table, to_table, offset) = match excel_sheet(rest) {
+            Some((table, to_table, len)) => (Some(table), to_table, len),
+            None => (None, None, 0),
+        };
+        let cells = &rest[offset..];
+        let invalid = |len: usize| {
+            ParseError::new(
+                ParseErrorKind::InvalidReference,
+                start..start + offset + len,
+            )
+        };
+
+        let (from, to, len) = if let Some(from_len) = a1_len(cells) {
+            match cells[from_len..].strip_prefix(':').and_then(a1_len) {
+                Some(to_len) => {
+                    let len = from_len + 1 + to_len;
+                    (
+                        parse_a1(&cells[..from_len]),
+                        parse_a1(&cells[from_len + 1..len]),
+                        len,
+                    )
+                }
+                None => {
+                    let from = parse_a1(&cells[..from_len]);
+                    (from, from, from_len)
+                }
+            }
+        } else if let Some((from, to, len)) = excel_lines(cells) {
+            (Some(from), Some(to), len)
+        } else if table.is_some() {
+            // Sheet local names are not available in OpenFormula.
+            let len = cells
+                .find(|c: char| !(c.is_alphanumeric() || c == '_' || c == '.'))
+                .unwrap_or(cells.len());
+            self.pos = start + offset + len;
+            return Some(Err(invalid(len)));
+        } else {
+            return None;
+        };
+        if is_name_char(cells[len..].chars().next()) || cells[len..].starts_with('!') {
+            return if table.is_some() {
+                Some(Err(invalid(len)))
+            } else {
+                None
+            };
+        }
+        self.pos = start + offset + len;
+
+        let (Some((row_abs, row, col_abs, col)), Some((to_row_abs, to_row, to_col_abs, to_col))) =
+            (from, to)
+        else {
+            return Some(Err(invalid(len)));
+        };
+        if to_table.is_none() && cells[..len].find(':').is_none() {
+            return Some(Ok(Expr::CellRef(CellRef::new_all(
+                None, table, row_abs, row, col_abs, col,
+            ))));
+        }
+        Some(Ok(Expr::CellRange(CellRange::new_all(
+            None, table, row_abs, row, col_abs, col, to_table, to_row_abs, to_row, to_col_abs,
+            to_col,
+        ))))
+    }
+
+    /// Position of the closing ']', skipping quoted sheet names and
+    /// the offsets in R1C1 references.
+    fn find_close_bracket(&self) -> Option<usize> {
+        let mut quoted = false;
+        let mut offset = false;
+        for (i, c) in self.rest().char_indices().skip(1) {
+            match c {
+                '\'' => quoted = !quoted,
+                '[' if !quoted && self.syntax.anchor.is_some() => offset = true,
+                ']' if !quoted && offset => offset = false,
+                ']' if !quoted => return Some(self.pos + i),
+                _ => {}
+            }
+        }
+        None
+    }
+}
+
+/// Splits a leading 'iri'# from the reference.
+fn split_iri(content: &str) -> Option<(Option<String>, &str)> {
+    if let Some(quoted) = content.strip_prefix('\'') {
+        let (name, len) = unquote(quoted)?;
+        let rest = &quoted[len..];
+        if let Some(rest) = rest.strip_prefix('#') {
+            return Some((Some(name), rest));
+        }
+    }
+    Some((None, content))
+}
+
+/// Reads a quoted name up to the closing quote, with '' as escaped quote.
+/// Returns the name and the consumed length including the closing quote.
+fn unquote(s: &str) -> Option<(String, usize)> {
+    let mut name = String::new();
+    let mut it = s.char_indices().peekable();
+    while let Some((i, c)) = it.next() {
+        if c == '\'' {
+            if let Some((_, '\'')) = it.peek() {
+                it.next();
+                name.push('\'');
+            } else {
+                return Some((name, i + 1));
+            }
+        } else {
+            name.push(c);
+        }
+    }
+    None
+}
+
+/// Splits at the first separator outside of quotes.
+fn split_unquoted(s: &str, sep: char) -> Option<(&str, &str)> {
+    let mut quoted = false;
+    for (i, c) in s.char_indices() {
+        match c {
+            '\'' => quoted = !quoted,
+            c if c == sep && !quoted => return Some((&s[..i], &s[i + 1..])),
+            _ => {}
+        }
+    }
+    None
+}
+
+fn is_name_char(c: Option<char>) -> bool {
+    matches!(c, Some(c) if c.is_alphanumeric() || c == '_' || c == '.' || c == '(')
+}
+
+type A1 = (bool, u32, bool, u32);
+
+/// Length of [$]col[$]row.
+fn a1_len(s: &str) -> Option<usize> {
+    let b = s.as_bytes();
+    let mut i = 0;
+    if b.first() == Some(&b'$') {
+        i += 1;
+    }
+    let letters = s[i..]
+        .find(|c: char| !c.is_ascii_alphabetic())
+        .unwrap_or(s.len() - i);
+    if letters == 0 || letters > 3 {
+        return None;
+    }
+    i += letters;
+    if b.get(i) == Some(&b'$') {
+        i += 1;
+    }
+    let digits = s[i..]
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(s.len() - i);
+    if digits == 0 {
+        return None;
+    }
+    Some(i + digits)
+}
+
+/// Parses [$]col[$]row.
+fn parse_a1(s: &str) -> Option<A1> {
+    let (_, row_abs, row, col_abs, col) = parse_cell(&format!(".{}", s))?;
+    Some((row_abs, row, col_abs, col))
+}
+
+/// Excel sheet prefix Sheet1! or 'Sheet 1:Sheet 3'!.
+/// Returns the tables and the length including the '!'.
+fn excel_sheet(s: &str) -> Option<(String, Option<String>, usize)> {
+    let (names, len) = if let Some(quoted) = s.strip_prefix('\'') {
+        let (names, len) = unquote(quoted)?;
+        (names, len + 1)
+    } else {
+        let plain = |s: &str| {
+            s.find(|c: char| !(c.is_alphanumeric() || c == '_' || c == '.'))
+                .unwrap_or(s.len())
+        };
+        let mut len = plain(s);
+        if s[len..].starts_with(':') {
+            len += 1 + plain(&s[len + 1..]);
+        }
+        (s[..len].to_string(), len)
+    };
+    if names.is_empty() || !s[len..].starts_with('!') {
+        return None;
+    }
+    match names.split_once(':') {
+        Some((table, to_table)) => Some((table.to_string(), Some(to_table.to_string()), len + 1)),
+        None => Some((names, None, len + 1)),
+    }
+}
+
+/// Whole columns A:B or rows 1:3 as cell ranges.
+fn excel_lines(s: &str) -> Option<(A1, A1, usize)> {
+    fn part(s: &str, digits: bool) -> Option<(bool, &str, usize)> {
+        let abs = s.starts_with('$');
+        let v = if abs { &s[1..] } else { s };
+        let len = v
+            .find(|c: char| {
+                if digits {
+                    !c.is_ascii_digit()
+                } else {
+                    !c.is_ascii_alphabetic()
+                }
+            })
+            .unwrap_or(v.len());
+        if len == 0 {
+            None
+        } else {
+            Some((abs, &v[..len], len + abs as usize))
+        }
+    }
+    for digits in [false, true] {
+        let Some((abs, from, from_len)) = part(s, digits) else {
+            continue;
+        };
+        let Some(rest) = s[from_len..].strip_prefix(':') else {
+            continue;
+        };
+        let Some((to_abs, to, to_len)) = part(rest, digits) else {
+            continue;
+        };
+        let len = from_len + 1 + to_len;
+        return if digits {
+            let (_, _, row, _, _) = parse_cell(&format!(".A{}", from))?;
+            let (_, _, to_row, _, _) = parse_cell(&format!(".A{}", to))?;
+            Some((
+                (abs, row, true, 0),
+                (to_abs, to_row, true, MAX_COLS - 1),
+                len,
+            ))
+        } else {
+            let (_, _, _, _, col) = parse_cell(&format!(".{}1", from))?;
+            let (_, _, _, _, to_col) = parse_cell(&format!(".{}1", to))?;
+            Some((
+                (true, 0, abs, col),
+                (true, MAX_ROWS - 1, to_abs, to_col),
+                len,
+            ))
+        };
+    }
+    None
+}
+
+/// Length of a cell in UI notation, [$][table.][$]col[$]row.
+fn calc_cell_len(s: &str) -> Option<usize> {
+    let b = s.as_bytes();
+    let mut i = 0;
+    if b.first() == Some(&b'$') {
+        i += 1;
+    }
+    if b.get(i) == Some(&b'\'') {
+        let (_, len) = unquote(&s[i + 1..])?;
+        i += 1 + len;
+        if b.get(i) != Some(&b'.') {
+            return None;
+        }
+        i += 1;
+    } else {
+        let len = s[i..]
+            .find(|c: char| !(c.is_alphanumeric() || c == '_'))
+            .unwrap_or(s.len() - i);
+        if len > 0 && b.get(i + len) == Some(&b'.') {
+            i += len + 1;
+        }
+    }
+    Some(i + a1_len(&s[i..])?)
+}
+
+/// Parses a cell in UI notation, where the table is optional.
+fn parse_calc_cell(s: &str) -> Option<(Option<String>, bool, u32, bool, u32)> {
+    if split_unquoted(s, '.').is_some() {
+        parse_cell(s)
+    } else {
+        parse_cell(&format!(".{}", s))
+    }
+}
+
+/// Splits [$]table. from the cell, the table may be empty.
+fn split_table(s: &str) -> Option<(Option<String>, &str)> {
+    let s = s.trim();
+    let s = s.strip_prefix('$').unwrap_or(s);
+    if let Some(quoted) = s.strip_prefix('\'') {
+        let (name, len) = unquote(quoted)?;
+        Some((Some(name), quoted[len..].strip_prefix('.')?))
+    } else {
+        let (table, cell) = s.split_once('.')?;
+        if table.is_empty() {
+            Some((None, cell))
+        } else {
+            Some((Some(table.to_string()), cell))
+        }
+    }
+}
+
+/// Parses [$]table.[$]col[$]row with an optional table.
+fn parse_cell(s: &str) -> Option<(Option<String>, bool, u32, bool, u32)> {
+    let (table, cell) = split_table(s)?;
+
+    let (col_abs, cell) = match cell.strip_prefix('$') {
+        Some(cell) => (true, cell),
+        None => (false, cell),
+    };
+    let col_len = cell
+        .find(|c: char| !c.is_ascii_alphabetic())
+        .unwrap_or(cell.len());
+    if col_len == 0 {
+        return None;
+    }
+    let mut col = 0u32;
+    for c in cell[..col_len].chars() {
+        col = col
+            .checked_mul(26)?
+            .checked_add(c.to_ascii_uppercase() as u32 - 'A' as u32 + 1)?;
+    }
+    let cell = &cell[col_len..];
+    let (row_abs, cell) = match cell.strip_prefix('$') {
+        Some(cell) => (true, cell),
+        None => (false, cell),
+    };
+    if cell.is_empty() || !cell.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    let row: u32 = cell.parse().ok()?;
+    if row == 0 {
+        return None;
+    }
+    Some((table, row_abs, row - 1, col_abs, col - 1))
+}
+
+/// Parses table.R1C1, R[-1]C[2] or RC with an optional table. Relative parts
+/// are offsets to the anchor.
+fn parse_r1c1_cell(
+    s: &str,
+    anchor_row: u32,
+    anchor_col: u32,
+) -> Option<(Option<String>, bool, u32, bool, u32)> {
+    fn part(s: &str, c: char, anchor: u32) -> Option<(bool, u32, &str)> {
+        let s = s.strip_prefix(c)?;
+        if let Some(s) = s.strip_prefix('[') {
+            let (offset, s) = s.split_once(']')?;
+            let v = anchor.checked_add_signed(offset.parse().ok()?)?;
+            Some((false, v, s))
+        } else {
+            let len = s.find(|c: char| !c.is_ascii_digit()).unwrap_or(s.len());
+            if len == 0 {
+                Some((false, anchor, s))
+            } else {
+                let v: u32 = s[..len].parse().ok()?;
+                Some((true, v.checked_sub(1)?, &s[len..]))
+            }
+        }
+    }
+    let (table, cell) = split_table(s)?;
+    let (row_abs, row, cell) = part(cell, 'R', anchor_row)?;
+    let (col_abs, col, cell) = part(cell, 'C', anchor_col)?;
+    if !cell.is_empty() {
+        return None;
+    }
+    Some((table, row_abs, row, col_abs, col))
+}
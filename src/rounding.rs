@@ -18,25 +18,25 @@ impl Param for RoundingMode {
 ///  Round a number N up to the nearest multiple of the second parameter, significance.
 #[inline]
 pub fn ceiling(n: impl Number) -> FNumber {
-    FNumber(func1("CEILIING", &n))
+    FNumber(func1("CEILING", &n))
 }
 
 ///  Round a number N up to the nearest multiple of the second parameter, significance.
 #[inline]
 pub fn ceiling2(n: impl Number, significance: impl Number) -> FNumber {
-    FNumber(func2("CEILIING", &n, &significance))
+    FNumber(func2("CEILING", &n, &significance))
 }
 
 ///  Round a number N up to the nearest multiple of the second parameter, significance.
 #[inline]
 pub fn ceiling_mode(n: impl Number, mode: RoundingMode) -> FNumber {
-    FNumber(func3("CEILIING", &n, &(), &mode.as_param()))
+    FNumber(func3("CEILING", &n, &(), &mode.as_param()))
 }
 
 ///  Round a number N up to the nearest multiple of the second parameter, significance.
 #[inline]
 pub fn ceiling2_mode(n: impl Number, significance: impl Number, mode: RoundingMode) -> FNumber {
-    FNumber(func3("CEILIING", &n, &significance, &mode.as_param()))
+    FNumber(func3("CEILING", &n, &significance, &mode.as_param()))
 }
 
 /// Rounds a number down to the nearest integer
@@ -1,8 +1,66 @@
 use crate::{
-    create_param, func, func1, func2, func3, func4, func5, param_assume_init, Any, Array,
-    Criterion, FNumber, Logical, Number, Reference, Sequence,
+    func, func1, func2, func3, func4, func5, Any, Array, Criterion, FArray, FNumber, Logical,
+    Number, Param, Reference, Sequence,
 };
 
+/// Selects the probability density function or the cumulative distribution function
+/// of a distribution.
+pub enum DistMode {
+    /// Probability density function.
+    Density,
+    /// Cumulative distribution function.
+    Cumulative,
+}
+impl Param for DistMode {
+    type ParamType<'a> = bool;
+
+    fn as_param(&self) -> Self::ParamType<'_> {
+        match self {
+            DistMode::Density => false,
+            DistMode::Cumulative => true,
+        }
+    }
+}
+
+/// Kind of t-test for TTEST().
+pub enum TTestType {
+    /// Paired samples.
+    Paired,
+    /// Two samples with equal variance.
+    Homoscedastic,
+    /// Two samples with unequal variance.
+    Heteroscedastic,
+}
+impl Param for TTestType {
+    type ParamType<'a> = u32;
+
+    fn as_param(&self) -> Self::ParamType<'_> {
+        match self {
+            TTestType::Paired => 1,
+            TTestType::Homoscedastic => 2,
+            TTestType::Heteroscedastic => 3,
+        }
+    }
+}
+
+/// Number of tails for the t-distribution and the tests.
+pub enum Tails {
+    /// One-tailed distribution.
+    One,
+    /// Two-tailed distribution.
+    Two,
+}
+impl Param for Tails {
+    type ParamType<'a> = u32;
+
+    fn as_param(&self) -> Self::ParamType<'_> {
+        match self {
+            Tails::One => 1,
+            Tails::Two => 2,
+        }
+    }
+}
+
 /// Calculates the average of the absolute deviations of the values in list
 #[inline]
 pub fn avedev(n: impl Sequence) -> FNumber {
@@ -43,15 +101,14 @@ pub fn averageifs(
     range: impl Reference,
     criterion: &[(impl Reference, impl Criterion)],
 ) -> FNumber {
-    let mut param = create_param(criterion.len() * 2 + 1);
-    param[0].write(&range);
-    for (i, (r, c)) in criterion.iter().enumerate() {
-        param[1 + 2 * i].write(r);
-        param[1 + 2 * i + 1].write(c);
+    let mut param: Vec<&dyn Any> = Vec::with_capacity(criterion.len() * 2 + 1);
+    param.push(&range);
+    for (r, c) in criterion {
+        param.push(r);
+        param.push(c);
     }
-    let param = unsafe { param_assume_init(param) };
 
-    FNumber(func("AVERAGEIFS", param.as_ref()))
+    FNumber(func("AVERAGEIFS", &param))
 }
 
 /// returns the value of the probability density function or the cumulative distribution
@@ -91,9 +148,12 @@ pub fn betadist_cu(
     beta: impl Number,
     a: impl Number,
     b: impl Number,
-    cumulative: impl Logical,
+    mode: DistMode,
 ) -> FNumber {
-    FNumber(func("BETADIST", &[&x, &alpha, &beta, &a, &b, &cumulative]))
+    FNumber(func(
+        "BETADIST",
+        &[&x, &alpha, &beta, &a, &b, &mode.as_param()],
+    ))
 }
 
 /// returns the inverse of BETADIST(x;α;β;A;B;TRUE()).
@@ -138,13 +198,8 @@ pub fn binom_dist_range2(
 }
 
 /// Returns the binomial distribution.
-pub fn binomdist(
-    s: impl Number,
-    n: impl Number,
-    p: impl Number,
-    cumulative: impl Logical,
-) -> FNumber {
-    FNumber(func4("BINOMDIST", &s, &n, &p, &cumulative))
+pub fn binomdist(s: impl Number, n: impl Number, p: impl Number, mode: DistMode) -> FNumber {
+    FNumber(func4("BINOMDIST", &s, &n, &p, &mode.as_param()))
 }
 
 /// returns the right-tail probability for the χ2-distribution
@@ -160,12 +215,13 @@ pub fn chisqdist(x: impl Number, degrees_of_freedom: impl Number) -> FNumber {
 
 /// returns the value of the probability density function or the cumulative distribution
 /// function for the χ2-distribution.
-pub fn chisqdist_cu(
-    x: impl Number,
-    degrees_of_freedom: impl Number,
-    cumulative: impl Logical,
-) -> FNumber {
-    FNumber(func3("CHISQDIST", &x, &degrees_of_freedom, &cumulative))
+pub fn chisqdist_cu(x: impl Number, degrees_of_freedom: impl Number, mode: DistMode) -> FNumber {
+    FNumber(func3(
+        "CHISQDIST",
+        &x,
+        &degrees_of_freedom,
+        &mode.as_param(),
+    ))
 }
 
 /// returns the inverse of LEGACY.CHIDIST(x; DegreesOfFreedom)
@@ -183,11 +239,31 @@ pub fn legacy_chitest(a: impl Array, e: impl Array) -> FNumber {
     FNumber(func2("LEGACY.CHITEST", &a, &e))
 }
 
+/// Returns the Chi square goodness-for-fit test.
+pub fn chisq_test(a: impl Array, e: impl Array) -> FNumber {
+    FNumber(func2("COM.MICROSOFT.CHISQ.TEST", &a, &e))
+}
+
 /// Returns the confidence interval for a population mean.
 pub fn confidence(alpha: impl Number, stddev: impl Number, size: impl Number) -> FNumber {
     FNumber(func3("CONFIDENCE", &alpha, &stddev, &size))
 }
 
+/// Returns the confidence interval for a population mean using the normal distribution.
+pub fn confidence_norm(alpha: impl Number, stddev: impl Number, size: impl Number) -> FNumber {
+    FNumber(func3(
+        "COM.MICROSOFT.CONFIDENCE.NORM",
+        &alpha,
+        &stddev,
+        &size,
+    ))
+}
+
+/// Returns the confidence interval for a population mean using the t-distribution.
+pub fn confidence_t(alpha: impl Number, stddev: impl Number, size: impl Number) -> FNumber {
+    FNumber(func3("COM.MICROSOFT.CONFIDENCE.T", &alpha, &stddev, &size))
+}
+
 /// Calculates the correlation coefficient of values in N1 and N2
 pub fn correl(n1: impl Array, n2: impl Array) -> FNumber {
     FNumber(func2("CORREL", &n1, &n2))
@@ -217,8 +293,8 @@ pub fn expondist(x: impl Number, lambda: impl Number) -> FNumber {
 
 ///  returns the value of the probability density function or the cumulative distribution
 /// function for the exponential distribution.
-pub fn expondist_cu(x: impl Number, lambda: impl Number, cumulative: impl Logical) -> FNumber {
-    FNumber(func3("EXPONDIST", &x, &lambda, &cumulative))
+pub fn expondist_cu(x: impl Number, lambda: impl Number, mode: DistMode) -> FNumber {
+    FNumber(func3("EXPONDIST", &x, &lambda, &mode.as_param()))
 }
 
 ///  returns the value of the probability density function or the cumulative distribution
@@ -229,13 +305,8 @@ pub fn fdist(x: impl Number, r1: impl Number, r2: impl Number) -> FNumber {
 
 ///  returns the value of the probability density function or the cumulative distribution
 /// function for the F-distribution.
-pub fn fdist_cu(
-    x: impl Number,
-    r1: impl Number,
-    r2: impl Number,
-    cumulative: impl Logical,
-) -> FNumber {
-    FNumber(func4("FDIST", &x, &r1, &r2, &cumulative))
+pub fn fdist_cu(x: impl Number, r1: impl Number, r2: impl Number, mode: DistMode) -> FNumber {
+    FNumber(func4("FDIST", &x, &r1, &r2, &mode.as_param()))
 }
 
 /// returns the area of the right tail of the probability density function for the F-distribution.
@@ -268,5 +339,583 @@ pub fn forecast(value: impl Number, data_y: impl Array, data_x: impl Array) -> F
     FNumber(func3("FORECAST", &value, &data_y, &data_x))
 }
 
+/// Categorizes values into bins and counts the items in each bin.
+pub fn frequency(data: impl Array, bins: impl Array) -> FArray {
+    FArray(func2("FREQUENCY", &data, &bins))
+}
+
+/// Returns the two-tailed probability that the variances of two data sets are not
+/// significantly different.
+pub fn ftest(data1: impl Array, data2: impl Array) -> FNumber {
+    FNumber(func2("FTEST", &data1, &data2))
+}
+
+/// Returns the value of the probability density function or the cumulative distribution
+/// function for the Gamma distribution.
+pub fn gammadist(x: impl Number, alpha: impl Number, beta: impl Number) -> FNumber {
+    FNumber(func3("GAMMADIST", &x, &alpha, &beta))
+}
+
+/// Returns the value of the probability density function or the cumulative distribution
+/// function for the Gamma distribution.
+pub fn gammadist_cu(
+    x: impl Number,
+    alpha: impl Number,
+    beta: impl Number,
+    mode: DistMode,
+) -> FNumber {
+    FNumber(func4("GAMMADIST", &x, &alpha, &beta, &mode.as_param()))
+}
+
+/// Returns the inverse of GAMMADIST(x;α;β;TRUE()).
+pub fn gammainv(p: impl Number, alpha: impl Number, beta: impl Number) -> FNumber {
+    FNumber(func3("GAMMAINV", &p, &alpha, &beta))
+}
+
+/// Returns the value of the standard normal cumulative distribution minus 0.5.
+pub fn gauss(x: impl Number) -> FNumber {
+    FNumber(func1("GAUSS", &x))
+}
+
+/// Calculates the points of an exponential trend.
+pub fn growth(data_y: impl Array) -> FArray {
+    FArray(func1("GROWTH", &data_y))
+}
+
+/// Calculates the points of an exponential trend.
+pub fn growth_x(data_y: impl Array, data_x: impl Array) -> FArray {
+    FArray(func2("GROWTH", &data_y, &data_x))
+}
+
+/// Calculates the points of an exponential trend for new x values.
+pub fn growth_new(data_y: impl Array, data_x: impl Array, new_x: impl Array) -> FArray {
+    FArray(func3("GROWTH", &data_y, &data_x, &new_x))
+}
+
+/// Calculates the points of an exponential trend for new x values.
+/// With const FALSE() the trend is forced through y=1.
+pub fn growth_const(
+    data_y: impl Array,
+    data_x: impl Array,
+    new_x: impl Array,
+    const_: impl Logical,
+) -> FArray {
+    FArray(func4("GROWTH", &data_y, &data_x, &new_x, &const_))
+}
+
+/// Returns the geometric mean of a sequence.
+pub fn geomean(n: impl Sequence) -> FNumber {
+    FNumber(func1("GEOMEAN", &n))
+}
+
+/// Returns the harmonic mean of a sequence.
+pub fn harmean(n: impl Sequence) -> FNumber {
+    FNumber(func1("HARMEAN", &n))
+}
+
+/// Returns the hypergeometric distribution.
+pub fn hypgeomdist(x: impl Number, n: impl Number, m: impl Number, big_n: impl Number) -> FNumber {
+    FNumber(func4("HYPGEOMDIST", &x, &n, &m, &big_n))
+}
+
+/// Returns the hypergeometric distribution.
+pub fn hypgeomdist_cu(
+    x: impl Number,
+    n: impl Number,
+    m: impl Number,
+    big_n: impl Number,
+    mode: DistMode,
+) -> FNumber {
+    FNumber(func5("HYPGEOMDIST", &x, &n, &m, &big_n, &mode.as_param()))
+}
+
+/// Returns the y-intercept of the linear regression line.
+pub fn intercept(data_y: impl Array, data_x: impl Array) -> FNumber {
+    FNumber(func2("INTERCEPT", &data_y, &data_x))
+}
+
+/// Returns the kurtosis of a data set.
+pub fn kurt(n: impl Sequence) -> FNumber {
+    FNumber(func1("KURT", &n))
+}
+
+/// Finds the nth largest value in a list.
+pub fn large(data: impl Array, n: impl Number) -> FNumber {
+    FNumber(func2("LARGE", &data, &n))
+}
+
+/// Returns the parameters of a linear regression line.
+pub fn linest(data_y: impl Array) -> FArray {
+    FArray(func1("LINEST", &data_y))
+}
+
+/// Returns the parameters of a linear regression line.
+pub fn linest_x(data_y: impl Array, data_x: impl Array) -> FArray {
+    FArray(func2("LINEST", &data_y, &data_x))
+}
+
+/// Returns the parameters of a linear regression line. With const FALSE() the line is
+/// forced through the origin, with stats TRUE() the regression statistics are added.
+pub fn linest_stats(
+    data_y: impl Array,
+    data_x: impl Array,
+    const_: impl Logical,
+    stats: impl Logical,
+) -> FArray {
+    FArray(func4("LINEST", &data_y, &data_x, &const_, &stats))
+}
+
+/// Selects one value of the statistics array returned by LINEST() and LOGEST().
+pub enum LinestStat {
+    /// Coefficient of the k-th x variable, starting with 1.
+    Coefficient(u32),
+    /// The constant b.
+    Constant,
+    /// Standard error of the k-th coefficient, starting with 1.
+    StdError(u32),
+    /// Standard error of the constant b.
+    ConstantStdError,
+    /// Coefficient of determination r².
+    RSquared,
+    /// Standard error of the y estimate.
+    StdErrorY,
+    /// F statistic.
+    FStatistic,
+    /// Degrees of freedom.
+    DegreesOfFreedom,
+    /// Regression sum of squares.
+    SsReg,
+    /// Residual sum of squares.
+    SsResid,
+}
+
+impl LinestStat {
+    /// Row and column in the statistics array. The coefficients are returned in reverse
+    /// order of the x variables, so their number is needed.
+    ///
+    /// None if k is not in 1..=x_vars.
+    fn position(&self, x_vars: u32) -> Option<(u32, u32)> {
+        let pos = match *self {
+            LinestStat::Coefficient(k) | LinestStat::StdError(k) if !(1..=x_vars).contains(&k) => {
+                return None
+            }
+            LinestStat::Coefficient(k) => (1, x_vars + 1 - k),
+            LinestStat::Constant => (1, x_vars + 1),
+            LinestStat::StdError(k) => (2, x_vars + 1 - k),
+            LinestStat::ConstantStdError => (2, x_vars + 1),
+            LinestStat::RSquared => (3, 1),
+            LinestStat::StdErrorY => (3, 2),
+            LinestStat::FStatistic => (4, 1),
+            LinestStat::DegreesOfFreedom => (4, 2),
+            LinestStat::SsReg => (5, 1),
+            LinestStat::SsResid => (5, 2),
+        };
+        Some(pos)
+    }
+}
+
+/// Returns one value of the linear regression statistics.
+/// x_vars is the number of x variables in data_x.
+///
+/// Compiles to INDEX(LINEST(data_y;data_x;TRUE();TRUE());row;col).
+/// Returns None if the k of a coefficient or standard error is not in 1..=x_vars.
+pub fn linest_value(
+    data_y: impl Array,
+    data_x: impl Array,
+    x_vars: u32,
+    stat: LinestStat,
+) -> Option<FNumber> {
+    let (row, col) = stat.position(x_vars)?;
+    let linest = linest_stats(data_y, data_x, true, true);
+    Some(FNumber(func3("INDEX", &linest, &row, &col)))
+}
+
+/// Returns the parameters of an exponential regression curve.
+pub fn logest(data_y: impl Array) -> FArray {
+    FArray(func1("LOGEST", &data_y))
+}
+
+/// Returns the parameters of an exponential regression curve.
+pub fn logest_x(data_y: impl Array, data_x: impl Array) -> FArray {
+    FArray(func2("LOGEST", &data_y, &data_x))
+}
+
+/// Returns the parameters of an exponential regression curve. With const FALSE() the
+/// constant is forced to 1, with stats TRUE() the regression statistics are added.
+pub fn logest_stats(
+    data_y: impl Array,
+    data_x: impl Array,
+    const_: impl Logical,
+    stats: impl Logical,
+) -> FArray {
+    FArray(func4("LOGEST", &data_y, &data_x, &const_, &stats))
+}
+
+/// Returns one value of the exponential regression statistics.
+/// x_vars is the number of x variables in data_x.
 ///
-pub fn frequency(data: impl Sequence, bins: impl Sequence) -> FArray {}
+/// Compiles to INDEX(LOGEST(data_y;data_x;TRUE();TRUE());row;col).
+/// Returns None if the k of a coefficient or standard error is not in 1..=x_vars.
+pub fn logest_value(
+    data_y: impl Array,
+    data_x: impl Array,
+    x_vars: u32,
+    stat: LinestStat,
+) -> Option<FNumber> {
+    let (row, col) = stat.position(x_vars)?;
+    let logest = logest_stats(data_y, data_x, true, true);
+    Some(FNumber(func3("INDEX", &logest, &row, &col)))
+}
+
+/// Returns the inverse of LOGNORMDIST(x;μ;σ;TRUE()).
+pub fn loginv(p: impl Number) -> FNumber {
+    FNumber(func1("LOGINV", &p))
+}
+
+/// Returns the inverse of LOGNORMDIST(x;μ;σ;TRUE()).
+pub fn loginv_param(p: impl Number, mean: impl Number, stddev: impl Number) -> FNumber {
+    FNumber(func3("LOGINV", &p, &mean, &stddev))
+}
+
+/// Returns the value of the probability density function or the cumulative distribution
+/// function for the lognormal distribution.
+pub fn lognormdist(x: impl Number) -> FNumber {
+    FNumber(func1("LOGNORMDIST", &x))
+}
+
+/// Returns the value of the probability density function or the cumulative distribution
+/// function for the lognormal distribution.
+pub fn lognormdist_param(x: impl Number, mean: impl Number, stddev: impl Number) -> FNumber {
+    FNumber(func3("LOGNORMDIST", &x, &mean, &stddev))
+}
+
+/// Returns the value of the probability density function or the cumulative distribution
+/// function for the lognormal distribution.
+pub fn lognormdist_cu(
+    x: impl Number,
+    mean: impl Number,
+    stddev: impl Number,
+    mode: DistMode,
+) -> FNumber {
+    FNumber(func4("LOGNORMDIST", &x, &mean, &stddev, &mode.as_param()))
+}
+
+/// Returns the maximum number in a sequence.
+pub fn max(n: impl Sequence) -> FNumber {
+    FNumber(func1("MAX", &n))
+}
+
+/// Returns the maximum number in a sequence, including values of type Text and Logical.
+pub fn maxa(n: impl Sequence) -> FNumber {
+    FNumber(func1("MAXA", &n))
+}
+
+/// Returns the median (middle) value in the list.
+pub fn median(n: impl Sequence) -> FNumber {
+    FNumber(func1("MEDIAN", &n))
+}
+
+/// Returns the minimum number in a sequence.
+pub fn min(n: impl Sequence) -> FNumber {
+    FNumber(func1("MIN", &n))
+}
+
+/// Returns the minimum number in a sequence, including values of type Text and Logical.
+pub fn mina(n: impl Sequence) -> FNumber {
+    FNumber(func1("MINA", &n))
+}
+
+/// Returns the most common value in a data set.
+pub fn mode(n: impl Sequence) -> FNumber {
+    FNumber(func1("MODE", &n))
+}
+
+/// Returns the negative binomial distribution.
+pub fn negbinomdist(x: impl Number, r: impl Number, p: impl Number) -> FNumber {
+    FNumber(func3("NEGBINOMDIST", &x, &r, &p))
+}
+
+/// Returns the value of the probability density function or the cumulative distribution
+/// function for the normal distribution.
+pub fn normdist(x: impl Number, mean: impl Number, stddev: impl Number) -> FNumber {
+    FNumber(func3("NORMDIST", &x, &mean, &stddev))
+}
+
+/// Returns the value of the probability density function or the cumulative distribution
+/// function for the normal distribution.
+pub fn normdist_cu(
+    x: impl Number,
+    mean: impl Number,
+    stddev: impl Number,
+    mode: DistMode,
+) -> FNumber {
+    FNumber(func4("NORMDIST", &x, &mean, &stddev, &mode.as_param()))
+}
+
+/// Returns the cumulative distribution function for the standard normal distribution.
+pub fn legacy_normsdist(x: impl Number) -> FNumber {
+    FNumber(func1("LEGACY.NORMSDIST", &x))
+}
+
+/// Returns the inverse of NORMDIST(x;μ;σ;TRUE()).
+pub fn norminv(p: impl Number, mean: impl Number, stddev: impl Number) -> FNumber {
+    FNumber(func3("NORMINV", &p, &mean, &stddev))
+}
+
+/// Returns the inverse of LEGACY.NORMSDIST(x).
+pub fn legacy_normsinv(p: impl Number) -> FNumber {
+    FNumber(func1("LEGACY.NORMSINV", &p))
+}
+
+/// Calculates the Pearson correlation coefficient of two data sets.
+pub fn pearson(data1: impl Array, data2: impl Array) -> FNumber {
+    FNumber(func2("PEARSON", &data1, &data2))
+}
+
+/// Calculates the x-th sample percentile of values in a range.
+pub fn percentile(data: impl Array, x: impl Number) -> FNumber {
+    FNumber(func2("PERCENTILE", &data, &x))
+}
+
+/// Returns the percentage rank of a value in a sample.
+pub fn percentrank(data: impl Array, x: impl Number) -> FNumber {
+    FNumber(func2("PERCENTRANK", &data, &x))
+}
+
+/// Returns the percentage rank of a value in a sample.
+pub fn percentrank_significance(
+    data: impl Array,
+    x: impl Number,
+    significance: impl Number,
+) -> FNumber {
+    FNumber(func3("PERCENTRANK", &data, &x, &significance))
+}
+
+/// Returns the value of the probability density function for the standard normal
+/// distribution.
+pub fn phi(x: impl Number) -> FNumber {
+    FNumber(func1("PHI", &x))
+}
+
+/// Returns the probability for a Poisson distribution.
+pub fn poisson(x: impl Number, lambda: impl Number) -> FNumber {
+    FNumber(func2("POISSON", &x, &lambda))
+}
+
+/// Returns the probability for a Poisson distribution.
+pub fn poisson_cu(x: impl Number, lambda: impl Number, mode: DistMode) -> FNumber {
+    FNumber(func3("POISSON", &x, &lambda, &mode.as_param()))
+}
+
+/// Quartile for QUARTILE()
+pub enum Quartile {
+    /// Minimum value.
+    Min,
+    /// First quartile, 25th percentile.
+    First,
+    /// Median, 50th percentile.
+    Median,
+    /// Third quartile, 75th percentile.
+    Third,
+    /// Maximum value.
+    Max,
+}
+impl Param for Quartile {
+    type ParamType<'a> = u32;
+
+    fn as_param(&self) -> Self::ParamType<'_> {
+        match self {
+            Quartile::Min => 0,
+            Quartile::First => 1,
+            Quartile::Median => 2,
+            Quartile::Third => 3,
+            Quartile::Max => 4,
+        }
+    }
+}
+
+/// Returns a quartile of a set of data points.
+pub fn quartile(data: impl Array, quart: Quartile) -> FNumber {
+    FNumber(func2("QUARTILE", &data, &quart.as_param()))
+}
+
+/// Sort order for RANK()
+pub enum RankOrder {
+    /// Largest value has rank 1.
+    Descending,
+    /// Smallest value has rank 1.
+    Ascending,
+}
+impl Param for RankOrder {
+    type ParamType<'a> = u32;
+
+    fn as_param(&self) -> Self::ParamType<'_> {
+        match self {
+            RankOrder::Descending => 0,
+            RankOrder::Ascending => 1,
+        }
+    }
+}
+
+/// Returns the rank of a number in a list of numbers.
+pub fn rank(value: impl Number, data: impl Array) -> FNumber {
+    FNumber(func2("RANK", &value, &data))
+}
+
+/// Returns the rank of a number in a list of numbers.
+pub fn rank_order(value: impl Number, data: impl Array, order: RankOrder) -> FNumber {
+    FNumber(func3("RANK", &value, &data, &order.as_param()))
+}
+
+/// Returns the square of the Pearson correlation coefficient.
+pub fn rsq(data_y: impl Array, data_x: impl Array) -> FNumber {
+    FNumber(func2("RSQ", &data_y, &data_x))
+}
+
+/// Estimates the skewness of a distribution using a sample set of numbers.
+pub fn skew(n: impl Sequence) -> FNumber {
+    FNumber(func1("SKEW", &n))
+}
+
+/// Calculates the skewness of a distribution using the population of a random variable.
+pub fn skewp(n: impl Sequence) -> FNumber {
+    FNumber(func1("SKEWP", &n))
+}
+
+/// Returns the slope of the linear regression line.
+pub fn slope(data_y: impl Array, data_x: impl Array) -> FNumber {
+    FNumber(func2("SLOPE", &data_y, &data_x))
+}
+
+/// Finds the nth smallest value in a list.
+pub fn small(data: impl Array, n: impl Number) -> FNumber {
+    FNumber(func2("SMALL", &data, &n))
+}
+
+/// Calculates a normalized value of a random variable.
+pub fn standardize(x: impl Number, mean: impl Number, stddev: impl Number) -> FNumber {
+    FNumber(func3("STANDARDIZE", &x, &mean, &stddev))
+}
+
+/// Compute an estimate of the standard deviation of a population based on a sample.
+pub fn stdev(n: impl Sequence) -> FNumber {
+    FNumber(func1("STDEV", &n))
+}
+
+/// Compute an estimate of the standard deviation of a population based on a sample,
+/// including values of type Text and Logical.
+pub fn stdeva(n: impl Sequence) -> FNumber {
+    FNumber(func1("STDEVA", &n))
+}
+
+/// Compute the standard deviation of the population.
+pub fn stdevp(n: impl Sequence) -> FNumber {
+    FNumber(func1("STDEVP", &n))
+}
+
+/// Compute the standard deviation of the population, including values of type Text and
+/// Logical.
+pub fn stdevpa(n: impl Sequence) -> FNumber {
+    FNumber(func1("STDEVPA", &n))
+}
+
+/// Returns the standard error of the predicted y value for each x in the regression.
+pub fn steyx(data_y: impl Array, data_x: impl Array) -> FNumber {
+    FNumber(func2("STEYX", &data_y, &data_x))
+}
+
+/// Returns the probability for the t-distribution.
+pub fn legacy_tdist(x: impl Number, degrees_of_freedom: impl Number, tails: Tails) -> FNumber {
+    FNumber(func3(
+        "LEGACY.TDIST",
+        &x,
+        &degrees_of_freedom,
+        &tails.as_param(),
+    ))
+}
+
+/// Returns the inverse of the two-tailed t-distribution.
+pub fn tinv(p: impl Number, degrees_of_freedom: impl Number) -> FNumber {
+    FNumber(func2("TINV", &p, &degrees_of_freedom))
+}
+
+/// Calculates the points of a linear trend.
+pub fn trend(data_y: impl Array) -> FArray {
+    FArray(func1("TREND", &data_y))
+}
+
+/// Calculates the points of a linear trend.
+pub fn trend_x(data_y: impl Array, data_x: impl Array) -> FArray {
+    FArray(func2("TREND", &data_y, &data_x))
+}
+
+/// Calculates the points of a linear trend for new x values.
+pub fn trend_new(data_y: impl Array, data_x: impl Array, new_x: impl Array) -> FArray {
+    FArray(func3("TREND", &data_y, &data_x, &new_x))
+}
+
+/// Calculates the points of a linear trend for new x values.
+/// With const FALSE() the trend is forced through the origin.
+pub fn trend_const(
+    data_y: impl Array,
+    data_x: impl Array,
+    new_x: impl Array,
+    const_: impl Logical,
+) -> FArray {
+    FArray(func4("TREND", &data_y, &data_x, &new_x, &const_))
+}
+
+/// Returns the mean of a data set, ignoring a proportion of high and low values.
+pub fn trimmean(data: impl Array, cutoff_fraction: impl Number) -> FNumber {
+    FNumber(func2("TRIMMEAN", &data, &cutoff_fraction))
+}
+
+/// Calculates the probability that two samples come from populations with the same mean.
+pub fn ttest(data1: impl Array, data2: impl Array, tails: Tails, kind: TTestType) -> FNumber {
+    FNumber(func4(
+        "TTEST",
+        &data1,
+        &data2,
+        &tails.as_param(),
+        &kind.as_param(),
+    ))
+}
+
+/// Compute an estimate of the variance of a population based on a sample.
+pub fn var(n: impl Sequence) -> FNumber {
+    FNumber(func1("VAR", &n))
+}
+
+/// Compute an estimate of the variance of a population based on a sample, including values
+/// of type Text and Logical.
+pub fn vara(n: impl Sequence) -> FNumber {
+    FNumber(func1("VARA", &n))
+}
+
+/// Compute the variance of the population.
+pub fn varp(n: impl Sequence) -> FNumber {
+    FNumber(func1("VARP", &n))
+}
+
+/// Compute the variance of the population, including values of type Text and Logical.
+pub fn varpa(n: impl Sequence) -> FNumber {
+    FNumber(func1("VARPA", &n))
+}
+
+/// Returns the value of the probability density function or the cumulative distribution
+/// function for the Weibull distribution.
+pub fn weibull(x: impl Number, alpha: impl Number, beta: impl Number, mode: DistMode) -> FNumber {
+    FNumber(func4("WEIBULL", &x, &alpha, &beta, &mode.as_param()))
+}
+
+/// Calculates the probability of observing a sample mean as large or larger than the mean
+/// of the given sample, using the sample standard deviation.
+pub fn ztest(data: impl Array, mean: impl Number) -> FNumber {
+    FNumber(func2("ZTEST", &data, &mean))
+}
+
+/// Calculates the probability of observing a sample mean as large or larger than the mean
+/// of the given sample, using the given population standard deviation.
+pub fn ztest_sigma(data: impl Array, mean: impl Number, sigma: impl Number) -> FNumber {
+    FNumber(func3("ZTEST", &data, &mean, &sigma))
+}
@@ -0,0 +1,304 @@
+use crate::{
+    func1, func2, func3, func4, Any, FLogical, FNumber, FText, Logical, Number, Scalar, Sequence,
+    Text,
+};
+
+/// Converts full-width to half-width ASCII and katakana characters.
+#[inline]
+pub fn asc(text: impl Text) -> FText {
+    FText(func1("ASC", &text))
+}
+
+/// Convert a number into text representing the number in Thai, including the Thai
+/// name for the currency.
+#[inline]
+pub fn bahttext(n: impl Number) -> FText {
+    FText(func1("BAHTTEXT", &n))
+}
+
+/// Return character represented by the given numeric value.
+#[inline]
+pub fn char(n: impl Number) -> FText {
+    FText(func1("CHAR", &n))
+}
+
+/// Remove all non-printable characters from the string and return the result.
+#[inline]
+pub fn clean(text: impl Text) -> FText {
+    FText(func1("CLEAN", &text))
+}
+
+/// Return numeric value corresponding to the first character of the text value.
+#[inline]
+pub fn code(text: impl Text) -> FNumber {
+    FNumber(func1("CODE", &text))
+}
+
+/// Concatenate the text strings.
+#[inline]
+pub fn concatenate(text: impl Sequence) -> FText {
+    FText(func1("CONCATENATE", &text))
+}
+
+/// Convert the parameters to Text formatted as currency.
+#[inline]
+pub fn dollar(n: impl Number) -> FText {
+    FText(func1("DOLLAR", &n))
+}
+
+/// Convert the parameters to Text formatted as currency.
+#[inline]
+pub fn dollar_decimals(n: impl Number, decimals: impl Number) -> FText {
+    FText(func2("DOLLAR", &n, &decimals))
+}
+
+/// Report if two text values are equal using a case-sensitive comparison.
+#[inline]
+pub fn exact(t1: impl Text, t2: impl Text) -> FLogical {
+    FLogical(func2("EXACT", &t1, &t2))
+}
+
+/// Return the starting position of a given text.
+#[inline]
+pub fn find(search: impl Text, text: impl Text) -> FNumber {
+    FNumber(func2("FIND", &search, &text))
+}
+
+/// Return the starting position of a given text.
+#[inline]
+pub fn find_from(search: impl Text, text: impl Text, start: impl Number) -> FNumber {
+    FNumber(func3("FIND", &search, &text, &start))
+}
+
+/// Return the starting position of a given text using byte positions.
+#[inline]
+pub fn findb(search: impl Text, text: impl Text) -> FNumber {
+    FNumber(func2("FINDB", &search, &text))
+}
+
+/// Return the starting position of a given text using byte positions.
+#[inline]
+pub fn findb_from(search: impl Text, text: impl Text, start: impl Number) -> FNumber {
+    FNumber(func3("FINDB", &search, &text, &start))
+}
+
+/// Round the number to a specified number of decimals and format the result as a text.
+#[inline]
+pub fn fixed(n: impl Number) -> FText {
+    FText(func1("FIXED", &n))
+}
+
+/// Round the number to a specified number of decimals and format the result as a text.
+#[inline]
+pub fn fixed_decimals(n: impl Number, decimals: impl Number) -> FText {
+    FText(func2("FIXED", &n, &decimals))
+}
+
+/// Round the number to a specified number of decimals and format the result as a text.
+/// Omits the thousands separators.
+#[inline]
+pub fn fixed_decimals_nosep(n: impl Number, decimals: impl Number) -> FText {
+    FText(func3("FIXED", &n, &decimals, &true))
+}
+
+/// Converts half-width to full-width ASCII and katakana characters.
+#[inline]
+pub fn jis(text: impl Text) -> FText {
+    FText(func1("JIS", &text))
+}
+
+/// Return a selected number of text characters from the left.
+#[inline]
+pub fn left(text: impl Text) -> FText {
+    FText(func1("LEFT", &text))
+}
+
+/// Return a selected number of text characters from the left.
+#[inline]
+pub fn left_len(text: impl Text, length: impl Number) -> FText {
+    FText(func2("LEFT", &text, &length))
+}
+
+/// Return a selected number of text characters from the left, counted in bytes.
+#[inline]
+pub fn leftb(text: impl Text) -> FText {
+    FText(func1("LEFTB", &text))
+}
+
+/// Return a selected number of text characters from the left, counted in bytes.
+#[inline]
+pub fn leftb_len(text: impl Text, length: impl Number) -> FText {
+    FText(func2("LEFTB", &text, &length))
+}
+
+/// Return the number of characters in a text string.
+#[inline]
+pub fn len(text: impl Text) -> FNumber {
+    FNumber(func1("LEN", &text))
+}
+
+/// Return the number of bytes in a text string.
+#[inline]
+pub fn lenb(text: impl Text) -> FNumber {
+    FNumber(func1("LENB", &text))
+}
+
+/// Return input string, but with all uppercase letters converted to lowercase letters.
+#[inline]
+pub fn lower(text: impl Text) -> FText {
+    FText(func1("LOWER", &text))
+}
+
+/// Returns extracted text, given an original text, starting position, and length.
+#[inline]
+pub fn mid(text: impl Text, start: impl Number, length: impl Number) -> FText {
+    FText(func3("MID", &text, &start, &length))
+}
+
+/// Returns extracted text, given an original text, starting position, and length in bytes.
+#[inline]
+pub fn midb(text: impl Text, start: impl Number, length: impl Number) -> FText {
+    FText(func3("MIDB", &text, &start, &length))
+}
+
+/// Return the input string with the first letter of each word converted to an uppercase
+/// letter and the rest of the letters in the word converted to lowercase.
+#[inline]
+pub fn proper(text: impl Text) -> FText {
+    FText(func1("PROPER", &text))
+}
+
+/// Returns text where an old text is substituted with a new text.
+#[inline]
+pub fn replace(text: impl Text, start: impl Number, len: impl Number, new: impl Text) -> FText {
+    FText(func4("REPLACE", &text, &start, &len, &new))
+}
+
+/// Returns text where an old text is substituted with a new text, positions counted in
+/// bytes.
+#[inline]
+pub fn replaceb(text: impl Text, start: impl Number, len: impl Number, new: impl Text) -> FText {
+    FText(func4("REPLACEB", &text, &start, &len, &new))
+}
+
+/// Return text repeated Count times.
+#[inline]
+pub fn rept(text: impl Text, count: impl Number) -> FText {
+    FText(func2("REPT", &text, &count))
+}
+
+/// Return a selected number of text characters from the right.
+#[inline]
+pub fn right(text: impl Text) -> FText {
+    FText(func1("RIGHT", &text))
+}
+
+/// Return a selected number of text characters from the right.
+#[inline]
+pub fn right_len(text: impl Text, length: impl Number) -> FText {
+    FText(func2("RIGHT", &text, &length))
+}
+
+/// Return a selected number of text characters from the right, counted in bytes.
+#[inline]
+pub fn rightb(text: impl Text) -> FText {
+    FText(func1("RIGHTB", &text))
+}
+
+/// Return a selected number of text characters from the right, counted in bytes.
+#[inline]
+pub fn rightb_len(text: impl Text, length: impl Number) -> FText {
+    FText(func2("RIGHTB", &text, &length))
+}
+
+/// Return the starting position of a given text, case-insensitive and with wildcards
+/// or regular expressions.
+#[inline]
+pub fn search(search: impl Text, text: impl Text) -> FNumber {
+    FNumber(func2("SEARCH", &search, &text))
+}
+
+/// Return the starting position of a given text, case-insensitive and with wildcards
+/// or regular expressions.
+#[inline]
+pub fn search_from(search: impl Text, text: impl Text, start: impl Number) -> FNumber {
+    FNumber(func3("SEARCH", &search, &text, &start))
+}
+
+/// Return the starting position of a given text using byte positions.
+#[inline]
+pub fn searchb(search: impl Text, text: impl Text) -> FNumber {
+    FNumber(func2("SEARCHB", &search, &text))
+}
+
+/// Return the starting position of a given text using byte positions.
+#[inline]
+pub fn searchb_from(search: impl Text, text: impl Text, start: impl Number) -> FNumber {
+    FNumber(func3("SEARCHB", &search, &text, &start))
+}
+
+/// Returns text where an old text is substituted with a new text.
+#[inline]
+pub fn substitute(text: impl Text, old: impl Text, new: impl Text) -> FText {
+    FText(func3("SUBSTITUTE", &text, &old, &new))
+}
+
+/// Returns text where the n-th occurrence of an old text is substituted with a new text.
+#[inline]
+pub fn substitute_nth(
+    text: impl Text,
+    old: impl Text,
+    new: impl Text,
+    which: impl Number,
+) -> FText {
+    FText(func4("SUBSTITUTE", &text, &old, &new, &which))
+}
+
+/// Return the text (if text), else return 0-length Text value.
+#[inline]
+pub fn t(value: impl Any) -> FText {
+    FText(func1("T", &value))
+}
+
+/// Change the number into text, using the given format code.
+/// See FormatCode for a typed format code.
+#[inline]
+pub fn text(value: impl Scalar, format: impl Text) -> FText {
+    FText(func2("TEXT", &value, &format))
+}
+
+/// Concatenates the text strings, separated by a delimiter.
+#[inline]
+pub fn textjoin(delimiter: impl Text, ignore_empty: impl Logical, text: impl Sequence) -> FText {
+    FText(func3(
+        "COM.MICROSOFT.TEXTJOIN",
+        &delimiter,
+        &ignore_empty,
+        &text,
+    ))
+}
+
+/// Remove leading and trailing spaces, and replace all internal multiple spaces with a
+/// single space.
+#[inline]
+pub fn trim(text: impl Text) -> FText {
+    FText(func1("TRIM", &text))
+}
+
+/// Return the character represented by the given numeric value.
+#[inline]
+pub fn unichar(n: impl Number) -> FText {
+    FText(func1("UNICHAR", &n))
+}
+
+/// Return the Unicode code point corresponding to the first character of the text value.
+#[inline]
+pub fn unicode(text: impl Text) -> FNumber {
+    FNumber(func1("UNICODE", &text))
+}
+
+/// Return input string, but with all lowercase letters converted to uppercase letters.
+#[inline]
+pub fn upper(text: impl Text) -> FText {
+    FText(func1("UPPER", &text))
+}
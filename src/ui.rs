@@ -0,0 +1,581 @@
+//!
+//! Formulas as they are shown and entered in the LibreOffice user interface.
+//!
+//! The UI syntax uses localized function names, the decimal and argument
+//! separators of the locale and references like A1 or $Sheet1.A1:B3.
+//! These functions convert between the UI syntax and OpenFormula.
+//!
+//! Supported locales are English (en-US) and German (de-DE).
+//!
+
+use crate::parse::{parse_with, RefNotation, Syntax};
+use crate::{function_info, functions, parse_expr, quoted_name, Any, Expr, ParseError};
+use spreadsheet_ods::{CellRange, CellRef};
+use std::fmt;
+use std::fmt::Write;
+
+/// Separators and function names of a UI locale.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UiSyntax {
+    /// Decimal separator.
+    pub decimal: char,
+    /// Separator for function arguments.
+    pub arg_sep: char,
+    /// Separator for the columns of an inline array.
+    pub array_col: char,
+    /// Separator for the rows of an inline array.
+    pub array_row: char,
+    /// OpenFormula name and localized name. Functions that are not
+    /// listed use their name without namespace prefix.
+    pub names: &'static [(&'static str, &'static str)],
+}
+
+impl UiSyntax {
+    /// English UI, as in en-US.
+    pub const EN_US: UiSyntax = UiSyntax {
+        decimal: '.',
+        arg_sep: ',',
+        array_col: ',',
+        array_row: ';',
+        names: EN_NAMES,
+    };
+
+    /// German UI, as in de-DE.
+    ///
+    /// DOLLAR is not translated and keeps its English name.
+    pub const DE_DE: UiSyntax = UiSyntax {
+        decimal: ',',
+        arg_sep: ';',
+        array_col: '.',
+        array_row: ';',
+        names: DE_NAMES,
+    };
+
+    /// Syntax for a language tag like "de-AT" or "en_GB".
+    /// Only the language is used, None for languages other than "en" and "de".
+    pub fn for_locale(tag: &str) -> Option<UiSyntax> {
+        let lang = tag.split(['-', '_']).next().unwrap_or(tag);
+        if lang.eq_ignore_ascii_case("en") {
+            Some(Self::EN_US)
+        } else if lang.eq_ignore_ascii_case("de") {
+            Some(Self::DE_DE)
+        } else {
+            None
+        }
+    }
+
+    /// Localized name for an OpenFormula function.
+    pub fn local_name<'a>(&self, name: &'a str) -> &'a str {
+        if let Some((_, local)) = self.names.iter().find(|(of, _)| *of == name) {
+            local
+        } else if let Some(info) = function_info(name) {
+            // The namespace prefix is not shown.
+            &name[name.len() - info.local_name().len()..]
+        } else {
+            name
+        }
+    }
+
+    /// OpenFormula name for a localized function name. Case insensitive.
+    /// Unknown names are returned in uppercase.
+    pub fn canonical_name(&self, local: &str) -> String {
+        let upper = local.to_uppercase();
+        if let Some((of, _)) = self.names.iter().find(|(_, v)| *v == upper) {
+            return of.to_string();
+        }
+        if function_info(&upper).is_some() {
+            return upper;
+        }
+        match functions().iter().find(|v| v.local_name() == upper) {
+            Some(info) => info.name.to_string(),
+            None => upper,
+        }
+    }
+}
+
+/// English names that differ from OpenFormula.
+const EN_NAMES: &[(&str, &str)] = &[
+    ("FDIST", "F.DIST"),
+    ("FINV", "F.INV"),
+    ("LEGACY.CHIDIST", "CHIDIST"),
+    ("LEGACY.CHIINV", "CHIINV"),
+    ("LEGACY.CHITEST", "CHITEST"),
+    ("LEGACY.FDIST", "FDIST"),
+    ("LEGACY.FINV", "FINV"),
+    ("LEGACY.NORMSDIST", "NORMSDIST"),
+    ("LEGACY.NORMSINV", "NORMSINV"),
+    ("LEGACY.TDIST", "TDIST"),
+];
+
+/// German function names. Functions with the same name in German are not listed.
+const DE_NAMES: &[(&str, &str)] = &[
+    ("ACCRINT", "AUFGELZINS"),
+    ("ACCRINTM", "AUFGELZINSF"),
+    ("ACOS", "ARCCOS"),
+    ("ACOSH", "ARCCOSHYP"),
+    ("ACOT", "ARCCOT"),
+    ("ACOTH", "ARCCOTHYP"),
+    ("ADDRESS", "ADRESSE"),
+    ("AMORDEGRC", "AMORDEGRK"),
+    ("AMORLINC", "AMORLINEARK"),
+    ("AND", "UND"),
+    ("ARABIC", "ARABISCH"),
+    ("AREAS", "BEREICHE"),
+    ("ASIN", "ARCSIN"),
+    ("ASINH", "ARCSINHYP"),
+    ("ATAN", "ARCTAN"),
+    ("ATAN2", "ARCTAN2"),
+    ("ATANH", "ARCTANHYP"),
+    ("AVEDEV", "MITTELABW"),
+    ("AVERAGE", "MITTELWERT"),
+    ("AVERAGEA", "MITTELWERTA"),
+    ("AVERAGEIF", "MITTELWERTWENN"),
+    ("AVERAGEIFS", "MITTELWERTWENNS"),
+    ("BASE", "BASIS"),
+    ("BETADIST", "BETAVERT"),
+    ("BIN2DEC", "BININDEZ"),
+    ("BIN2HEX", "BININHEX"),
+    ("BIN2OCT", "BININOKT"),
+    ("BINOM.DIST.RANGE", "BINOM.VERT.BEREICH"),
+    ("BINOMDIST", "BINOMVERT"),
+    ("BITAND", "BITUND"),
+    ("BITLSHIFT", "BITLVERSCHIEB"),
+    ("BITOR", "BITODER"),
+    ("BITRSHIFT", "BITRVERSCHIEB"),
+    ("BITXOR", "BITXODER"),
+    ("CEILING", "OBERGRENZE"),
+    ("CELL", "ZELLE"),
+    ("CHAR", "ZEICHEN"),
+    ("CHISQDIST", "CHIQUVERT"),
+    ("CHISQINV", "CHIQUINV"),
+    ("CHOOSE", "WAHL"),
+    ("CLEAN", "SÄUBERN"),
+    ("COLUMN", "SPALTE"),
+    ("COLUMNS", "SPALTEN"),
+    ("COM.MICROSOFT.CHISQ.TEST", "CHIQU.TEST"),
+    ("COM.MICROSOFT.CONFIDENCE.NORM", "KONFIDENZ.NORM"),
+    ("COM.MICROSOFT.CONFIDENCE.T", "KONFIDENZ.T"),
+    ("COM.MICROSOFT.TEXTJOIN", "TEXTVERKETTEN"),
+    ("COMBIN", "KOMBINATIONEN"),
+    ("COMBINA", "KOMBINATIONEN2"),
+    ("COMPLEX", "KOMPLEXE"),
+    ("CONCATENATE", "VERKETTEN"),
+    ("CONFIDENCE", "KONFIDENZ"),
+    ("CONVERT", "UMWANDELN"),
+    ("CORREL", "KORREL"),
+    ("COUNT", "ANZAHL"),
+    ("COUNTA", "ANZAHL2"),
+    ("COUNTBLANK", "ANZAHLLEEREZELLEN"),
+    ("COUNTIF", "ZÄHLENWENN"),
+    ("COUNTIFS", "ZÄHLENWENNS"),
+    ("COUPDAYBS", "ZINSTERMTAGVA"),
+    ("COUPDAYS", "ZINSTERMTAGE"),
+    ("COUPDAYSNC", "ZINSTERMTAGNZ"),
+    ("COUPNCD", "ZINSTERMNZ"),
+    ("COUPNUM", "ZINSTERMZAHL"),
+    ("COUPPCD", "ZINSTERMVZ"),
+    ("COVAR", "KOVAR"),
+    ("CRITBINOM", "KRITBINOM"),
+    ("CUMIPMT", "KUMZINSZ"),
+    ("CUMPRINC", "KUMKAPITAL"),
+    ("DATE", "DATUM"),
+    ("DATEDIF", "DATUMDIF"),
+    ("DATEVALUE", "DATWERT"),
+    ("DAVERAGE", "DBMITTELWERT"),
+    ("DAY", "TAG"),
+    ("DAYS", "TAGE"),
+    ("DAYS360", "TAGE360"),
+    ("DB", "GDA2"),
+    ("DCOUNT", "DBANZAHL"),
+    ("DCOUNTA", "DBANZAHL2"),
+    ("DDB", "GDA"),
+    ("DEC2BIN", "DEZINBIN"),
+    ("DEC2HEX", "DEZINHEX"),
+    ("DEC2OCT", "DEZINOKT"),
+    ("DECIMAL", "DEZIMAL"),
+    ("DEGREES", "GRAD"),
+    ("DEVSQ", "SUMQUADABW"),
+    ("DGET", "DBAUSZUG"),
+    ("DISC", "DISAGIO"),
+    ("DMAX", "DBMAX"),
+    ("DMIN", "DBMIN"),
+    ("DPRODUCT", "DBPRODUKT"),
+    ("DSTDEV", "DBSTDABW"),
+    ("DSTDEVP", "DBSTDABWN"),
+    ("DSUM", "DBSUMME"),
+    ("DVAR", "DBVARIANZ"),
+    ("DVARP", "DBVARIANZEN"),
+    ("EDATE", "EDATUM"),
+    ("EFFECT", "EFFEKTIV"),
+    ("EOMONTH", "MONATSENDE"),
+    ("ERF", "GAUSSFEHLER"),
+    ("ERFC", "GAUSSFKOMPL"),
+    ("ERROR.TYPE", "FEHLER.TYP"),
+    ("EVEN", "GERADE"),
+    ("EXACT", "IDENTISCH"),
+    ("EXPONDIST", "EXPONVERT"),
+    ("FACT", "FAKULTÄT"),
+    ("FACTDOUBLE", "ZWEIFAKULTÄT"),
+    ("FALSE", "FALSCH"),
+    ("FDIST", "F.VERT"),
+    ("FIND", "FINDEN"),
+    ("FINDB", "FINDENB"),
+    ("FINV", "F.INV"),
+    ("FIXED", "FEST"),
+    ("FLOOR", "UNTERGRENZE"),
+    ("FORECAST", "SCHÄTZER"),
+    ("FORMULA", "FORMEL"),
+    ("FREQUENCY", "HÄUFIGKEIT"),
+    ("FV", "ZW"),
+    ("GAMMADIST", "GAMMAVERT"),
+    ("GCD", "GGT"),
+    ("GEOMEAN", "GEOMITTEL"),
+    ("GESTEP", "GGANZZAHL"),
+    ("GETPIVOTDATA", "PIVOTDATENZUORDNEN"),
+    ("GROWTH", "VARIATION"),
+    ("HARMEAN", "HARMITTEL"),
+    ("HEX2BIN", "HEXINBIN"),
+    ("HEX2DEC", "HEXINDEZ"),
+    ("HEX2OCT", "HEXINOKT"),
+    ("HLOOKUP", "WVERWEIS"),
+    ("HOUR", "STUNDE"),
+    ("HYPGEOMDIST", "HYPGEOMVERT"),
+    ("IF", "WENN"),
+    ("IFERROR", "WENNFEHLER"),
+    ("IFNA", "WENNNV"),
+    ("IMAGINARY", "IMAGINÄRTEIL"),
+    ("IMCONJUGATE", "IMKONJUGIERTE"),
+    ("IMPOWER", "IMAPOTENZ"),
+    ("IMPRODUCT", "IMPRODUKT"),
+    ("IMREAL", "IMREALTEIL"),
+    ("IMSQRT", "IMWURZEL"),
+    ("IMSUM", "IMSUMME"),
+    ("INDIRECT", "INDIREKT"),
+    ("INT", "GANZZAHL"),
+    ("INTERCEPT", "ACHSENABSCHNITT"),
+    ("INTRATE", "ZINSSATZ"),
+    ("IPMT", "ZINSZ"),
+    ("IRR", "IKV"),
+    ("ISBLANK", "ISTLEER"),
+    ("ISERR", "ISTFEHL"),
+    ("ISERROR", "ISTFEHLER"),
+    ("ISEVEN", "ISTGERADE"),
+    ("ISFORMULA", "ISTFORMEL"),
+    ("ISLOGICAL", "ISTLOG"),
+    ("ISNA", "ISTNV"),
+    ("ISNONTEXT", "ISTKTEXT"),
+    ("ISNUMBER", "ISTZAHL"),
+    ("ISODD", "ISTUNGERADE"),
+    ("ISOWEEKNUM", "ISOKALENDERWOCHE"),
+    ("ISREF", "ISTBEZUG"),
+    ("ISTEXT", "ISTTEXT"),
+    ("LARGE", "KGRÖSSTE"),
+    ("LCM", "KGV"),
+    ("LEFT", "LINKS"),
+    ("LEFTB", "LINKSB"),
+    ("LEGACY.CHIDIST", "CHIVERT"),
+    ("LEGACY.CHIINV", "CHIINV"),
+    ("LEGACY.CHITEST", "CHITEST"),
+    ("LEGACY.FDIST", "FVERT"),
+    ("LEGACY.FINV", "FINV"),
+    ("LEGACY.NORMSDIST", "STANDNORMVERT"),
+    ("LEGACY.NORMSINV", "STANDNORMINV"),
+    ("LEGACY.TDIST", "TVERT"),
+    ("LEN", "LÄNGE"),
+    ("LENB", "LÄNGEB"),
+    ("LINEST", "RGP"),
+    ("LOGEST", "RKP"),
+    ("LOGNORMDIST", "LOGNORMVERT"),
+    ("LOOKUP", "VERWEIS"),
+    ("LOWER", "KLEIN"),
+    ("MATCH", "VERGLEICH"),
+    ("MDETERM", "MDET"),
+    ("MID", "TEIL"),
+    ("MIDB", "TEILB"),
+    ("MINVERSE", "MINV"),
+    ("MIRR", "QIKV"),
+    ("MOD", "REST"),
+    ("MODE", "MODALWERT"),
+    ("MONTH", "MONAT"),
+    ("MROUND", "VRUNDEN"),
+    ("MULTINOMIAL", "POLYNOMIAL"),
+    ("MULTIPLE.OPERATIONS", "MEHRFACH.OPERATIONEN"),
+    ("MUNIT", "EINHEITSMATRIX"),
+    ("NA", "NV"),
+    ("NEGBINOMDIST", "NEGBINOMVERT"),
+    ("NETWORKDAYS", "NETTOARBEITSTAGE"),
+    ("NORMDIST", "NORMVERT"),
+    ("NOT", "NICHT"),
+    ("NOW", "JETZT"),
+    ("NPER", "ZZR"),
+    ("NPV", "NBW"),
+    ("NUMBERVALUE", "ZAHLENWERT"),
+    ("OCT2BIN", "OKTINBIN"),
+    ("OCT2DEC", "OKTINDEZ"),
+    ("OCT2HEX", "OKTINHEX"),
+    ("ODD", "UNGERADE"),
+    ("ODDFPRICE", "UNREGER.KURS"),
+    ("ODDFYIELD", "UNREGER.REND"),
+    ("ODDLPRICE", "UNREGLE.KURS"),
+    ("ODDLYIELD", "UNREGLE.REND"),
+    ("OFFSET", "VERSCHIEBUNG"),
+    ("OR", "ODER"),
+    ("PDURATION", "LAUFZEIT"),
+    ("PERCENTILE", "QUANTIL"),
+    ("PERCENTRANK", "QUANTILSRANG"),
+    ("PMT", "RMZ"),
+    ("POWER", "POTENZ"),
+    ("PPMT", "KAPZ"),
+    ("PRICE", "KURS"),
+    ("PRICEDISC", "KURSDISAGIO"),
+    ("PRICEMAT", "KURSFÄLLIG"),
+    ("PRODUCT", "PRODUKT"),
+    ("PROPER", "GROSS2"),
+    ("PV", "BW"),
+    ("RADIANS", "BOGENMASS"),
+    ("RAND", "ZUFALLSZAHL"),
+    ("RANDBETWEEN", "ZUFALLSBEREICH"),
+    ("RANK", "RANG"),
+    ("RATE", "ZINS"),
+    ("RECEIVED", "AUSZAHLUNG"),
+    ("REPLACE", "ERSETZEN"),
+    ("REPLACEB", "ERSETZENB"),
+    ("REPT", "WIEDERHOLEN"),
+    ("RIGHT", "RECHTS"),
+    ("RIGHTB", "RECHTSB"),
+    ("ROMAN", "RÖMISCH"),
+    ("ROUND", "RUNDEN"),
+    ("ROUNDDOWN", "ABRUNDEN"),
+    ("ROUNDUP", "AUFRUNDEN"),
+    ("ROW", "ZEILE"),
+    ("ROWS", "ZEILEN"),
+    ("RRI", "ZGZ"),
+    ("RSQ", "BESTIMMTHEITSMASS"),
+    ("SEARCH", "SUCHEN"),
+    ("SEARCHB", "SUCHENB"),
+    ("SECOND", "SEKUNDE"),
+    ("SERIESSUM", "POTENZREIHE"),
+    ("SHEET", "TABELLE"),
+    ("SHEETS", "TABELLEN"),
+    ("SIGN", "VORZEICHEN"),
+    ("SKEW", "SCHIEFE"),
+    ("SKEWP", "SCHIEFEP"),
+    ("SLN", "LIA"),
+    ("SLOPE", "STEIGUNG"),
+    ("SMALL", "KKLEINSTE"),
+    ("SQRT", "WURZEL"),
+    ("SQRTPI", "WURZELPI"),
+    ("STANDARDIZE", "STANDARDISIERUNG"),
+    ("STDEV", "STABW"),
+    ("STDEVA", "STABWA"),
+    ("STDEVP", "STABWN"),
+    ("STDEVPA", "STABWNA"),
+    ("STEYX", "STFEHLERYX"),
+    ("SUBSTITUTE", "WECHSELN"),
+    ("SUBTOTAL", "TEILERGEBNIS"),
+    ("SUM", "SUMME"),
+    ("SUMIF", "SUMMEWENN"),
+    ("SUMIFS", "SUMMEWENNS"),
+    ("SUMPRODUCT", "SUMMENPRODUKT"),
+    ("SUMX2MY2", "SUMMEX2MY2"),
+    ("SUMX2PY2", "SUMMEX2PY2"),
+    ("SUMXMY2", "SUMMEXMY2"),
+    ("SYD", "DIA"),
+    ("TBILLEQ", "TBILLÄQUIV"),
+    ("TBILLPRICE", "TBILLKURS"),
+    ("TBILLYIELD", "TBILLRENDITE"),
+    ("TIME", "ZEIT"),
+    ("TIMEVALUE", "ZEITWERT"),
+    ("TODAY", "HEUTE"),
+    ("TRANSPOSE", "MTRANS"),
+    ("TRIM", "GLÄTTEN"),
+    ("TRIMMEAN", "GESTUTZTMITTEL"),
+    ("TRUE", "WAHR"),
+    ("TRUNC", "KÜRZEN"),
+    ("TYPE", "TYP"),
+    ("UNICHAR", "UNIZEICHEN"),
+    ("UPPER", "GROSS"),
+    ("VALUE", "WERT"),
+    ("VAR", "VARIANZ"),
+    ("VARA", "VARIANZA"),
+    ("VARP", "VARIANZEN"),
+    ("VARPA", "VARIANZENA"),
+    ("VLOOKUP", "SVERWEIS"),
+    ("WEEKDAY", "WOCHENTAG"),
+    ("WEEKNUM", "KALENDERWOCHE"),
+    ("WORKDAY", "ARBEITSTAG"),
+    ("XIRR", "XINTZINSFUSS"),
+    ("XNPV", "XKAPITALWERT"),
+    ("XOR", "XODER"),
+    ("YEAR", "JAHR"),
+    ("YEARFRAC", "BRTEILJAHRE"),
+    ("YIELD", "RENDITE"),
+    ("YIELDDISC", "RENDITEDIS"),
+    ("YIELDMAT", "RENDITEFÄLL"),
+    ("ZTEST", "GTEST"),
+];
+
+/// Creates the formula as shown in the UI, starting with '='.
+pub fn ui_formula<T: Any>(f: T, syntax: &UiSyntax) -> String {
+    let mut buf = String::new();
+    let _ = ui_formula_into(&mut buf, f, syntax);
+    buf
+}
+
+/// Writes the formula as shown in the UI, starting with '=', into the buffer.
+pub fn ui_formula_into<W: Write + ?Sized, T: Any>(
+    buf: &mut W,
+    f: T,
+    syntax: &UiSyntax,
+) -> fmt::Result {
+    buf.write_char('=')?;
+    UiWriter { buf, syntax }.expr(&f.expr())
+}
+
+/// Parses a formula in UI syntax to the OpenFormula expression.
+/// The leading '=' is optional.
+pub fn parse_ui(formula: &str, syntax: &UiSyntax) -> Result<Expr, ParseError> {
+    let start = if formula.starts_with('=') { 1 } else { 0 };
+    let func_name = |name: &str| syntax.canonical_name(name);
+    let parse_syntax = Syntax {
+        arg_sep: syntax.arg_sep,
+        decimal: syntax.decimal,
+        array_col: syntax.array_col,
+        array_row: syntax.array_row,
+        refs: RefNotation::Calc,
+        anchor: None,
+        func_name: &func_name,
+    };
+    parse_with(&formula[start..], &parse_syntax).map_err(|mut e| {
+        e.span = e.span.start + start..e.span.end + start;
+        e
+    })
+}
+
+struct UiWriter<'a, W: Write + ?Sized> {
+    buf: &'a mut W,
+    syntax: &'a UiSyntax,
+}
+
+impl<'a, W: Write + ?Sized> UiWriter<'a, W> {
+    fn expr(&mut self, expr: &Expr) -> fmt::Result {
+        match expr {
+            Expr::Number(v) => {
+                for c in v.chars() {
+                    self.buf
+                        .write_char(if c == '.' { self.syntax.decimal } else { c })?;
+                }
+            }
+            Expr::Text(v) => crate::expr::text_literal(self.buf, v)?,
+            Expr::Logical(v) => {
+                let name = if *v { "TRUE" } else { "FALSE" };
+                write!(self.buf, "{}()", self.syntax.local_name(name))?;
+            }
+            Expr::CellRef(v) => self.cellref(v)?,
+            Expr::CellRange(v) => self.cellrange(v)?,
+            Expr::Name(v) => self.buf.write_str(v)?,
+            Expr::Error(v) => self.buf.write_str(v.as_str())?,
+            Expr::Missing => {}
+            Expr::Func { name, args } => {
+                self.buf.write_str(self.syntax.local_name(name))?;
+                self.buf.write_char('(')?;
+                self.separated(args, self.syntax.arg_sep)?;
+                self.buf.write_char(')')?;
+            }
+            Expr::Infix { op, lhs, rhs } => {
+                self.operand(lhs, op.precedence())?;
+                write!(self.buf, "{}", op)?;
+                self.operand(rhs, op.precedence() + 1)?;
+            }
+            Expr::Prefix { op, expr: v } => {
+                write!(self.buf, "{}", op)?;
+                self.operand(v, expr.precedence())?;
+            }
+            Expr::Postfix { op, expr: v } => {
+                self.operand(v, expr.precedence())?;
+                write!(self.buf, "{}", op)?;
+            }
+            Expr::Parentheses(v) => {
+                self.buf.write_char('(')?;
+                self.expr(v)?;
+                self.buf.write_char(')')?;
+            }
+            Expr::Array(rows) => {
+                self.buf.write_char('{')?;
+                for (i, r) in rows.iter().enumerate() {
+                    if i > 0 {
+                        self.buf.write_char(self.syntax.array_row)?;
+                    }
+                    self.separated(r, self.syntax.array_col)?;
+                }
+                self.buf.write_char('}')?;
+            }
+            Expr::List(list) => self.separated(list, self.syntax.arg_sep)?,
+            Expr::Raw(v) => match parse_expr(v) {
+                Ok(v) => self.expr(&v)?,
+                Err(_) => self.buf.write_str(v)?,
+            },
+        }
+        Ok(())
+    }
+
+    fn operand(&mut self, expr: &Expr, min_precedence: u8) -> fmt::Result {
+        if expr.precedence() < min_precedence {
+            self.buf.write_char('(')?;
+            self.expr(expr)?;
+            self.buf.write_char(')')
+        } else {
+            self.expr(expr)
+        }
+    }
+
+    fn separated(&mut self, list: &[Expr], sep: char) -> fmt::Result {
+        for (i, v) in list.iter().enumerate() {
+            if i > 0 {
+                self.buf.write_char(sep)?;
+            }
+            self.expr(v)?;
+        }
+        Ok(())
+    }
+
+    fn cellref(&mut self, r: &CellRef) -> fmt::Result {
+        self.table(r.iri(), r.table())?;
+        self.cell(r.col_abs(), r.col(), r.row_abs(), r.row())
+    }
+
+    fn cellrange(&mut self, r: &CellRange) -> fmt::Result {
+        self.table(r.iri(), r.table())?;
+        self.cell(r.col_abs(), r.col(), r.row_abs(), r.row())?;
+        self.buf.write_char(':')?;
+        if let Some(to_table) = r.to_table().filter(|v| Some(*v) != r.table()) {
+            self.table(None, Some(to_table))?;
+        }
+        self.cell(r.to_col_abs(), r.to_col(), r.to_row_abs(), r.to_row())
+    }
+
+    /// Writes 'iri'#$Sheet1. as far as present.
+    fn table(&mut self, iri: Option<&String>, table: Option<&String>) -> fmt::Result {
+        if let Some(iri) = iri {
+            quoted_name(self.buf, iri, true)?;
+            self.buf.write_char('#')?;
+        }
+        if let Some(table) = table {
+            self.buf.write_char('$')?;
+            quoted_name(self.buf, table, false)?;
+            self.buf.write_char('.')?;
+        }
+        Ok(())
+    }
+
+    fn cell(&mut self, col_abs: bool, col: u32, row_abs: bool, row: u32) -> fmt::Result {
+        if col_abs {
+            self.buf.write_char('$')?;
+        }
+        crate::excel::col_name(self.buf, col)?;
+        if row_abs {
+            self.buf.write_char('$')?;
+        }
+        write!(self.buf, "{}", row + 1)
+    }
+}
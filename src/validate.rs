@@ -0,0 +1,221 @@
+//!
+//! Checks an expression against the OpenFormula rules before it is written.
+//!
+//! Uses the function catalog for arity and parameter types, and checks
+//! inline arrays and references.
+//!
+
+use crate::{function_info, parse_expr, Expr, InfixOp, ParseError, ValueType};
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+
+/// Number of rows of a sheet.
+pub const MAX_ROWS: u32 = 1_048_576;
+/// Number of columns of a sheet.
+pub const MAX_COLS: u32 = 16_384;
+
+/// Reasons for an invalid formula.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ValidationError {
+    /// Function is not in the catalog.
+    UnknownFunction(String),
+    /// Wrong number of arguments.
+    ArgCount {
+        func: String,
+        count: usize,
+        min: usize,
+        max: Option<usize>,
+    },
+    /// A required argument is left out.
+    MissingArgument { func: String, index: usize },
+    /// The argument can never be of the parameter type.
+    ArgType {
+        func: String,
+        index: usize,
+        expected: ValueType,
+    },
+    /// Inline array without values.
+    EmptyArray,
+    /// Inline array row with a different length than the first row.
+    RaggedArray {
+        row: usize,
+        len: usize,
+        expected: usize,
+    },
+    /// Reference outside of the sheet.
+    OutOfBounds { row: u32, col: u32 },
+    /// Formula text that could not be parsed.
+    Parse(ParseError),
+}
+
+impl Display for ValidationError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ValidationError::UnknownFunction(name) => write!(f, "unknown function {}", name),
+            ValidationError::ArgCount {
+                func,
+                count,
+                min,
+                max,
+            } => match max {
+                Some(max) if min == max => {
+                    write!(f, "{} expects {} arguments, got {}", func, min, count)
+                }
+                Some(max) => write!(
+                    f,
+                    "{} expects {} to {} arguments, got {}",
+                    func, min, max, count
+                ),
+                None => write!(
+                    f,
+                    "{} expects at least {} arguments, got {}",
+                    func, min, count
+                ),
+            },
+            ValidationError::MissingArgument { func, index } => {
+                write!(f, "{} argument {} is required", func, index + 1)
+            }
+            ValidationError::ArgType {
+                func,
+                index,
+                expected,
+            } => write!(f, "{} argument {} must be {:?}", func, index + 1, expected),
+            ValidationError::EmptyArray => write!(f, "empty inline array"),
+            ValidationError::RaggedArray { row, len, expected } => write!(
+                f,
+                "inline array row {} has {} values, expected {}",
+                row + 1,
+                len,
+                expected
+            ),
+            ValidationError::OutOfBounds { row, col } => write!(
+                f,
+                "reference to row {} column {} is outside of the sheet",
+                row + 1,
+                col + 1
+            ),
+            ValidationError::Parse(err) => write!(f, "{}", err),
+        }
+    }
+}
+
+impl Error for ValidationError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ValidationError::Parse(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<ParseError> for ValidationError {
+    fn from(err: ParseError) -> Self {
+        ValidationError::Parse(err)
+    }
+}
+
+/// Checks the expression and all its subexpressions.
+pub fn validate(expr: &Expr) -> Result<(), ValidationError> {
+    match expr {
+        Expr::CellRef(r) => bounds(r.row(), r.col())?,
+        Expr::CellRange(r) => {
+            bounds(r.row(), r.col())?;
+            bounds(r.to_row(), r.to_col())?;
+        }
+        Expr::Func { name, args } => validate_func(name, args)?,
+        Expr::Array(rows) => {
+            let expected = rows.first().map(|v| v.len()).unwrap_or(0);
+            if expected == 0 {
+                return Err(ValidationError::EmptyArray);
+            }
+            for (row, r) in rows.iter().enumerate() {
+                if r.len() != expected {
+                    return Err(ValidationError::RaggedArray {
+                        row,
+                        len: r.len(),
+                        expected,
+                    });
+                }
+            }
+        }
+        // Text from Any implementations without an expression tree.
+        Expr::Raw(v) => return validate(&parse_expr(v)?),
+        _ => {}
+    }
+    for v in expr.children() {
+        validate(v)?;
+    }
+    Ok(())
+}
+
+fn bounds(row: u32, col: u32) -> Result<(), ValidationError> {
+    if row >= MAX_ROWS || col >= MAX_COLS {
+        Err(ValidationError::OutOfBounds { row, col })
+    } else {
+        Ok(())
+    }
+}
+
+fn validate_func(name: &str, args: &[Expr]) -> Result<(), ValidationError> {
+    let Some(info) = function_info(name) else {
+        return Err(ValidationError::UnknownFunction(name.to_string()));
+    };
+    if !info.accepts(args.len()) {
+        return Err(ValidationError::ArgCount {
+            func: info.name.to_string(),
+            count: args.len(),
+            min: info.min_args,
+            max: info.max_args,
+        });
+    }
+    for (index, arg) in args.iter().enumerate() {
+        if index < info.min_args && matches!(arg, Expr::Missing) {
+            return Err(ValidationError::MissingArgument {
+                func: info.name.to_string(),
+                index,
+            });
+        }
+        let Some(expected) = info.param_type(index) else {
+            continue;
+        };
+        if !accepts(expected, arg) {
+            return Err(ValidationError::ArgType {
+                func: info.name.to_string(),
+                index,
+                expected,
+            });
+        }
+    }
+    Ok(())
+}
+
+// Rejects only arguments that can't be converted to the parameter type.
+fn accepts(expected: ValueType, arg: &Expr) -> bool {
+    match expected {
+        ValueType::Reference | ValueType::ReferenceList | ValueType::Database => {
+            maybe_reference(arg)
+        }
+        ValueType::Number | ValueType::Integer => match arg {
+            Expr::Text(v) => v.trim().parse::<f64>().is_ok(),
+            _ => true,
+        },
+        ValueType::Logical => !matches!(arg, Expr::Text(_)),
+        _ => true,
+    }
+}
+
+fn maybe_reference(arg: &Expr) -> bool {
+    match arg {
+        Expr::CellRef(_) | Expr::CellRange(_) | Expr::Name(_) | Expr::Missing | Expr::Raw(_) => {
+            true
+        }
+        Expr::Infix { op, .. } => {
+            matches!(op, InfixOp::Range | InfixOp::Intersect | InfixOp::RefConcat)
+        }
+        Expr::Parentheses(v) => maybe_reference(v),
+        Expr::List(v) => v.iter().all(maybe_reference),
+        Expr::Func { name, .. } => function_info(name)
+            .is_none_or(|v| matches!(v.returns, ValueType::Reference | ValueType::Any)),
+        _ => false,
+    }
+}
@@ -1,8 +1,14 @@
 use crate::result_test::{test_ok, ReportValues};
 use ods_formula as of;
 use ods_formula::prelude::*;
-use ods_formula::{cell, range, CriterionCmp, Days360Method, FAny, FCriterion, WeekdayMethod};
-use spreadsheet_ods::{CellRange, CellRef};
+use ods_formula::{
+    cell, range, Category, CriterionCmp, DayCountBasis, Days360Method, DistMode, ErrorValue,
+    EvalValue, Evaluator, ExcelError, Expr, FAny, FComplex, FCriterion, FError, FNumber,
+    FormatCode, FormatColor, FormulaError, Frequency, InfixOp, LinestStat, Namespace,
+    ParseErrorKind, PaymentType, Quartile, RankOrder, RomanForm, RoundingMode, TTestType, Tails,
+    UiSyntax, ValidationError, ValueType, WeekdayMethod,
+};
+use spreadsheet_ods::{CellRange, CellRef, Sheet, WorkBook};
 
 mod result_test;
 
@@ -115,6 +121,69 @@ pub fn test_op() {
     .q(Q);
 }
 
+#[test]
+fn test_precedence() {
+    test_ok(of::formula((1.n() + 2) * 3))
+        .test(eq, "of=(1+2)*3")
+        .q(Q);
+    test_ok(of::formula(1.n() + 2 * 3)).test(eq, "of=1+6").q(Q);
+    test_ok(of::formula(1.n() + 2.n() * 3))
+        .test(eq, "of=1+2*3")
+        .q(Q);
+    test_ok(of::formula(1.n() - (2.n() - 3)))
+        .test(eq, "of=1-(2-3)")
+        .q(Q);
+    test_ok(of::formula((1.n() - 2) - 3))
+        .test(eq, "of=1-2-3")
+        .q(Q);
+    test_ok(of::formula(-(1.n() + 2)))
+        .test(eq, "of=-(1+2)")
+        .q(Q);
+    test_ok(of::formula((1.n() ^ 2) ^ 3))
+        .test(eq, "of=1^2^3")
+        .q(Q);
+    test_ok(of::formula(2.n() ^ (1.n() ^ 3)))
+        .test(eq, "of=2^(1^3)")
+        .q(Q);
+    test_ok(of::formula(of::pow(-2, 2)))
+        .test(eq, "of=-2^2")
+        .q(Q);
+    test_ok(of::formula(of::neg(of::pow(2, 2))))
+        .test(eq, "of=-(2^2)")
+        .q(Q);
+    test_ok(of::formula((cell!(0, 0).n() / 4).percent()))
+        .test(eq, "of=([.A1]/4)%")
+        .q(Q);
+    test_ok(of::formula(of::eq("a".concat("b"), "ab")))
+        .test(eq, "of=\"a\"&\"b\"=\"ab\"")
+        .q(Q);
+    test_ok(of::formula(p(1.n() + 2) * 3.n()))
+        .test(eq, "of=(1+2)*3")
+        .q(Q);
+}
+
+#[test]
+fn test_formula_into() {
+    let mut buf = String::from("formula: ");
+    of::formula_into(
+        &mut buf,
+        of::sumifs(
+            range!(0, 0, 9, 0),
+            &[(range!(0, 1, 9, 1), (CriterionCmp::Gt, 5))],
+        ),
+    )
+    .unwrap();
+    of::formula_into(&mut buf, of::countifs(&[])).unwrap();
+    assert_eq!(
+        buf,
+        "formula: of=SUMIFS([.A1:.A10];[.B1:.B10];\">\"&5)of=COUNTIFS()"
+    );
+
+    test_ok(format!("{}", (1.n() + 2).expr()))
+        .test(eq, "1+2")
+        .q(Q);
+}
+
 #[test]
 fn test_bitop() {
     test_ok(of::formula(of::bitand(1.n() + CellRef::local(5, 5), 5)))
@@ -184,7 +253,9 @@ fn test_date() {
 
 #[test]
 fn test_compose() {
-    test_ok(of::formula(of::date(1, of::month(of::today()), 2200))).q(Q);
+    test_ok(of::formula(of::date(2200, of::month(of::today()), 1)))
+        .test(eq, "of=DATE(2200;MONTH(TODAY());1)")
+        .q(Q);
 }
 
 #[test]
@@ -226,3 +297,1363 @@ fn test_math() {
     )))
     .q(Q);
 }
+
+#[test]
+fn test_text() {
+    test_ok(of::formula(of::left_len(cell!(0, 0), 3)))
+        .test(eq, "of=LEFT([.A1];3)")
+        .q(Q);
+    test_ok(of::formula(of::mid("asdf", 2, 1)))
+        .test(eq, "of=MID(\"asdf\";2;1)")
+        .q(Q);
+    test_ok(of::formula(of::find_from("a", cell!(1, 1), 2)))
+        .test(eq, "of=FIND(\"a\";[.B2];2)")
+        .q(Q);
+    test_ok(of::formula(of::substitute_nth(cell!(1, 1), " ", "_", 1)))
+        .test(eq, "of=SUBSTITUTE([.B2];\" \";\"_\";1)")
+        .q(Q);
+    test_ok(of::formula(of::trim(of::upper(cell!(1, 1)))))
+        .test(eq, "of=TRIM(UPPER([.B2]))")
+        .q(Q);
+    test_ok(of::formula(of::concatenate(("a", cell!(0, 0), "b"))))
+        .test(eq, "of=CONCATENATE(\"a\";[.A1];\"b\")")
+        .q(Q);
+    test_ok(of::formula(of::textjoin(", ", true, range!(0, 0, 9, 0))))
+        .test(eq, "of=COM.MICROSOFT.TEXTJOIN(\", \";TRUE();[.A1:.A10])")
+        .q(Q);
+}
+
+#[test]
+fn test_text_format() {
+    test_ok(of::formula(of::text(
+        cell!(0, 0),
+        FormatCode::new().number(1, 2).percent(),
+    )))
+    .test(eq, "of=TEXT([.A1];\"0.00%\")")
+    .q(Q);
+    test_ok(of::formula(of::text(
+        cell!(0, 0),
+        FormatCode::new()
+            .grouped(1, 2)
+            .section()
+            .color(FormatColor::Red)
+            .sep('-')
+            .grouped(1, 2),
+    )))
+    .test(eq, "of=TEXT([.A1];\"#,##0.00;[RED]-#,##0.00\")")
+    .q(Q);
+    test_ok(of::formula(of::text(
+        of::today(),
+        FormatCode::new()
+            .year4()
+            .sep('-')
+            .month2()
+            .sep('-')
+            .day2()
+            .literal(" at ")
+            .hours2()
+            .sep(':')
+            .minutes2(),
+    )))
+    .test(eq, "of=TEXT(TODAY();\"YYYY-MM-DD\"\" at \"\"HH:MM\")")
+    .q(Q);
+    // Minutes before seconds, without hours.
+    test_ok(of::formula(of::text(
+        cell!(0, 0),
+        FormatCode::new().minutes2().sep(':').seconds2_fraction(1),
+    )))
+    .test(eq, "of=TEXT([.A1];\"MM:SS.0\")")
+    .q(Q);
+    test_ok(of::formula(of::concatenate((
+        "Total: ",
+        FormatCode::new().number(0, 0).optional_decimals(2).build(),
+    ))))
+    .test(eq, "of=CONCATENATE(\"Total: \";\"#.##\")")
+    .q(Q);
+    test_ok(of::formula(of::fixed_decimals_nosep(cell!(0, 0), 3)))
+        .test(eq, "of=FIXED([.A1];3;TRUE())")
+        .q(Q);
+    test_ok(of::formula(of::unichar(of::unicode("x"))))
+        .test(eq, "of=UNICHAR(UNICODE(\"x\"))")
+        .q(Q);
+}
+
+#[test]
+fn test_financial() {
+    test_ok(of::formula(of::pmt(0.05.n() / 12, 360, cell!(0, 1))))
+        .test(eq, "of=PMT(0.05/12;360;[.B1])")
+        .q(Q);
+    test_ok(of::formula(of::pmt_type(
+        0.05,
+        10,
+        1000,
+        PaymentType::Begin,
+    )))
+    .test(eq, "of=PMT(0.05;10;1000;;1)")
+    .q(Q);
+    test_ok(of::formula(of::cumipmt(
+        0.05,
+        10,
+        1000,
+        1,
+        5,
+        PaymentType::End,
+    )))
+    .test(eq, "of=CUMIPMT(0.05;10;1000;1;5;0)")
+    .q(Q);
+    test_ok(of::formula(of::npv(0.1, range!(0, 0, 9, 0))))
+        .test(eq, "of=NPV(0.1;[.A1:.A10])")
+        .q(Q);
+    test_ok(of::formula(of::xirr_guess(
+        range!(0, 0, 9, 0),
+        range!(0, 1, 9, 1),
+        0.1,
+    )))
+    .test(eq, "of=XIRR([.A1:.A10];[.B1:.B10];0.1)")
+    .q(Q);
+}
+
+#[test]
+fn test_fixed_income() {
+    test_ok(of::formula(of::price_basis(
+        cell!(0, 0),
+        cell!(0, 1),
+        0.0575,
+        0.065,
+        100,
+        Frequency::Semiannual,
+        DayCountBasis::USNasd30_360,
+    )))
+    .test(eq, "of=PRICE([.A1];[.B1];0.0575;0.065;100;2;0)")
+    .q(Q);
+    test_ok(of::formula(of::coupnum(
+        cell!(0, 0),
+        cell!(0, 1),
+        Frequency::Quarterly,
+    )))
+    .test(eq, "of=COUPNUM([.A1];[.B1];4)")
+    .q(Q);
+    test_ok(of::formula(of::yearfrac(
+        cell!(0, 0),
+        cell!(0, 1),
+        DayCountBasis::Actual365,
+    )))
+    .test(eq, "of=YEARFRAC([.A1];[.B1];3)")
+    .q(Q);
+    test_ok(of::formula(of::tbillprice(cell!(0, 0), cell!(0, 1), 0.09)))
+        .test(eq, "of=TBILLPRICE([.A1];[.B1];0.09)")
+        .q(Q);
+}
+
+#[test]
+fn test_depreciation() {
+    test_ok(of::formula(of::sln(10000, 1000, 5)))
+        .test(eq, "of=SLN(10000;1000;5)")
+        .q(Q);
+    test_ok(of::formula(of::db_month(10000, 1000, 5, 1, 7)))
+        .test(eq, "of=DB(10000;1000;5;1;7)")
+        .q(Q);
+    test_ok(of::formula(of::vdb_noswitch(10000, 1000, 5, 0, 1)))
+        .test(eq, "of=VDB(10000;1000;5;0;1;;TRUE())")
+        .q(Q);
+    test_ok(of::formula(of::vdb_factor_noswitch(
+        10000, 1000, 5, 0, 1, 1.5,
+    )))
+    .test(eq, "of=VDB(10000;1000;5;0;1;1.5;TRUE())")
+    .q(Q);
+    test_ok(of::formula(of::amorlinc_basis(
+        2400,
+        cell!(0, 0),
+        cell!(0, 1),
+        300,
+        1,
+        0.15,
+        DayCountBasis::ActualActual,
+    )))
+    .test(eq, "of=AMORLINC(2400;[.A1];[.B1];300;1;0.15;1)")
+    .q(Q);
+}
+
+#[test]
+fn test_statistic() {
+    test_ok(of::formula(of::max((cell!(0, 0), range!(1, 0, 9, 0)))))
+        .test(eq, "of=MAX([.A1];[.A2:.A10])")
+        .q(Q);
+    test_ok(of::formula(of::quartile(
+        range!(0, 0, 9, 0),
+        Quartile::Third,
+    )))
+    .test(eq, "of=QUARTILE([.A1:.A10];3)")
+    .q(Q);
+    test_ok(of::formula(of::rank_order(
+        cell!(0, 0),
+        range!(0, 0, 9, 0),
+        RankOrder::Ascending,
+    )))
+    .test(eq, "of=RANK([.A1];[.A1:.A10];1)")
+    .q(Q);
+    test_ok(of::formula(of::frequency(
+        range!(0, 0, 9, 0),
+        [[10, 20, 30]],
+    )))
+    .test(eq, "of=FREQUENCY([.A1:.A10];{10;20;30})")
+    .q(Q);
+    test_ok(of::formula(of::percentile(range!(0, 0, 9, 0), 0.9)))
+        .test(eq, "of=PERCENTILE([.A1:.A10];0.9)")
+        .q(Q);
+}
+
+#[test]
+fn test_distribution() {
+    test_ok(of::formula(of::normdist_cu(
+        cell!(0, 0),
+        0,
+        1,
+        DistMode::Cumulative,
+    )))
+    .test(eq, "of=NORMDIST([.A1];0;1;TRUE())")
+    .q(Q);
+    test_ok(of::formula(of::expondist_cu(1, 0.5, DistMode::Density)))
+        .test(eq, "of=EXPONDIST(1;0.5;FALSE())")
+        .q(Q);
+    test_ok(of::formula(of::legacy_tdist(2.5, 10, Tails::Two)))
+        .test(eq, "of=LEGACY.TDIST(2.5;10;2)")
+        .q(Q);
+    test_ok(of::formula(of::legacy_normsinv(0.95)))
+        .test(eq, "of=LEGACY.NORMSINV(0.95)")
+        .q(Q);
+    test_ok(of::formula(of::weibull(1, 2, 3, DistMode::Cumulative)))
+        .test(eq, "of=WEIBULL(1;2;3;TRUE())")
+        .q(Q);
+}
+
+#[test]
+fn test_hypothesis() {
+    test_ok(of::formula(of::ttest(
+        range!(0, 0, 9, 0),
+        range!(0, 1, 9, 1),
+        Tails::Two,
+        TTestType::Paired,
+    )))
+    .test(eq, "of=TTEST([.A1:.A10];[.B1:.B10];2;1)")
+    .q(Q);
+    test_ok(of::formula(of::ztest_sigma(range!(0, 0, 9, 0), 4, 1.5)))
+        .test(eq, "of=ZTEST([.A1:.A10];4;1.5)")
+        .q(Q);
+    test_ok(of::formula(of::ftest(
+        range!(0, 0, 9, 0),
+        range!(0, 1, 9, 1),
+    )))
+    .test(eq, "of=FTEST([.A1:.A10];[.B1:.B10])")
+    .q(Q);
+    test_ok(of::formula(of::chisq_test(
+        range!(0, 0, 1, 1),
+        range!(2, 0, 3, 1),
+    )))
+    .test(eq, "of=COM.MICROSOFT.CHISQ.TEST([.A1:.B2];[.A3:.B4])")
+    .q(Q);
+    test_ok(of::formula(of::confidence_t(0.05, 2.5, 50)))
+        .test(eq, "of=COM.MICROSOFT.CONFIDENCE.T(0.05;2.5;50)")
+        .q(Q);
+}
+
+#[test]
+fn test_regression() {
+    test_ok(of::formula(of::slope(
+        range!(0, 0, 9, 0),
+        range!(0, 1, 9, 1),
+    )))
+    .test(eq, "of=SLOPE([.A1:.A10];[.B1:.B10])")
+    .q(Q);
+    test_ok(of::formula(of::trend_new(
+        range!(0, 0, 9, 0),
+        range!(0, 1, 9, 1),
+        range!(10, 1, 12, 1),
+    )))
+    .test(eq, "of=TREND([.A1:.A10];[.B1:.B10];[.B11:.B13])")
+    .q(Q);
+    test_ok(of::formula(
+        of::linest_value(
+            range!(0, 0, 9, 0),
+            range!(0, 1, 9, 2),
+            2,
+            LinestStat::Coefficient(1),
+        )
+        .unwrap(),
+    ))
+    .test(
+        eq,
+        "of=INDEX(LINEST([.A1:.A10];[.B1:.C10];TRUE();TRUE());1;2)",
+    )
+    .q(Q);
+    test_ok(of::formula(
+        of::linest_value(
+            range!(0, 0, 9, 0),
+            range!(0, 1, 9, 1),
+            1,
+            LinestStat::RSquared,
+        )
+        .unwrap(),
+    ))
+    .test(
+        eq,
+        "of=INDEX(LINEST([.A1:.A10];[.B1:.B10];TRUE();TRUE());3;1)",
+    )
+    .q(Q);
+    test_ok(of::formula(
+        of::logest_value(
+            range!(0, 0, 9, 0),
+            range!(0, 1, 9, 1),
+            1,
+            LinestStat::Constant,
+        )
+        .unwrap(),
+    ))
+    .test(
+        eq,
+        "of=INDEX(LOGEST([.A1:.A10];[.B1:.B10];TRUE();TRUE());1;2)",
+    )
+    .q(Q);
+    assert!(of::linest_value(
+        range!(0, 0, 9, 0),
+        range!(0, 1, 9, 2),
+        2,
+        LinestStat::Coefficient(3)
+    )
+    .is_none());
+    assert!(of::linest_value(
+        range!(0, 0, 9, 0),
+        range!(0, 1, 9, 2),
+        2,
+        LinestStat::Coefficient(0)
+    )
+    .is_none());
+    assert!(of::logest_value(
+        range!(0, 0, 9, 0),
+        range!(0, 1, 9, 1),
+        1,
+        LinestStat::StdError(2)
+    )
+    .is_none());
+}
+
+#[test]
+fn test_engineering() {
+    test_ok(of::formula(of::dec2hex_places(
+        of::bitand(cell!(0, 0), 255),
+        2,
+    )))
+    .test(eq, "of=DEC2HEX(BITAND([.A1];255);2)")
+    .q(Q);
+    test_ok(of::formula(of::hex2dec("FF")))
+        .test(eq, "of=HEX2DEC(\"FF\")")
+        .q(Q);
+    test_ok(of::formula(of::bin2oct(1010)))
+        .test(eq, "of=BIN2OCT(1010)")
+        .q(Q);
+    test_ok(of::formula(of::base_len(42, 16, 4)))
+        .test(eq, "of=BASE(42;16;4)")
+        .q(Q);
+    test_ok(of::formula(of::roman_form(1999, RomanForm::Simplified)))
+        .test(eq, "of=ROMAN(1999;4)")
+        .q(Q);
+    test_ok(of::formula(of::arabic("MCMXCIX")))
+        .test(eq, "of=ARABIC(\"MCMXCIX\")")
+        .q(Q);
+}
+
+#[test]
+fn test_expr() {
+    let f = of::sum((cell!(0, 0), range!(1, 0, 9, 0))) * 2;
+    let e = f.expr();
+    match &e {
+        Expr::Infix { op, lhs, rhs } => {
+            assert_eq!(*op, InfixOp::Mul);
+            assert_eq!(**rhs, Expr::Number("2".into()));
+            match lhs.as_ref() {
+                Expr::Func { name, args } => {
+                    assert_eq!(name, "SUM");
+                    assert_eq!(args.len(), 2);
+                    assert_eq!(args[0], Expr::CellRef(cell!(0, 0)));
+                }
+                _ => panic!("not a function"),
+            }
+        }
+        _ => panic!("not an operator"),
+    }
+
+    let rewritten = FNumber::from(Expr::func(
+        "PRODUCT",
+        e.children().into_iter().cloned().collect(),
+    ));
+    test_ok(of::formula(rewritten))
+        .test(eq, "of=PRODUCT(SUM([.A1];[.A2:.A10]);2)")
+        .q(Q);
+
+    // Formulas can be built in one thread and written in another.
+    let f = of::sum((cell!(0, 0), range!(1, 0, 9, 0))) * 2;
+    let txt = std::thread::spawn(move || of::formula(f)).join().unwrap();
+    assert_eq!(txt, "of=SUM([.A1];[.A2:.A10])*2");
+}
+
+#[test]
+fn test_parse() {
+    for f in [
+        "of=SUM([.A1:.B3])*2",
+        "of=-[$Sheet1.$A$1]^2%",
+        "of=IF([.A1]<>\"say \"\"hi\"\"\";{1;2|3;4};#N/A)",
+        "of=COM.MICROSOFT.TEXTJOIN(\",\";TRUE();[.A1:.A3])",
+        "of=([.A1]+1)*([.B1]-1)&\"x\"",
+        "of=ROUND(1.5E-3;)+['My Sheet'.B2]",
+        "of=[.A1]:[.B2]!named_range",
+    ] {
+        test_ok(of::formula(of::parse(f).unwrap())).test(eq, f).q(Q);
+    }
+    test_ok(of::formula(of::parse("of:=sum( 1 ; 2 )").unwrap()))
+        .test(eq, "of=SUM(1;2)")
+        .q(Q);
+
+    let e = of::parse_expr("of=1+2*3^2").unwrap();
+    match e {
+        Expr::Infix { op, rhs, .. } => {
+            assert_eq!(op, InfixOp::Add);
+            assert!(matches!(
+                rhs.as_ref(),
+                Expr::Infix {
+                    op: InfixOp::Mul,
+                    ..
+                }
+            ));
+        }
+        _ => panic!("not an operator"),
+    }
+    match of::parse_expr("-2^2").unwrap() {
+        Expr::Infix { op, .. } => assert_eq!(op, InfixOp::Pow),
+        _ => panic!("not an operator"),
+    }
+
+    let err = of::parse("of=SUM(1;2").unwrap_err();
+    assert_eq!(err.kind, ParseErrorKind::Unclosed(')'));
+    assert_eq!(err.span, 3..10);
+    let err = of::parse("of=1+*2").unwrap_err();
+    assert_eq!(err.kind, ParseErrorKind::UnexpectedChar('*'));
+    assert_eq!(err.span, 5..6);
+    let err = of::parse("of=[.A0]").unwrap_err();
+    assert_eq!(err.kind, ParseErrorKind::InvalidReference);
+    assert_eq!(err.span, 3..8);
+    let err = of::parse("of={1;2|3}").unwrap_err();
+    assert_eq!(err.kind, ParseErrorKind::ArrayShape);
+    let err = of::parse("of=#FOO!").unwrap_err();
+    assert_eq!(err.kind, ParseErrorKind::InvalidError);
+    let err = of::parse_expr(&"(".repeat(100_000)).unwrap_err();
+    assert_eq!(err.kind, ParseErrorKind::TooDeep);
+    assert_eq!(err.span, of::MAX_DEPTH + 1..of::MAX_DEPTH + 1);
+    let err = of::parse_expr(&format!("{}1", "-".repeat(100_000))).unwrap_err();
+    assert_eq!(err.kind, ParseErrorKind::TooDeep);
+    let deep = format!(
+        "{}1{}",
+        "(".repeat(of::MAX_DEPTH),
+        ")".repeat(of::MAX_DEPTH)
+    );
+    let expr = of::parse_expr(&deep).unwrap();
+    assert_eq!(of::formula(expr), format!("of={}", deep));
+}
+
+#[test]
+fn test_eval() {
+    let mut sheet = Sheet::new("Sheet1");
+    sheet.set_value(0, 0, 1.5);
+    sheet.set_value(1, 0, 2.5);
+    sheet.set_value(2, 0, "text");
+    sheet.set_value(3, 0, true);
+    sheet.set_formula(4, 0, "of=SUM([.A1:.A4])*2");
+    sheet.set_formula(5, 0, "of=[.A6]+1");
+    let mut book = WorkBook::new_empty();
+    book.push_sheet(sheet);
+    let ev = Evaluator::new(&book, 0);
+
+    assert_eq!(ev.eval(of::sum(range!(0, 0, 3, 0))), EvalValue::Number(4.0));
+    assert_eq!(ev.eval(cell!(4, 0).n() / 2), EvalValue::Number(4.0));
+    assert_eq!(ev.eval_value(cell!(2, 0)), EvalValue::Text("text".into()));
+    assert_eq!(ev.eval(of::div(1, 0)), EvalValue::Error(ErrorValue::Div0));
+    assert_eq!(
+        ev.eval(of::iferror(of::div(1, 0), "none")),
+        EvalValue::Text("none".into())
+    );
+    assert_eq!(
+        ev.eval(of::if_then_else(cell!(0, 0).gt(1), "big", "small")),
+        EvalValue::Text("big".into())
+    );
+    assert_eq!(ev.eval(of::istext(cell!(2, 0))), EvalValue::Logical(true));
+    assert_eq!(ev.eval(of::isblank(cell!(9, 9))), EvalValue::Logical(true));
+    assert_eq!(
+        ev.eval(of::counta(range!(0, 0, 9, 0))),
+        EvalValue::Number(6.0)
+    );
+    assert_eq!(ev.eval(of::sqrt(-1)), EvalValue::Error(ErrorValue::Num));
+    assert_eq!(ev.eval(cell!(5, 0)), {
+        let mut r = CellRange::local(5, 0, 5, 0);
+        r.set_table("Sheet1");
+        EvalValue::Reference(r)
+    });
+    assert_eq!(
+        ev.eval_value(cell!(5, 0)),
+        EvalValue::Error(ErrorValue::Value)
+    );
+    assert_eq!(
+        ev.eval(of::parse("of=-2^2&\"x\"").unwrap()),
+        EvalValue::Text("4x".into())
+    );
+    assert_eq!(ev.eval(of::fact(5)), EvalValue::Number(120.0));
+    assert_eq!(ev.eval(of::fact(171)), EvalValue::Error(ErrorValue::Num));
+    assert_eq!(ev.eval(of::fact(1e18)), EvalValue::Error(ErrorValue::Num));
+    assert_eq!(
+        ev.eval(of::factdouble(1e18)),
+        EvalValue::Error(ErrorValue::Num)
+    );
+    assert_eq!(ev.eval(of::combin(5, 2)), EvalValue::Number(10.0));
+    assert_eq!(
+        ev.eval(of::combin(1e18, 5e17)),
+        EvalValue::Error(ErrorValue::Num)
+    );
+}
+
+#[test]
+fn test_eval_chain() {
+    // Each row sums all rows above, without memoizing this takes 2^n steps.
+    let mut sheet = Sheet::new("Sheet1");
+    sheet.set_value(0, 0, 1);
+    for row in 1..500 {
+        sheet.set_formula(row, 0, format!("of=SUM([.A1:.A{}])", row));
+    }
+    let mut book = WorkBook::new_empty();
+    book.push_sheet(sheet);
+    let ev = Evaluator::new(&book, 0);
+
+    assert_eq!(
+        ev.eval_value(cell!(499, 0)),
+        EvalValue::Number(2f64.powi(498))
+    );
+    assert_eq!(ev.eval_value(cell!(10, 0)), EvalValue::Number(512.0));
+}
+
+#[test]
+fn test_eval_large_range() {
+    let mut sheet = Sheet::new("Sheet1");
+    sheet.set_value(0, 0, 1);
+    sheet.set_value(1, 0, 2);
+    sheet.set_value(0, 1, 3);
+    let mut book = WorkBook::new_empty();
+    book.push_sheet(sheet);
+    let ev = Evaluator::new(&book, 0);
+
+    // Only the used area is read.
+    let all = of::parse_excel("=SUM(A:XFD)").unwrap();
+    assert_eq!(ev.eval(all), EvalValue::Number(6.0));
+    assert_eq!(
+        ev.eval(of::parse_expr("COUNTBLANK([.A1:.A1048576])").unwrap()),
+        EvalValue::Number(1048574.0)
+    );
+    assert_eq!(
+        ev.eval(of::sumproduct((range!(0, 0, 999, 0), range!(0, 1, 999, 1)))),
+        EvalValue::Number(3.0)
+    );
+    assert_eq!(
+        ev.eval(of::sumproduct((range!(0, 0, 9, 0), range!(0, 1, 8, 1)))),
+        EvalValue::Error(ErrorValue::Value)
+    );
+    assert_eq!(
+        ev.eval(of::sumx2py2((range!(0, 0, 999, 0), range!(0, 1, 999, 1)))),
+        EvalValue::Number(10.0)
+    );
+    assert_eq!(
+        ev.eval_value(range!(0, 0, 999, 1)),
+        EvalValue::Array(vec![
+            vec![EvalValue::Number(1.0), EvalValue::Number(3.0)],
+            vec![EvalValue::Number(2.0), EvalValue::Empty],
+        ])
+    );
+    assert_eq!(
+        ev.eval(range!(0, 1, 2, 1).n() + range!(0, 0, 2, 0).n()),
+        EvalValue::Array(vec![
+            vec![EvalValue::Number(4.0)],
+            vec![EvalValue::Number(2.0)],
+        ])
+    );
+}
+
+#[test]
+fn test_catalog() {
+    // Function names written by the builders must be in the catalog. The builders
+    // check this with a debug assertion, this also covers nested calls.
+    fn func_names(e: &Expr, names: &mut Vec<String>) {
+        if let Expr::Func { name, .. } = e {
+            names.push(name.to_string());
+        }
+        for v in e.children() {
+            func_names(v, names);
+        }
+    }
+    let a = &cell!(0, 0);
+    let r = &range!(0, 0, 9, 0);
+    let week = [[false, false, false, false, false, true, true]];
+    #[allow(unused_mut)]
+    let mut built: Vec<Expr> = vec![
+        of::bitand(1, 2).expr(),
+        of::bitlshift(1, 2).expr(),
+        of::imabs(of::complex(1, 2)).expr(),
+        of::imexp(of::imsum((a, "1+i"))).expr(),
+        (of::complex(1, 2) * of::complex(3, 4) / of::complex(1, 1)).expr(),
+        (of::complex(1, 2) + of::complex(3, 4) - of::complex(1, 1)).expr(),
+        of::dsum(r, 1, r).expr(),
+        of::dcount(r, r).expr(),
+        of::date(2024, 1, 1).expr(),
+        of::edate(a, 2).expr(),
+        of::weekday(a, WeekdayMethod::Monday0).expr(),
+        of::networkdays(a, a, r, week).expr(),
+        of::workday(a, 5, r, week).expr(),
+        of::base(10, 2).expr(),
+        of::bin2dec("101").expr(),
+        of::dec2hex(255).expr(),
+        of::dde("a", "b", "c").expr(),
+        of::hyperlink("http://x", a).expr(),
+        of::effect(0.1, 4).expr(),
+        of::npv(0.1, r).expr(),
+        of::pmt(0.1, 12, 1000).expr(),
+        of::xirr(r, r).expr(),
+        of::areas(r).expr(),
+        of::countif(r, (CriterionCmp::Gt, 1)).expr(),
+        of::error_type(a).expr(),
+        of::isnumber(a).expr(),
+        of::and((a, true)).expr(),
+        of::if_then_else(a.gt(1), 1, 2).expr(),
+        of::iferror(a, 0).expr(),
+        of::choose(1, (a, 2)).expr(),
+        of::index(r, 1, 1).expr(),
+        of::multiple_operations(a, a, a, a, a).expr(),
+        of::vlookup(a, r, 1, false).expr(),
+        of::abs(-1).expr(),
+        of::acot(1).expr(),
+        of::combin(5, 2).expr(),
+        of::fact(5).expr(),
+        of::sumifs(r, &[(r, (CriterionCmp::Gt, 1))]).expr(),
+        of::sumproduct(r).expr(),
+        of::mdeterm(r).expr(),
+        of::mmult(r, r).expr(),
+        of::ceiling2(2.5, 1).expr(),
+        of::floor_mode(2.5, RoundingMode::AwayFrom0).expr(),
+        of::mround(7, 2).expr(),
+        of::trunc(1.5).expr(),
+        of::average(r).expr(),
+        of::averageifs(r, &[(r, (CriterionCmp::Gt, 1))]).expr(),
+        of::betadist(0.5, 2, 3).expr(),
+        of::binom_dist_range(10, 0.5, 3).expr(),
+        of::legacy_chidist(1, 2).expr(),
+        of::chisq_test(r, r).expr(),
+        of::confidence_norm(0.05, 1, 10).expr(),
+        of::confidence_t(0.05, 1, 10).expr(),
+        of::legacy_fdist(1, 2, 3).expr(),
+        of::ftest(r, r).expr(),
+        of::legacy_normsinv(0.5).expr(),
+        of::percentile(r, 0.5).expr(),
+        of::quartile(r, Quartile::Median).expr(),
+        of::rank(1, r).expr(),
+        of::legacy_tdist(1, 2, Tails::Two).expr(),
+        of::linest_value(r, r, 1, LinestStat::RSquared)
+            .unwrap()
+            .expr(),
+        of::concatenate((a, "x")).expr(),
+        of::substitute(a, "a", "b").expr(),
+        of::text(1.5, "0.00").expr(),
+        of::textjoin(",", true, r).expr(),
+        of::bahttext(1).expr(),
+    ];
+    #[cfg(feature = "chrono")]
+    {
+        let d = chrono::NaiveDate::from_ymd_opt(2024, 3, 15).unwrap();
+        built.push(d.and_hms_opt(12, 0, 0).unwrap().expr());
+        built.push(of::edate(d, 1).expr());
+    }
+    let mut names = Vec::new();
+    for e in &built {
+        func_names(e, &mut names);
+    }
+    for name in &names {
+        assert!(of::function_info(name).is_some(), "{} not in catalog", name);
+    }
+    assert!(names.len() > built.len());
+
+    for (i, f) in of::functions().iter().enumerate() {
+        assert!(
+            of::functions()[..i].iter().all(|v| v.name != f.name),
+            "{} twice",
+            f.name
+        );
+        assert!(f
+            .max_args
+            .is_none_or(|max| f.min_args <= max && max <= f.params.len()));
+        assert_eq!(of::function_info(f.name), Some(f));
+        assert_eq!(of::function_info(&f.name.to_lowercase()), Some(f));
+    }
+    assert_eq!(of::function_info("NOSUCHFUNCTION"), None);
+
+    let sum = of::function_info("sum").unwrap();
+    assert_eq!(sum.name, "SUM");
+    assert!(sum.accepts(3));
+    assert!(!sum.accepts(0));
+    assert_eq!(sum.param_type(5), Some(ValueType::NumberSequenceList));
+    let chisq = of::function_info("COM.MICROSOFT.CHISQ.TEST").unwrap();
+    assert_eq!(chisq.namespace, Namespace::Microsoft);
+    assert_eq!(chisq.local_name(), "CHISQ.TEST");
+    assert_eq!(chisq.category, Category::Statistic);
+    assert!(of::function_info("NOW").unwrap().volatile);
+    assert_eq!(
+        of::function_info("ROUND").unwrap().to_string(),
+        "ROUND(Number;[Integer]) -> Number"
+    );
+
+    test_ok(of::formula(of::countif(
+        range!(0, 0, 9, 0),
+        (CriterionCmp::Eq, 1),
+    )))
+    .test(eq, "of=COUNTIF([.A1:.A10];\"=\"&1)")
+    .q(Q);
+    test_ok(of::formula(of::mmult(
+        range!(0, 0, 1, 1),
+        range!(0, 2, 1, 3),
+    )))
+    .test(eq, "of=MMULT([.A1:.B2];[.C1:.D2])")
+    .q(Q);
+    test_ok(of::formula(of::ceiling2(2.5, 1)))
+        .test(eq, "of=CEILING(2.5;1)")
+        .q(Q);
+}
+
+#[test]
+fn test_validate() {
+    test_ok(of::try_formula(of::sum((cell!(0, 0), 2))).unwrap())
+        .test(eq, "of=SUM([.A1];2)")
+        .q(Q);
+
+    let no_criteria: &[(CellRange, FCriterion)] = &[];
+    assert_eq!(
+        of::try_formula(of::sumifs(range!(0, 0, 9, 0), no_criteria)),
+        Err(ValidationError::ArgCount {
+            func: "SUMIFS".into(),
+            count: 1,
+            min: 3,
+            max: None
+        })
+    );
+    assert_eq!(
+        of::validate(&of::parse_expr("ABS(\"x\")").unwrap()),
+        Err(ValidationError::ArgType {
+            func: "ABS".into(),
+            index: 0,
+            expected: ValueType::Number
+        })
+    );
+    assert!(of::validate(&of::parse_expr("ABS(\"12\")").unwrap()).is_ok());
+    assert_eq!(
+        of::try_formula(of::sum(FAny::from(Expr::Array(
+            vec![vec![1.expr(), 2.expr()], vec![3.expr()]].into()
+        )))),
+        Err(ValidationError::RaggedArray {
+            row: 1,
+            len: 1,
+            expected: 2
+        })
+    );
+    assert_eq!(
+        of::validate(&of::parse_expr("AREAS(5)").unwrap()),
+        Err(ValidationError::ArgType {
+            func: "AREAS".into(),
+            index: 0,
+            expected: ValueType::ReferenceList
+        })
+    );
+    assert!(of::validate(&of::parse_expr("AREAS(INDIRECT(\"A1\"))").unwrap()).is_ok());
+    assert_eq!(
+        of::validate(&of::parse_expr("MID(\"abc\";;1)").unwrap()),
+        Err(ValidationError::MissingArgument {
+            func: "MID".into(),
+            index: 1
+        })
+    );
+    assert_eq!(
+        of::validate(&of::parse_expr("FOO(1)").unwrap()),
+        Err(ValidationError::UnknownFunction("FOO".into()))
+    );
+    assert_eq!(
+        of::try_formula(cell!(0, of::MAX_COLS)),
+        Err(ValidationError::OutOfBounds {
+            row: 0,
+            col: of::MAX_COLS
+        })
+    );
+    test_ok(
+        of::validate(&of::parse_expr("MID(\"abc\";1;)").unwrap())
+            .unwrap_err()
+            .to_string(),
+    )
+    .test(eq, "MID argument 3 is required")
+    .q(Q);
+}
+
+#[test]
+fn test_excel() {
+    test_ok(of::excel_formula(of::sum((cell!(0, 0), 2.5, true))).unwrap())
+        .test(eq, "=SUM(A1,2.5,TRUE)")
+        .q(Q);
+    test_ok(
+        of::excel_formula(of::if_then_else(
+            cell!("My Sheet" => 1, 1).n().gt(1),
+            "a\"b",
+            range!("Sheet1" => 0, 0, 9, 27).absolute(),
+        ))
+        .unwrap(),
+    )
+    .test(eq, "=IF('My Sheet'!B2>1,\"a\"\"b\",Sheet1!$A$1:$AB$10)")
+    .q(Q);
+    test_ok(
+        of::excel_formula(of::parse_expr("SUM([.A1:.B2]![.B1:.B3]~[.C1])*2").unwrap()).unwrap(),
+    )
+    .test(eq, "=SUM((A1:B2 B1:B3,C1))*2")
+    .q(Q);
+    test_ok(of::excel_formula(of::parse_expr("{1;2|3;4}").unwrap()).unwrap())
+        .test(eq, "={1,2;3,4}")
+        .q(Q);
+    test_ok(of::excel_formula(of::legacy_normsdist(0.5)).unwrap())
+        .test(eq, "=NORMSDIST(0.5)")
+        .q(Q);
+    test_ok(of::excel_formula(of::chisqdist(1, 2)).unwrap())
+        .test(eq, "=_xlfn.CHISQ.DIST(1,2,TRUE)")
+        .q(Q);
+    test_ok(of::excel_formula(of::normdist(1, 0, 1)).unwrap())
+        .test(eq, "=NORMDIST(1,0,1,TRUE)")
+        .q(Q);
+    test_ok(of::excel_formula(of::normdist_cu(1, 0, 1, DistMode::Density)).unwrap())
+        .test(eq, "=NORMDIST(1,0,1,FALSE)")
+        .q(Q);
+    test_ok(of::excel_formula(of::gammadist(1, 2, 3)).unwrap())
+        .test(eq, "=GAMMADIST(1,2,3,TRUE)")
+        .q(Q);
+    test_ok(of::excel_formula(of::expondist(1, 2)).unwrap())
+        .test(eq, "=EXPONDIST(1,2,TRUE)")
+        .q(Q);
+    test_ok(of::excel_formula(of::poisson(1, 2)).unwrap())
+        .test(eq, "=POISSON(1,2,TRUE)")
+        .q(Q);
+    test_ok(of::excel_formula(of::fdist(1, 2, 3)).unwrap())
+        .test(eq, "=_xlfn.F.DIST(1,2,3,TRUE)")
+        .q(Q);
+    test_ok(of::excel_formula(of::lognormdist(1)).unwrap())
+        .test(eq, "=_xlfn.LOGNORM.DIST(1,0,1,TRUE)")
+        .q(Q);
+    test_ok(of::excel_formula(of::lognormdist_param(1, 2, 3)).unwrap())
+        .test(eq, "=_xlfn.LOGNORM.DIST(1,2,3,TRUE)")
+        .q(Q);
+    test_ok(of::excel_formula(of::lognormdist_cu(1, 2, 3, DistMode::Density)).unwrap())
+        .test(eq, "=_xlfn.LOGNORM.DIST(1,2,3,FALSE)")
+        .q(Q);
+    test_ok(of::excel_formula(of::betadist(0.5, 2, 3)).unwrap())
+        .test(eq, "=BETADIST(0.5,2,3)")
+        .q(Q);
+    test_ok(of::excel_formula(of::betadist_range(0.5, 2, 3, 0, 2)).unwrap())
+        .test(eq, "=BETADIST(0.5,2,3,0,2)")
+        .q(Q);
+    test_ok(of::excel_formula(of::betadist_cu(0.5, 2, 3, 0, 2, DistMode::Density)).unwrap())
+        .test(eq, "=_xlfn.BETA.DIST(0.5,2,3,FALSE,0,2)")
+        .q(Q);
+    test_ok(of::excel_formula(of::ceiling(2.5)).unwrap())
+        .test(eq, "=CEILING(2.5,1)")
+        .q(Q);
+    test_ok(of::excel_formula(of::floor(2.5)).unwrap())
+        .test(eq, "=FLOOR(2.5,1)")
+        .q(Q);
+    test_ok(of::excel_formula(of::ceiling2(2.5, 2)).unwrap())
+        .test(eq, "=CEILING(2.5,2)")
+        .q(Q);
+    test_ok(of::excel_formula(of::ceiling_mode(-2.5, RoundingMode::AwayFrom0)).unwrap())
+        .test(eq, "=_xlfn.CEILING.MATH(-2.5,1,1)")
+        .q(Q);
+    test_ok(of::excel_formula(of::ceiling2_mode(-2.5, 2, RoundingMode::TowardsPlusInf)).unwrap())
+        .test(eq, "=_xlfn.CEILING.MATH(-2.5,2,0)")
+        .q(Q);
+    test_ok(of::excel_formula(of::floor_mode(-2.5, RoundingMode::AwayFrom0)).unwrap())
+        .test(eq, "=_xlfn.FLOOR.MATH(-2.5,1,1)")
+        .q(Q);
+    test_ok(of::excel_formula(of::floor2_mode(-2.5, 2, RoundingMode::AwayFrom0)).unwrap())
+        .test(eq, "=_xlfn.FLOOR.MATH(-2.5,2,1)")
+        .q(Q);
+    test_ok(of::excel_formula(of::round(2.5)).unwrap())
+        .test(eq, "=ROUND(2.5,0)")
+        .q(Q);
+    test_ok(of::excel_formula(of::roundup(2.5)).unwrap())
+        .test(eq, "=ROUNDUP(2.5,0)")
+        .q(Q);
+    test_ok(of::excel_formula(of::rounddown(2.5)).unwrap())
+        .test(eq, "=ROUNDDOWN(2.5,0)")
+        .q(Q);
+    test_ok(of::excel_formula(of::round_digits(2.5, 1)).unwrap())
+        .test(eq, "=ROUND(2.5,1)")
+        .q(Q);
+    test_ok(of::excel_formula(of::loginv(0.5)).unwrap())
+        .test(eq, "=LOGINV(0.5,0,1)")
+        .q(Q);
+    test_ok(of::excel_formula(of::loginv_param(0.5, 1, 2)).unwrap())
+        .test(eq, "=LOGINV(0.5,1,2)")
+        .q(Q);
+    test_ok(of::excel_formula(of::hypgeomdist(1, 4, 8, 20)).unwrap())
+        .test(eq, "=HYPGEOMDIST(1,4,8,20)")
+        .q(Q);
+    test_ok(of::excel_formula(of::hypgeomdist_cu(1, 4, 8, 20, DistMode::Cumulative)).unwrap())
+        .test(eq, "=_xlfn.HYPGEOM.DIST(1,4,8,20,TRUE)")
+        .q(Q);
+    test_ok(
+        of::excel_formula(of::networkdays(
+            cell!(0, 0),
+            cell!(0, 1),
+            range!(1, 0, 2, 0),
+            [[1, 0, 0, 0, 0, 0, 1]],
+        ))
+        .unwrap(),
+    )
+    .test(eq, "=_xlfn.NETWORKDAYS.INTL(A1,B1,\"0000011\",A2:A3)")
+    .q(Q);
+    test_ok(
+        of::excel_formula(of::workday(
+            cell!(0, 0),
+            5,
+            range!(1, 0, 2, 0),
+            [[true, false, false, false, false, true, false]],
+        ))
+        .unwrap(),
+    )
+    .test(eq, "=_xlfn.WORKDAY.INTL(A1,5,\"0000101\",A2:A3)")
+    .q(Q);
+    assert_eq!(
+        of::excel_formula(of::networkdays(
+            cell!(0, 0),
+            cell!(0, 1),
+            range!(1, 0, 2, 0),
+            [[
+                cell!(3, 0),
+                cell!(3, 1),
+                cell!(3, 2),
+                cell!(3, 3),
+                cell!(3, 4),
+                cell!(3, 5),
+                cell!(3, 6)
+            ]],
+        )),
+        Err(ExcelError::UnsupportedFunction("NETWORKDAYS".to_string()))
+    );
+    test_ok(of::excel_formula(of::ifna(cell!(0, 0), 0)).unwrap())
+        .test(eq, "=_xlfn.IFNA(A1,0)")
+        .q(Q);
+    assert_eq!(
+        of::excel_formula(of::dde("a", "b", "c")),
+        Err(ExcelError::UnsupportedFunction("DDE".into()))
+    );
+}
+
+#[test]
+fn test_r1c1() {
+    let anchor = cell!(4, 2);
+    let f = of::sum((
+        cell!(3, 2),
+        cell!(4, 4),
+        range!("Sheet 2" => 0, 0, 4, 2).absolute(),
+    ));
+    test_ok(of::formula_r1c1(f.clone(), &anchor))
+        .test(eq, "SUM([.R[-1]C];[.RC[2]];['Sheet 2'.R1C1:.R5C3])")
+        .q(Q);
+    test_ok(of::excel_formula_r1c1(f.clone(), &anchor).unwrap())
+        .test(eq, "=SUM(R[-1]C,RC[2],'Sheet 2'!R1C1:R5C3)")
+        .q(Q);
+
+    // Same formula for every row.
+    let rows: Vec<String> = (1..4)
+        .map(|row| of::formula_r1c1(cell!(row - 1, 0).n() * 2, &cell!(row, 0)))
+        .collect();
+    assert!(rows.iter().all(|v| v == "[.R[-1]C]*2"));
+
+    // Round trip through the parser.
+    let text = of::formula_r1c1(f.clone(), &anchor);
+    assert_eq!(of::parse_expr_r1c1(&text, &anchor), Ok(f.expr()));
+    assert_eq!(
+        of::parse_expr_r1c1("[.R[-1]C[+1]]", &anchor),
+        Ok(cell!(3, 3).expr())
+    );
+    assert_eq!(
+        of::parse_expr_r1c1("[.RC:.R[1]C]", &cell!(0, 0)),
+        Ok(range!(0, 0, 1, 0).expr())
+    );
+    assert!(of::parse_expr_r1c1("[.R[-1]C]", &cell!(0, 0)).is_err());
+    assert!(of::parse_expr_r1c1("[.A1]", &anchor).is_err());
+    assert!(of::parse_expr(&text).is_err());
+}
+
+#[test]
+fn test_ui_syntax() {
+    let de = UiSyntax::DE_DE;
+    let en = UiSyntax::for_locale("en-GB").unwrap();
+    assert_eq!(UiSyntax::for_locale("de_AT"), Some(de));
+    assert_eq!(UiSyntax::for_locale("xx"), None);
+
+    let f = of::sum((range!(0, 0, 2, 0), 2.5));
+    test_ok(of::ui_formula(f.clone(), &de))
+        .test(eq, "=SUMME(A1:A3;2,5)")
+        .q(Q);
+    test_ok(of::ui_formula(f, &en))
+        .test(eq, "=SUM(A1:A3,2.5)")
+        .q(Q);
+
+    let f = of::if_then_else(cell!("Sheet1" => 0, 0).absolute().n().gt(1), true, "x");
+    test_ok(of::ui_formula(f.clone(), &de))
+        .test(eq, "=WENN($Sheet1.$A$1>1;WAHR();\"x\")")
+        .q(Q);
+    test_ok(of::ui_formula(f, &en))
+        .test(eq, "=IF($Sheet1.$A$1>1,TRUE(),\"x\")")
+        .q(Q);
+
+    // Round trip between UI syntax and OpenFormula.
+    for (ui, of_txt) in [
+        ("=SUMME(A1:B3;2,5)", "of=SUM([.A1:.B3];2.5)"),
+        ("=summe($'My Sheet'.A1:B3)", "of=SUM(['My Sheet'.A1:.B3])"),
+        ("=LOG10(Sheet1.$B$2)*-1,5", "of=LOG10([$Sheet1.$B$2])*-1.5"),
+        ("=VERSCHIEBUNG(A1;1;)", "of=OFFSET([.A1];1;)"),
+        ("=MITTELWERT({1.2;3.4})", "of=AVERAGE({1;2|3;4})"),
+        ("=WENN(WAHR();Umsatz;0)", "of=IF(TRUE();Umsatz;0)"),
+    ] {
+        let expr = of::parse_ui(ui, &de).unwrap();
+        assert_eq!(of::formula(expr.clone()), of_txt, "{}", ui);
+        let back = of::parse_ui(&of::ui_formula(expr.clone(), &de), &de).unwrap();
+        assert_eq!(back, expr, "{}", ui);
+    }
+    let expr = of::parse_ui("=NORMSDIST(A1)+CHISQ.TEST(A1:A2,B1:B2)", &en).unwrap();
+    test_ok(of::formula(expr.clone()))
+        .test(
+            eq,
+            "of=LEGACY.NORMSDIST([.A1])+COM.MICROSOFT.CHISQ.TEST([.A1:.A2];[.B1:.B2])",
+        )
+        .q(Q);
+    test_ok(of::ui_formula(expr, &en))
+        .test(eq, "=NORMSDIST(A1)+CHISQ.TEST(A1:A2,B1:B2)")
+        .q(Q);
+    assert!(of::parse_ui("=SUMME(A1;", &de).is_err());
+
+    // Legacy right-tailed and current left-tailed distributions.
+    for (f, en_txt, de_txt) in [
+        (
+            of::legacy_tdist(2.5, 10, Tails::Two),
+            "=TDIST(2.5,10,2)",
+            "=TVERT(2,5;10;2)",
+        ),
+        (of::legacy_fdist(1, 2, 3), "=FDIST(1,2,3)", "=FVERT(1;2;3)"),
+        (
+            of::legacy_finv(0.5, 2, 3),
+            "=FINV(0.5,2,3)",
+            "=FINV(0,5;2;3)",
+        ),
+        (of::fdist(1, 2, 3), "=F.DIST(1,2,3)", "=F.VERT(1;2;3)"),
+        (of::finv(0.5, 2, 3), "=F.INV(0.5,2,3)", "=F.INV(0,5;2;3)"),
+    ] {
+        assert_eq!(of::ui_formula(f.clone(), &en), en_txt);
+        assert_eq!(of::ui_formula(f.clone(), &de), de_txt);
+        assert_eq!(of::parse_ui(en_txt, &en), Ok(f.expr()));
+        assert_eq!(of::parse_ui(de_txt, &de), Ok(f.expr()));
+    }
+
+    // Every localized name belongs to a known function.
+    for (name, _) in de.names.iter().chain(en.names) {
+        assert!(of::function_info(name).is_some(), "{}", name);
+    }
+    for (name, local) in [
+        ("NORMDIST", "NORMVERT"),
+        ("STDEVP", "STABWN"),
+        ("TRANSPOSE", "MTRANS"),
+        ("PROPER", "GROSS2"),
+        ("PERCENTILE", "QUANTIL"),
+        ("COM.MICROSOFT.TEXTJOIN", "TEXTVERKETTEN"),
+        ("ABS", "ABS"),
+    ] {
+        assert_eq!(de.local_name(name), local);
+    }
+    // Localized names are unique.
+    for f in of::functions() {
+        for syntax in [&de, &en] {
+            assert_eq!(syntax.canonical_name(syntax.local_name(f.name)), f.name);
+        }
+    }
+}
+
+#[test]
+fn test_excel_import() {
+    for (excel, of_txt) in [
+        ("=SUM(Sheet1!A1:B3, 2)", "of=SUM([Sheet1.A1:.B3];2)"),
+        (
+            "=IFERROR(VLOOKUP($A2,'My Sheet'!$A:$C,3,FALSE),\"\")",
+            "of=IFERROR(VLOOKUP([.$A2];[$'My Sheet'.$A$1:.$C$1048576];3;FALSE());\"\")",
+        ),
+        (
+            "=_xlfn.CHISQ.DIST(A1,2,TRUE)+CHISQ.TEST(A1:A2,B1:B2)",
+            "of=CHISQDIST([.A1];2;TRUE())+COM.MICROSOFT.CHISQ.TEST([.A1:.A2];[.B1:.B2])",
+        ),
+        (
+            "=FDIST(1,2,3)*NORMSDIST(0)",
+            "of=LEGACY.FDIST(1;2;3)*LEGACY.NORMSDIST(0)",
+        ),
+        (
+            "=_xlfn.LOGNORM.DIST(1,0,1,TRUE)+LOGNORMDIST(1,0,1)",
+            "of=LOGNORMDIST(1;0;1;TRUE())+LOGNORMDIST(1;0;1)",
+        ),
+        (
+            "=_xlfn.BETA.DIST(0.5,2,3,FALSE)+_xlfn.BETA.DIST(0.5,2,3,TRUE,0,2)",
+            "of=BETADIST(0.5;2;3;0;1;FALSE())+BETADIST(0.5;2;3;0;2;TRUE())",
+        ),
+        (
+            "=_xlfn.NORM.DIST(A1,0,1,TRUE)+_xlfn.NORM.INV(0.5,0,1)",
+            "of=NORMDIST([.A1];0;1;TRUE())+NORMINV(0.5;0;1)",
+        ),
+        (
+            "=_xlfn.STDEV.S(A1:A3)+_xlfn.STDEV.P(A1:A3)+_xlfn.VAR.S(A1:A3)",
+            "of=STDEV([.A1:.A3])+STDEVP([.A1:.A3])+VAR([.A1:.A3])",
+        ),
+        (
+            "=_xlfn.PERCENTILE.INC(A1:A3,0.5)+_xlfn.QUARTILE.INC(A1:A3,1)",
+            "of=PERCENTILE([.A1:.A3];0.5)+QUARTILE([.A1:.A3];1)",
+        ),
+        (
+            "=_xlfn.RANK.EQ(A1,A1:A3)+_xlfn.MODE.SNGL(A1:A3)",
+            "of=RANK([.A1];[.A1:.A3])+MODE([.A1:.A3])",
+        ),
+        (
+            "=_xlfn.POISSON.DIST(1,2,TRUE)+_xlfn.EXPON.DIST(1,2,TRUE)",
+            "of=POISSON(1;2;TRUE())+EXPONDIST(1;2;TRUE())",
+        ),
+        (
+            "=_xlfn.GAMMA.DIST(1,2,3,TRUE)+_xlfn.BINOM.DIST(1,4,0.5,FALSE)",
+            "of=GAMMADIST(1;2;3;TRUE())+BINOMDIST(1;4;0.5;FALSE())",
+        ),
+        (
+            "=_xlfn.CHISQ.DIST.RT(1,2)+_xlfn.F.DIST.RT(1,2,3)+_xlfn.T.INV.2T(0.5,2)",
+            "of=LEGACY.CHIDIST(1;2)+LEGACY.FDIST(1;2;3)+TINV(0.5;2)",
+        ),
+        ("=SUM(Sheet1:Sheet3!B2)", "of=SUM([Sheet1.B2:Sheet3.B2])"),
+        ("=SUM((A1,C1),2:3)", "of=SUM(([.A1]~[.C1]);[.$A2:.$XFD3])"),
+        ("=SUM(A1:C3 B2:D4)", "of=SUM([.A1:.C3]![.B2:.D4])"),
+        (
+            "=IF(A1>1,TRUE,\"x\")&{1,2;3,4}",
+            "of=IF([.A1]>1;TRUE();\"x\")&{1;2|3;4}",
+        ),
+    ] {
+        let expr = of::parse_excel(excel).unwrap();
+        assert_eq!(of::formula(expr), of_txt, "{}", excel);
+    }
+
+    assert_eq!(of::excel_import_name("_xlfn.F.DIST"), Ok("FDIST"));
+    assert_eq!(of::excel_import_name("concatenate"), Ok("CONCATENATE"));
+    assert_eq!(of::excel_import_name("stdev.s"), Ok("STDEV"));
+    assert_eq!(of::excel_name("STDEV"), Ok("STDEV"));
+    assert_eq!(
+        of::parse_excel("=XLOOKUP(1,A:A,B:B)"),
+        Err(ExcelError::UnsupportedFunction("XLOOKUP".to_string()))
+    );
+    assert!(matches!(
+        of::parse_excel("=Sheet1!Total*2"),
+        Err(ExcelError::Parse(e)) if e.kind == ParseErrorKind::InvalidReference
+    ));
+    assert!(of::parse_excel("=[1]Sheet1!A1").is_err());
+
+    // Round trip for formulas written with excel_formula().
+    let f = of::sumifs(
+        range!(0, 0, 4, 0),
+        &[(range!(0, 1, 4, 1), (CriterionCmp::Gt, 1))],
+    );
+    let excel = of::excel_formula(f.clone()).unwrap();
+    assert_eq!(of::parse_excel(&excel).map(of::formula), Ok(of::formula(f)));
+}
+
+#[test]
+fn test_error_value() {
+    test_ok(of::formula(of::if_then_else(
+        cell!(0, 0).gt(0),
+        cell!(0, 1).n() / cell!(0, 0),
+        FormulaError::Div0,
+    )))
+    .test(eq, "of=IF([.A1]>0;[.B1]/[.A1];#DIV/0!)")
+    .q(Q);
+    test_ok(of::formula(of::sum((1, ErrorValue::NA))))
+        .test(eq, "of=SUM(1;#N/A)")
+        .q(Q);
+    test_ok(of::formula(of::is_error_type(cell!(0, 0), ErrorValue::Ref)))
+        .test(eq, "of=IFERROR(ERROR.TYPE([.A1])=4;FALSE())")
+        .q(Q);
+    test_ok(of::formula(FError::from(ErrorValue::Name)))
+        .test(eq, "of=#NAME?")
+        .q(Q);
+    test_ok(of::excel_formula(ErrorValue::Num).unwrap())
+        .test(eq, "=#NUM!")
+        .q(Q);
+
+    for (i, e) in ErrorValue::ALL.iter().enumerate() {
+        assert_eq!(e.code(), i as u32 + 1);
+        assert_eq!(ErrorValue::from_code(e.code()), Some(*e));
+        assert_eq!(ErrorValue::from_literal(e.as_str()), Some(*e));
+        assert_eq!(of::parse_expr(&e.to_string()), Ok(Expr::Error(*e)));
+    }
+    assert_eq!(ErrorValue::from_code(0), None);
+
+    let mut book = WorkBook::new_empty();
+    book.push_sheet(Sheet::new("Sheet1"));
+    let ev = Evaluator::new(&book, 0);
+    assert_eq!(
+        ev.eval(of::error_type(ErrorValue::Value)),
+        EvalValue::Number(3.0)
+    );
+    assert_eq!(
+        ev.eval(of::is_error_type(ErrorValue::Ref, ErrorValue::Ref)),
+        EvalValue::Logical(true)
+    );
+    assert_eq!(
+        ev.eval(of::is_error_type(of::div(1, 0), ErrorValue::Ref)),
+        EvalValue::Logical(false)
+    );
+    assert_eq!(
+        ev.eval(of::is_error_type(1, ErrorValue::Ref)),
+        EvalValue::Logical(false)
+    );
+    assert_eq!(
+        ev.eval(of::if_then_else(of::is_error_type(1, ErrorValue::NA), 1, 2)),
+        EvalValue::Number(2.0)
+    );
+}
+
+#[cfg(feature = "chrono")]
+#[test]
+fn test_chrono() {
+    use chrono::{Duration, NaiveDate, NaiveTime};
+
+    let d = NaiveDate::from_ymd_opt(2024, 3, 15).unwrap();
+    let t = NaiveTime::from_hms_milli_opt(12, 30, 15, 500).unwrap();
+    test_ok(of::formula(of::edate(d, 2)))
+        .test(eq, "of=EDATE(DATE(2024;3;15);2)")
+        .q(Q);
+    test_ok(of::formula(d.and_time(t)))
+        .test(eq, "of=DATE(2024;3;15)+TIME(12;30;15.5)")
+        .q(Q);
+    test_ok(of::formula(cell!(0, 0).n() + Duration::hours(36)))
+        .test(eq, "of=[.A1]+1.5")
+        .q(Q);
+    test_ok(of::formula(Duration::nanoseconds(1)))
+        .test(eq, "of=1.1574074074074075E-14")
+        .q(Q);
+
+    let end = NaiveDate::from_ymd_opt(2024, 3, 29).unwrap();
+    let holiday = NaiveDate::from_ymd_opt(2024, 3, 25).unwrap();
+    test_ok(of::formula(of::networkdays(
+        d,
+        end,
+        [[of::serial(holiday)]],
+        [[0, 0, 0, 0, 0, 1, 1]],
+    )))
+    .test(
+        eq,
+        "of=NETWORKDAYS(DATE(2024;3;15);DATE(2024;3;29);{45376};{0;0;0;0;0;1;1})",
+    )
+    .q(Q);
+    test_ok(of::formula(
+        of::serial(d.and_hms_opt(12, 30, 15).unwrap())
+            .null_date(NaiveDate::from_ymd_opt(1904, 1, 1).unwrap()),
+    ))
+    .test(eq, "of=43904.521006944444")
+    .q(Q);
+    test_ok(of::formula(of::yearfrac(
+        of::serial(d),
+        of::serial(end),
+        DayCountBasis::USNasd30_360,
+    )))
+    .test(eq, "of=YEARFRAC(45366;45380;0)")
+    .q(Q);
+}
+
+#[test]
+fn test_float_literals() {
+    test_ok(of::formula(0.1 + 0.2))
+        .test(eq, "of=0.30000000000000004")
+        .q(Q);
+    test_ok(of::formula(0.1f32)).test(eq, "of=0.1").q(Q);
+    test_ok(of::formula(-0.0)).test(eq, "of=0").q(Q);
+    test_ok(of::formula(1e20)).test(eq, "of=1E20").q(Q);
+    test_ok(of::formula(-2.5e-12)).test(eq, "of=-2.5E-12").q(Q);
+    test_ok(of::formula(123456.789))
+        .test(eq, "of=123456.789")
+        .q(Q);
+    test_ok(of::formula(of::sum((f64::NAN, f64::INFINITY))))
+        .test(eq, "of=SUM(#NUM!;#NUM!)")
+        .q(Q);
+    for v in [1e20, -2.5e-12, 0.1 + 0.2, 1e-5, f64::MAX, f64::MIN_POSITIVE] {
+        let txt = of::formula(v);
+        assert!(of::parse_expr(&txt).is_ok(), "{}", txt);
+        assert_eq!(txt[3..].parse::<f64>(), Ok(v));
+    }
+}
+
+#[cfg(feature = "rust_decimal")]
+#[test]
+fn test_decimal() {
+    use rust_decimal::Decimal;
+
+    let price = Decimal::new(30, 2);
+    test_ok(of::formula(cell!(0, 0).n() * price))
+        .test(eq, "of=[.A1]*0.30")
+        .q(Q);
+    test_ok(of::formula(of::round_digits(Decimal::new(-12345, 3), 2)))
+        .test(eq, "of=ROUND(-12.345;2)")
+        .q(Q);
+}
+
+#[test]
+fn test_complex_ops() {
+    let z: FComplex = of::complex(1, 2);
+    test_ok(of::formula(z.clone() + "3-4i"))
+        .test(eq, "of=IMSUM(COMPLEX(1;2);\"3-4i\")")
+        .q(Q);
+    test_ok(of::formula(
+        (z.clone() - cell!(0, 0)) * of::imsum(range!(0, 1, 4, 1)),
+    ))
+    .test(
+        eq,
+        "of=IMPRODUCT(IMSUB(COMPLEX(1;2);[.A1]);IMSUM([.B1:.B5]))",
+    )
+    .q(Q);
+    test_ok(of::formula(of::imabs(z.clone() / 2).n() + 1))
+        .test(eq, "of=IMABS(IMDIV(COMPLEX(1;2);2))+1")
+        .q(Q);
+    let w = of::complex(0, 1);
+    test_ok(of::formula(of::imabs(&w) + 0))
+        .test(eq, "of=IMABS(COMPLEX(0;1))+0")
+        .q(Q);
+    test_ok(of::formula(w.clone() * &w))
+        .test(eq, "of=IMPRODUCT(COMPLEX(0;1);COMPLEX(0;1))")
+        .q(Q);
+    test_ok(of::formula(of::impower(z, 2)))
+        .test(eq, "of=IMPOWER(COMPLEX(1;2);2)")
+        .q(Q);
+}
+
+#[cfg(feature = "num-complex")]
+#[test]
+fn test_complex64() {
+    use num_complex::Complex64;
+
+    let z = Complex64::new(1.5, -2.0);
+    test_ok(of::formula(of::imreal(z)))
+        .test(eq, "of=IMREAL(COMPLEX(1.5;-2))")
+        .q(Q);
+    test_ok(of::formula(of::imsqrt(cell!(0, 0)) * z))
+        .test(eq, "of=IMPRODUCT(IMSQRT([.A1]);COMPLEX(1.5;-2))")
+        .q(Q);
+}